@@ -0,0 +1,433 @@
+//! An optional Elm / Bubble Tea style update loop (feature: `app`).
+//!
+//! Components stay usable on their own; this module only adds a thin driver for apps that prefer
+//! the `update` / `view` shape:
+//!
+//! - [`Model`] owns the app state, turns messages into new state via [`Model::update`] and draws it
+//!   via [`Model::view`].
+//! - [`Cmd`] describes follow-up work returned from `update` (emit another message, run a closure,
+//!   quit). The driver executes it synchronously on the loop thread; long-running work should be
+//!   started from a [`Cmd::perform`] closure and report back through [`Program::send`].
+//! - [`Program`] owns a ratatui [`Terminal`] and routes [`InputEvent`]s to the component returned
+//!   by [`Model::focused`], falling back to [`Model::on_input`].
+//!
+//! Everything is backend-generic, so a [`ratatui::backend::TestBackend`] drives the loop
+//! headlessly in tests.
+use crate::component::Component;
+use crate::component::ComponentAction;
+use crate::input::InputEvent;
+use ratatui::Frame;
+use ratatui::Terminal;
+use ratatui::backend::Backend;
+use ratatui::layout::Rect;
+use std::collections::VecDeque;
+
+/// Deferred work returned from [`Model::update`].
+#[derive(Default)]
+pub enum Cmd<Msg> {
+    #[default]
+    None,
+    /// Feeds a message back into [`Model::update`].
+    Msg(Msg),
+    /// Runs a closure on the loop thread; its message (if any) is fed back into `update`.
+    Perform(Box<dyn FnOnce() -> Option<Msg>>),
+    /// Runs several commands in order.
+    Batch(Vec<Cmd<Msg>>),
+    /// Stops [`Program::run`] once the messages queued before it have been processed.
+    Quit,
+}
+
+impl<Msg> Cmd<Msg> {
+    pub fn none() -> Self {
+        Self::None
+    }
+
+    pub fn msg(msg: Msg) -> Self {
+        Self::Msg(msg)
+    }
+
+    pub fn perform(f: impl FnOnce() -> Option<Msg> + 'static) -> Self {
+        Self::Perform(Box::new(f))
+    }
+
+    pub fn batch(cmds: impl IntoIterator<Item = Cmd<Msg>>) -> Self {
+        Self::Batch(cmds.into_iter().collect())
+    }
+
+    pub fn quit() -> Self {
+        Self::Quit
+    }
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+}
+
+impl<Msg: std::fmt::Debug> std::fmt::Debug for Cmd<Msg> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => f.write_str("None"),
+            Self::Msg(msg) => f.debug_tuple("Msg").field(msg).finish(),
+            Self::Perform(_) => f.write_str("Perform(..)"),
+            Self::Batch(cmds) => f.debug_tuple("Batch").field(cmds).finish(),
+            Self::Quit => f.write_str("Quit"),
+        }
+    }
+}
+
+/// The input target chosen by [`Model::focused`]: a component, the area it was rendered in, and a
+/// mapping from its action to an app message.
+pub struct Focused<'a, Msg> {
+    route: Box<dyn FnOnce(InputEvent) -> (bool, Option<Msg>) + 'a>,
+}
+
+impl<'a, Msg> Focused<'a, Msg> {
+    /// Routes events to `component` laid out in `area`.
+    ///
+    /// `map` returns `None` for actions the app does not care about. Only events the component
+    /// ignored (see [`ComponentAction::is_consumed`]) fall through to [`Model::on_input`], so typing
+    /// a letter into a focused text area never triggers a global shortcut bound to that letter.
+    pub fn new<C>(
+        component: &'a mut C,
+        area: Rect,
+        map: impl FnOnce(C::Action) -> Option<Msg> + 'a,
+    ) -> Self
+    where
+        C: Component + ?Sized,
+        C::Action: ComponentAction,
+    {
+        Self {
+            route: Box::new(move |event| {
+                let action = component.handle_event(area, event);
+                (action.is_consumed(), map(action))
+            }),
+        }
+    }
+
+    /// Returns whether the component consumed `event`, and the mapped message.
+    fn handle(self, event: InputEvent) -> (bool, Option<Msg>) {
+        (self.route)(event)
+    }
+}
+
+/// Application state driven by a [`Program`].
+pub trait Model {
+    type Msg;
+
+    /// Command to run once before the first frame.
+    fn init(&mut self) -> Cmd<Self::Msg> {
+        Cmd::none()
+    }
+
+    /// Applies `msg` to the state.
+    fn update(&mut self, msg: Self::Msg) -> Cmd<Self::Msg>;
+
+    /// Draws the current state.
+    fn view(&mut self, frame: &mut Frame);
+
+    /// The component that should receive input, if any.
+    fn focused(&mut self) -> Option<Focused<'_, Self::Msg>> {
+        None
+    }
+
+    /// Maps an event that the focused component ignored (global keys, resize, ...).
+    fn on_input(&mut self, event: &InputEvent) -> Option<Self::Msg> {
+        let _ = event;
+        None
+    }
+}
+
+/// A synchronous driver for a [`Model`] on any ratatui backend.
+pub struct Program<B: Backend, M: Model> {
+    terminal: Terminal<B>,
+    model: M,
+    queue: VecDeque<Queued<M::Msg>>,
+    quit: bool,
+}
+
+/// A pending step of the update loop. `Quit` is queued rather than applied at once so messages
+/// queued before it (e.g. earlier in the same [`Cmd::Batch`]) still reach `update`.
+enum Queued<Msg> {
+    Msg(Msg),
+    Quit,
+}
+
+impl<B: Backend, M: Model> Program<B, M> {
+    pub fn new(terminal: Terminal<B>, model: M) -> Self {
+        Self {
+            terminal,
+            model,
+            queue: VecDeque::new(),
+            quit: false,
+        }
+    }
+
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    pub fn model_mut(&mut self) -> &mut M {
+        &mut self.model
+    }
+
+    pub fn terminal(&self) -> &Terminal<B> {
+        &self.terminal
+    }
+
+    pub fn terminal_mut(&mut self) -> &mut Terminal<B> {
+        &mut self.terminal
+    }
+
+    pub fn into_parts(self) -> (Terminal<B>, M) {
+        (self.terminal, self.model)
+    }
+
+    /// Whether a [`Cmd::Quit`] has been executed.
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Runs [`Model::init`] and its resulting commands.
+    pub fn init(&mut self) {
+        let cmd = self.model.init();
+        self.exec(cmd);
+        self.drain();
+    }
+
+    /// Feeds `msg` into [`Model::update`] and runs any resulting commands.
+    pub fn send(&mut self, msg: M::Msg) {
+        self.queue.push_back(Queued::Msg(msg));
+        self.drain();
+    }
+
+    /// Routes `event` to the focused component (or [`Model::on_input`]) and processes the
+    /// resulting message. Returns `true` if a message was produced, i.e. a redraw is warranted.
+    pub fn handle_event(&mut self, event: InputEvent) -> bool {
+        let (consumed, routed) = match self.model.focused() {
+            Some(focused) => focused.handle(event.clone()),
+            None => (false, None),
+        };
+        let msg = if consumed {
+            routed
+        } else {
+            routed.or_else(|| self.model.on_input(&event))
+        };
+        match msg {
+            Some(msg) => {
+                self.send(msg);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Draws one frame via [`Model::view`].
+    pub fn draw(&mut self) -> Result<(), B::Error> {
+        let model = &mut self.model;
+        self.terminal.draw(|frame| model.view(frame))?;
+        Ok(())
+    }
+
+    /// Runs the loop: init, draw, then handle events from `next_event` until it returns `None` or
+    /// a [`Cmd::Quit`] is executed.
+    ///
+    /// `next_event` is where the app blocks on its input source (e.g. `crossterm::event::read`
+    /// mapped through `crate::crossterm_input`) and may also synthesize
+    /// [`InputEvent::Tick`]s.
+    pub fn run(
+        &mut self,
+        mut next_event: impl FnMut() -> Option<InputEvent>,
+    ) -> Result<(), B::Error> {
+        self.init();
+        while !self.quit {
+            self.draw()?;
+            let Some(event) = next_event() else {
+                break;
+            };
+            if let InputEvent::Resize { .. } = event {
+                self.terminal.autoresize()?;
+            }
+            self.handle_event(event);
+        }
+        Ok(())
+    }
+
+    fn drain(&mut self) {
+        while !self.quit {
+            match self.queue.pop_front() {
+                Some(Queued::Msg(msg)) => {
+                    let cmd = self.model.update(msg);
+                    self.exec(cmd);
+                }
+                Some(Queued::Quit) => {
+                    self.quit = true;
+                    self.queue.clear();
+                }
+                None => break,
+            }
+        }
+    }
+
+    fn exec(&mut self, cmd: Cmd<M::Msg>) {
+        match cmd {
+            Cmd::None => {}
+            Cmd::Msg(msg) => self.queue.push_back(Queued::Msg(msg)),
+            Cmd::Perform(f) => {
+                if let Some(msg) = f() {
+                    self.queue.push_back(Queued::Msg(msg));
+                }
+            }
+            Cmd::Batch(cmds) => {
+                for cmd in cmds {
+                    self.exec(cmd);
+                }
+            }
+            Cmd::Quit => self.queue.push_back(Queued::Quit),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::KeyCode;
+    use crate::input::KeyEvent;
+    use crate::textarea::TextArea;
+    use crate::textarea::TextAreaAction;
+    use ratatui::backend::TestBackend;
+
+    #[derive(Debug)]
+    enum Msg {
+        Edited,
+        Submitted(String),
+        Saved(usize),
+        Quit,
+    }
+
+    #[derive(Default)]
+    struct App {
+        input: TextArea,
+        input_area: Rect,
+        edits: usize,
+        saved: Vec<usize>,
+        submitted: Vec<String>,
+        ignore_edits: bool,
+    }
+
+    impl Model for App {
+        type Msg = Msg;
+
+        fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+            match msg {
+                Msg::Edited => {
+                    self.edits += 1;
+                    Cmd::none()
+                }
+                Msg::Submitted(text) => {
+                    let len = text.len();
+                    self.submitted.push(text);
+                    Cmd::perform(move || Some(Msg::Saved(len)))
+                }
+                Msg::Saved(len) => {
+                    self.saved.push(len);
+                    Cmd::none()
+                }
+                Msg::Quit => Cmd::quit(),
+            }
+        }
+
+        fn view(&mut self, frame: &mut Frame) {
+            self.input_area = frame.area();
+            self.input.render_ref(self.input_area, frame.buffer_mut());
+        }
+
+        fn focused(&mut self) -> Option<Focused<'_, Msg>> {
+            let track_edits = !self.ignore_edits;
+            Some(Focused::new(
+                &mut self.input,
+                self.input_area,
+                move |action| match action {
+                    TextAreaAction::Submitted(text) => Some(Msg::Submitted(text)),
+                    TextAreaAction::Changed if track_edits => Some(Msg::Edited),
+                    _ => None,
+                },
+            ))
+        }
+
+        fn on_input(&mut self, event: &InputEvent) -> Option<Msg> {
+            match event {
+                InputEvent::Key(key) if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) => {
+                    Some(Msg::Quit)
+                }
+                _ => None,
+            }
+        }
+    }
+
+    fn key(code: KeyCode) -> InputEvent {
+        InputEvent::Key(KeyEvent::new(code))
+    }
+
+    #[test]
+    fn routes_input_to_focused_component_and_runs_commands() {
+        let terminal = Terminal::new(TestBackend::new(10, 2)).unwrap();
+        let mut program = Program::new(terminal, App::default());
+
+        let mut events = vec![
+            key(KeyCode::Char('h')),
+            key(KeyCode::Char('i')),
+            key(KeyCode::Enter),
+            key(KeyCode::Esc),
+            key(KeyCode::Char('x')),
+        ]
+        .into_iter();
+        program.run(|| events.next()).unwrap();
+
+        assert!(program.should_quit());
+        assert_eq!(events.len(), 1);
+        let app = program.model();
+        assert_eq!(app.edits, 2);
+        assert_eq!(app.submitted, vec!["hi".to_string()]);
+        assert_eq!(app.saved, vec![2]);
+        assert_eq!(app.input_area, Rect::new(0, 0, 10, 2));
+    }
+
+    #[test]
+    fn events_consumed_by_the_focused_component_skip_global_shortcuts() {
+        let terminal = Terminal::new(TestBackend::new(10, 2)).unwrap();
+        let app = App {
+            ignore_edits: true,
+            ..App::default()
+        };
+        let mut program = Program::new(terminal, app);
+        program.draw().unwrap();
+
+        // `q` is a global quit key, but the text area consumes it even though the app maps the
+        // resulting `Changed` action to no message.
+        assert!(!program.handle_event(key(KeyCode::Char('q'))));
+        assert!(!program.should_quit());
+        assert_eq!(program.model().input.text(), "q");
+
+        // Esc is ignored by the text area and reaches `on_input`.
+        assert!(program.handle_event(key(KeyCode::Esc)));
+        assert!(program.should_quit());
+    }
+
+    #[test]
+    fn batch_runs_in_order_and_stops_at_quit() {
+        let terminal = Terminal::new(TestBackend::new(4, 1)).unwrap();
+        let mut program = Program::new(terminal, App::default());
+        program.send(Msg::Edited);
+        assert_eq!(program.model().edits, 1);
+
+        let cmd = Cmd::batch([
+            Cmd::msg(Msg::Saved(1)),
+            Cmd::quit(),
+            Cmd::msg(Msg::Saved(2)),
+        ]);
+        program.exec(cmd);
+        program.drain();
+        assert!(program.should_quit());
+        assert_eq!(program.model().saved, [1]);
+    }
+}
@@ -4,7 +4,9 @@ use ratatui::text::Span;
 use ratatui::text::Text;
 use unicode_width::UnicodeWidthStr;
 
+use crate::tabs::TabPolicy;
 use crate::text::CodeHighlighter;
+use crate::theme::Theme;
 
 /// Styling and layout options for the `render_code_lines` render core.
 ///
@@ -20,6 +22,8 @@ pub struct CodeRenderOptions {
     pub line_number_start: usize,
     /// Separator after the line number gutter (e.g. `" │ "`).
     pub line_number_separator: &'static str,
+    /// How tabs in the code are expanded.
+    pub tabs: TabPolicy,
 }
 
 impl Default for CodeRenderOptions {
@@ -28,6 +32,7 @@ impl Default for CodeRenderOptions {
             show_line_numbers: false,
             line_number_start: 1,
             line_number_separator: " │ ",
+            tabs: TabPolicy::default(),
         }
     }
 }
@@ -40,6 +45,16 @@ pub struct CodeRenderStyles {
     pub gutter: Style,
 }
 
+impl CodeRenderStyles {
+    /// Uses [`crate::theme::CodeTheme::text`] and [`crate::theme::CodeTheme::line_number`].
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            base: theme.code.text,
+            gutter: theme.code.line_number,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RenderedCode {
     /// Fully materialized lines ready for drawing via `Paragraph`/custom rendering.
@@ -133,7 +148,9 @@ pub fn render_code_lines<S: AsRef<str>>(
         for s in &mut code_spans {
             s.style = styles.base.patch(s.style);
         }
-        spans.extend(code_spans);
+        let mut code = Line::from(code_spans);
+        options.tabs.expand_line_for_display(&mut code);
+        spans.extend(code.spans);
 
         let plain = join_spans_plain(&spans);
         max_w = max_w.max(UnicodeWidthStr::width(plain.as_str()) as u32);
@@ -222,6 +239,7 @@ mod tests {
                 show_line_numbers: true,
                 line_number_start: 10,
                 line_number_separator: " | ",
+                ..Default::default()
             },
         );
 
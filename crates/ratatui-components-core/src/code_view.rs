@@ -1,31 +1,52 @@
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Modifier;
 use ratatui::text::Span;
 use ratatui::text::Text;
 use unicode_width::UnicodeWidthStr;
 
+use crate::component::Component;
+use crate::help;
+use crate::help::BindingGroup;
+use crate::input::ClickTracker;
 use crate::input::InputEvent;
+use crate::input::KeyEvent;
 use crate::input::MouseButton;
 use crate::input::MouseEvent;
 use crate::input::MouseEventKind;
+use crate::keymap::ChordMatch;
+use crate::keymap::ChordState;
+use crate::keymap::Keymap;
 use crate::render;
+use crate::render::MarkerKind;
+use crate::render::ScrollbarLayout;
+use crate::render::ScrollbarMarker;
+use crate::scroll::ScrollAction;
 use crate::scroll::ScrollBindings;
+use crate::selection;
 use crate::selection::SelectionAction;
 use crate::selection::SelectionBindings;
+use crate::selection::VisualSelection;
+use crate::selection::VisualStep;
+use crate::tabs::TabPolicy;
 use crate::text::CodeHighlighter;
 use crate::theme::Theme;
+use crate::viewport::ScrollAnimator;
 use crate::viewport::ViewportState;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::time::Instant;
 
 #[derive(Clone, Debug)]
 pub struct CodeViewOptions {
     pub show_line_numbers: bool,
     pub show_scrollbar: bool,
+    /// Draws a horizontal scrollbar on the bottom row.
+    pub show_scrollbar_x: bool,
     pub scroll: ScrollBindings,
     pub enable_selection: bool,
     pub selection: SelectionBindings,
+    /// How tabs are expanded; applies to code set after the change.
+    pub tabs: TabPolicy,
 }
 
 impl Default for CodeViewOptions {
@@ -33,13 +54,25 @@ impl Default for CodeViewOptions {
         Self {
             show_line_numbers: true,
             show_scrollbar: true,
+            show_scrollbar_x: false,
             scroll: ScrollBindings::default(),
             enable_selection: true,
             selection: SelectionBindings::default(),
+            tabs: TabPolicy::default(),
         }
     }
 }
 
+impl CodeViewOptions {
+    /// Describes the configured bindings for help screens (see [`help::HelpPanel`]).
+    pub fn help_groups(&self) -> Vec<BindingGroup> {
+        help::view_help_groups(
+            &self.scroll,
+            self.enable_selection.then_some(&self.selection),
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 struct VisibleHighlightCache {
     start: usize,
@@ -51,8 +84,15 @@ struct VisibleHighlightCache {
 #[derive(Clone, Default)]
 pub struct CodeView {
     lines: Vec<String>,
+    /// Tab start columns per line, for drawing the tab glyph; empty when the code has no tabs.
+    tab_cols: Vec<Vec<u32>>,
     max_content_width: u16,
     pub state: ViewportState,
+    scroll_chord: ChordState,
+    /// Built from `options.scroll` on first use and dropped when the options change.
+    scroll_keymap: Option<Keymap<ScrollAction>>,
+    scroll_animator: ScrollAnimator,
+    clicks: ClickTracker,
     options: CodeViewOptions,
     highlighter: Option<std::sync::Arc<dyn CodeHighlighter + Send + Sync>>,
     language: Option<String>,
@@ -60,6 +100,11 @@ pub struct CodeView {
     highlight_scratch: String,
     selection_anchor: Option<(usize, u32)>,
     selection: Option<((usize, u32), (usize, u32))>,
+    hover_row: Option<u16>,
+    scrollbar_markers: Vec<ScrollbarMarker>,
+    visual: VisualSelection,
+    /// Inverted so the derived `Default` starts focused.
+    unfocused: bool,
 }
 
 impl CodeView {
@@ -80,6 +125,8 @@ impl CodeView {
 
     pub fn set_options(&mut self, options: CodeViewOptions) {
         self.options = options;
+        self.scroll_keymap = None;
+        self.scroll_chord.clear();
     }
 
     pub fn handle_event(&mut self, event: InputEvent) -> bool {
@@ -89,31 +136,119 @@ impl CodeView {
     pub fn handle_event_action(&mut self, event: InputEvent) -> SelectionAction {
         match event {
             InputEvent::Paste(_) => SelectionAction::None,
-            InputEvent::Mouse(_) => SelectionAction::None,
-            InputEvent::Key(key) => {
-                if self.options.enable_selection && self.options.selection.is_clear(&key) {
-                    self.clear_selection();
-                    return SelectionAction::Redraw;
+            InputEvent::Resize { .. } | InputEvent::FocusGained => SelectionAction::None,
+            InputEvent::FocusLost => {
+                // Drop half-typed chords and any in-progress drag; the release may never arrive.
+                self.scroll_chord.clear();
+                self.selection_anchor = None;
+                SelectionAction::Redraw
+            }
+            InputEvent::Tick(now) => {
+                let mut redraw =
+                    self.options
+                        .scroll
+                        .tick(&mut self.scroll_animator, &mut self.state, now);
+                if let ChordMatch::Matched(action) = self
+                    .scroll_keymap
+                    .get_or_insert_with(|| self.options.scroll.keymap())
+                    .expire(&mut self.scroll_chord, now)
+                {
+                    self.apply_scroll_action(action);
+                    redraw = true;
                 }
-                if self.options.enable_selection && self.options.selection.is_copy(&key) {
-                    return self
-                        .selected_text()
-                        .map(SelectionAction::CopyRequested)
-                        .unwrap_or(SelectionAction::None);
+                if redraw {
+                    SelectionAction::Redraw
+                } else {
+                    SelectionAction::None
                 }
-                let Some(action) = self.options.scroll.action_for(&key) else {
-                    return SelectionAction::None;
-                };
-                self.options.scroll.apply(&mut self.state, action);
-                SelectionAction::Redraw
             }
+            InputEvent::Mouse(_) => SelectionAction::None,
+            InputEvent::Key(key) => self.handle_key_event_at(key, Instant::now()),
+        }
+    }
+
+    /// Handles a key received at `now`.
+    ///
+    /// The timestamp drives [`ScrollBindings::chord_timeout`]; a prefix left pending is
+    /// resolved by a later [`InputEvent::Tick`].
+    pub fn handle_key_event_at(&mut self, key: KeyEvent, now: Instant) -> SelectionAction {
+        let action = self.handle_key(key, now);
+        // A bound chord prefix fired because the key could not continue it; handle the key too.
+        match self.scroll_chord.take_replay() {
+            Some(key) => match self.handle_key_event_at(key, now) {
+                SelectionAction::None => action,
+                replayed => replayed,
+            },
+            None => action,
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, now: Instant) -> SelectionAction {
+        if self.options.enable_selection && self.options.selection.is_clear(&key) {
+            self.clear_selection();
+            return SelectionAction::Redraw;
+        }
+        if self.options.enable_selection && self.options.selection.is_copy(&key) {
+            let text = self.selected_text();
+            if self.visual.is_active() {
+                self.clear_selection();
+            }
+            return text
+                .map(SelectionAction::CopyRequested)
+                .unwrap_or(SelectionAction::None);
+        }
+        if self.options.enable_selection {
+            let step = self.visual.step(
+                &key,
+                &self.options.selection,
+                self.scroll_keymap
+                    .get_or_insert_with(|| self.options.scroll.keymap()),
+                &mut self.scroll_chord,
+                Some(now),
+            );
+            if let Some(action) = self.apply_visual_step(step) {
+                return action;
+            }
+        }
+        let action = match self
+            .scroll_keymap
+            .get_or_insert_with(|| self.options.scroll.keymap())
+            .feed(&mut self.scroll_chord, &key, Some(now))
+        {
+            ChordMatch::Matched(action) => action,
+            ChordMatch::Pending => return SelectionAction::Redraw,
+            ChordMatch::NoMatch => return SelectionAction::None,
+        };
+        self.options.scroll.apply(&mut self.state, action);
+        SelectionAction::Redraw
+    }
+
+    fn apply_visual_step(&mut self, step: VisualStep) -> Option<SelectionAction> {
+        let (lines, state) = (&self.lines, &mut self.state);
+        let action = self
+            .visual
+            .apply(step, state, lines.len(), |l| lines[l].clone())?;
+        self.selection_anchor = None;
+        self.selection = self.visual.selection();
+        Some(action)
+    }
+
+    /// Applies a scroll action resolved outside a key press, moving the visual caret if active.
+    fn apply_scroll_action(&mut self, action: ScrollAction) {
+        if self.visual.is_active() {
+            self.apply_visual_step(VisualStep::Move(action.into()));
+        } else {
+            self.options.scroll.apply(&mut self.state, action);
         }
     }
 
     pub fn handle_event_in_area(&mut self, area: Rect, event: InputEvent) -> bool {
         match event {
-            InputEvent::Key(_) => self.handle_event(event),
+            InputEvent::Key(_) | InputEvent::Tick(_) | InputEvent::FocusLost => {
+                self.handle_event(event)
+            }
             InputEvent::Paste(_) => false,
+            InputEvent::Resize { .. } | InputEvent::FocusGained => false,
             InputEvent::Mouse(m) => self.handle_mouse_event(area, m),
         }
     }
@@ -125,7 +260,10 @@ impl CodeView {
     ) -> SelectionAction {
         match event {
             InputEvent::Paste(_) => SelectionAction::None,
-            InputEvent::Key(_) => self.handle_event_action(event),
+            InputEvent::Resize { .. } | InputEvent::FocusGained => SelectionAction::None,
+            InputEvent::Key(_) | InputEvent::Tick(_) | InputEvent::FocusLost => {
+                self.handle_event_action(event)
+            }
             InputEvent::Mouse(m) => {
                 if self.handle_mouse_event(area, m) {
                     SelectionAction::Redraw
@@ -137,16 +275,19 @@ impl CodeView {
     }
 
     pub fn handle_mouse_event(&mut self, area: Rect, event: MouseEvent) -> bool {
+        self.handle_mouse_event_at(area, event, Instant::now())
+    }
+
+    /// Like [`Self::handle_mouse_event`], but with the time the event was received.
+    ///
+    /// The timestamp drives double-click (select word) and triple-click (select line) detection.
+    pub fn handle_mouse_event_at(&mut self, area: Rect, event: MouseEvent, now: Instant) -> bool {
         if area.width == 0 || area.height == 0 {
             return false;
         }
         self.set_viewport(area);
 
-        let content_area = if self.options.show_scrollbar && area.width >= 2 {
-            Rect::new(area.x, area.y, area.width - 1, area.height)
-        } else {
-            area
-        };
+        let content_area = self.scrollbar_layout(area).content;
 
         let gutter_w = if self.options.show_line_numbers {
             (digits(self.lines.len()).saturating_add(1) as u16).min(content_area.width)
@@ -156,13 +297,31 @@ impl CodeView {
 
         match event.kind {
             MouseEventKind::ScrollUp => {
-                self.state.scroll_y_by(-3);
+                self.options
+                    .scroll
+                    .wheel(&mut self.scroll_animator, &mut self.state, -3, now);
                 return true;
             }
             MouseEventKind::ScrollDown => {
-                self.state.scroll_y_by(3);
+                self.options
+                    .scroll
+                    .wheel(&mut self.scroll_animator, &mut self.state, 3, now);
+                return true;
+            }
+            MouseEventKind::ScrollLeft => {
+                self.state.scroll_x_by(-self.options.scroll.horiz_step);
+                return true;
+            }
+            MouseEventKind::ScrollRight => {
+                self.state.scroll_x_by(self.options.scroll.horiz_step);
                 return true;
             }
+            MouseEventKind::Moved => {
+                let row = event.position_in(content_area).map(|(_, row)| row);
+                let changed = row != self.hover_row;
+                self.hover_row = row;
+                return changed;
+            }
             _ => {}
         }
 
@@ -223,8 +382,18 @@ impl CodeView {
             .min(self.lines.len().saturating_sub(1) as u32) as usize;
         let col = self.state.x.saturating_add(rel_x);
 
+        let click = self.clicks.on_mouse(&event, now);
         match event.kind {
             MouseEventKind::Down(MouseButton::Left) => {
+                self.visual.stop();
+                let count = click.map_or(1, |c| c.count);
+                if let Some(sel) = selection::click_selection(count, line, col, || {
+                    self.lines.get(line).cloned().unwrap_or_default()
+                }) {
+                    self.selection_anchor = None;
+                    self.selection = Some(sel);
+                    return true;
+                }
                 self.selection_anchor = Some((line, col));
                 self.selection = Some(((line, col), (line, col)));
                 true
@@ -251,28 +420,36 @@ impl CodeView {
         }
     }
 
+    /// The line under the mouse pointer, tracked from [`MouseEventKind::Moved`] events.
+    pub fn hovered_line(&self) -> Option<usize> {
+        let line = self.state.y as usize + self.hover_row? as usize;
+        (line < self.lines.len()).then_some(line)
+    }
+
+    pub fn is_focused(&self) -> bool {
+        !self.unfocused
+    }
+
+    /// Sets focus. While unfocused the visual-mode caret is drawn with
+    /// [`crate::theme::ChromeTheme::cursor_unfocused`].
+    pub fn set_focused(&mut self, focused: bool) {
+        self.unfocused = !focused;
+    }
+
     pub fn clear_selection(&mut self) {
         self.selection_anchor = None;
         self.selection = None;
+        self.visual.stop();
     }
 
     pub fn selected_text(&self) -> Option<String> {
-        let ((l0, c0), (l1, c1)) = self.selection?;
-        let ((start_line, start_col), (end_line, end_col)) =
-            normalize_sel_inclusive((l0, c0), (l1, c1));
+        let sel = self.selection?;
+        let (start_line, end_line) = (sel.0.0.min(sel.1.0), sel.0.0.max(sel.1.0));
 
         let mut out = String::new();
         for line_idx in start_line..=end_line {
             let line = self.lines.get(line_idx)?;
-            let (from, to) = if start_line == end_line {
-                (start_col, end_col)
-            } else if line_idx == start_line {
-                (start_col, u32::MAX)
-            } else if line_idx == end_line {
-                (0, end_col)
-            } else {
-                (0, u32::MAX)
-            };
+            let (from, to) = selection::line_cols(sel, self.visual.is_block(), line_idx)?;
 
             if line_idx > start_line {
                 out.push('\n');
@@ -299,7 +476,7 @@ impl CodeView {
     }
 
     pub fn set_code(&mut self, code: &str) {
-        let mut lines = code.lines().map(normalize_tabs).collect::<Vec<_>>();
+        let mut lines = code.lines().map(str::to_string).collect::<Vec<_>>();
         if code.ends_with('\n') {
             lines.push(String::new());
         }
@@ -307,9 +484,12 @@ impl CodeView {
     }
 
     pub fn set_lines(&mut self, mut lines: Vec<String>) {
-        for l in &mut lines {
-            if l.contains('\t') {
-                *l = normalize_tabs(l);
+        self.tab_cols.clear();
+        if lines.iter().any(|l| l.contains('\t')) {
+            let tabs = self.options.tabs;
+            self.tab_cols = lines.iter().map(|l| tabs.tab_cols([l.as_str()])).collect();
+            for l in &mut lines {
+                *l = tabs.expand(l).into_owned();
             }
         }
 
@@ -333,11 +513,7 @@ impl CodeView {
     }
 
     pub fn set_viewport(&mut self, area: Rect) {
-        let content_area = if self.options.show_scrollbar && area.width >= 2 {
-            Rect::new(area.x, area.y, area.width - 1, area.height)
-        } else {
-            area
-        };
+        let content_area = self.scrollbar_layout(area).content;
 
         let gutter_w = if self.options.show_line_numbers {
             let digits = digits(self.lines.len());
@@ -356,14 +532,8 @@ impl CodeView {
 
         self.set_viewport(area);
 
-        let (content_area, scrollbar_x) = if self.options.show_scrollbar && area.width >= 2 {
-            (
-                Rect::new(area.x, area.y, area.width - 1, area.height),
-                Some(area.x + area.width - 1),
-            )
-        } else {
-            (area, None)
-        };
+        let layout = self.scrollbar_layout(area);
+        let content_area = layout.content;
 
         let gutter_w = if self.options.show_line_numbers {
             (digits(self.lines.len()).saturating_add(1) as u16).min(content_area.width)
@@ -372,17 +542,23 @@ impl CodeView {
         };
         let content_w = content_area.width.saturating_sub(gutter_w);
 
-        let (base_style, gutter_style) =
-            if let Some(bg) = self.highlighter.as_ref().and_then(|h| h.background_color()) {
-                (theme.text_primary.bg(bg), theme.text_muted.bg(bg))
-            } else {
-                (theme.text_primary, theme.text_muted)
-            };
+        // Monochrome output drops highlighting entirely, including its modifiers.
+        let highlight = self.highlighter.is_some() && !theme.is_monochrome();
+        let code_bg = self
+            .highlighter
+            .as_ref()
+            .filter(|_| highlight)
+            .and_then(|h| h.background_color());
+        let (base_style, gutter_style) = if let Some(bg) = code_bg {
+            (theme.code.text.bg(bg), theme.code.line_number.bg(bg))
+        } else {
+            (theme.code.text, theme.code.line_number)
+        };
 
         let start = self.state.y as usize;
         let end = (start + content_area.height as usize).min(self.lines.len());
 
-        let highlighted_visible = if self.highlighter.is_some() && content_w > 0 && start < end {
+        let highlighted_visible = if highlight && content_w > 0 && start < end {
             Some(self.highlight_visible_cached(start, end))
         } else {
             None
@@ -429,32 +605,16 @@ impl CodeView {
             {
                 spans.clone()
             } else {
-                vec![Span::styled(line.clone(), theme.text_primary)]
+                vec![Span::styled(line.clone(), theme.code.text)]
             };
 
             if self.options.enable_selection
-                && let Some(((l0, c0), (l1, c1))) = self.selection
+                && let Some(sel) = self.selection
+                && let Some((from, to)) = selection::line_cols(sel, self.visual.is_block(), idx)
+                && let Some((bs, be)) = render::byte_range_for_cols(line, from, to)
             {
-                let ((start_line, start_col), (end_line, end_col)) =
-                    normalize_sel_inclusive((l0, c0), (l1, c1));
-                if idx >= start_line && idx <= end_line {
-                    let (from, to) = if start_line == end_line {
-                        (start_col, end_col)
-                    } else if idx == start_line {
-                        (start_col, u32::MAX)
-                    } else if idx == end_line {
-                        (0, end_col)
-                    } else {
-                        (0, u32::MAX)
-                    };
-                    if let Some((bs, be)) = render::byte_range_for_cols(line, from, to) {
-                        spans = render::apply_modifier_to_byte_ranges(
-                            spans,
-                            &[(bs, be)],
-                            Modifier::REVERSED,
-                        );
-                    }
-                }
+                spans =
+                    render::apply_style_to_byte_ranges(spans, &[(bs, be)], theme.selection_style());
             }
 
             render::render_spans_clipped(
@@ -466,16 +626,73 @@ impl CodeView {
                 &spans,
                 base_style,
             );
+            if let Some(cols) = self.tab_cols.get(idx) {
+                render::render_tab_glyphs(
+                    content_area.x + gutter_w,
+                    y,
+                    self.state.x,
+                    content_w,
+                    buf,
+                    cols,
+                    self.options.tabs,
+                );
+            }
         }
 
-        if let Some(sb_x) = scrollbar_x {
-            render::render_scrollbar(
-                Rect::new(sb_x, area.y, 1, area.height),
+        let text_area = Rect {
+            x: content_area.x + gutter_w,
+            width: content_w,
+            ..content_area
+        };
+        if let Some(line) = self.hovered_line() {
+            render::render_line_highlight(text_area, buf, &self.state, line, theme.chrome.hover);
+        }
+        if let Some(caret) = self.visual.caret() {
+            render::render_caret(
+                text_area,
                 buf,
                 &self.state,
-                theme.text_muted,
+                caret,
+                theme.chrome.cursor_style(self.is_focused()),
             );
         }
+        if let Some(track) = layout.vertical {
+            render::render_scrollbar(track, buf, &self.state, theme.chrome.scrollbar);
+            let mut markers = self.scrollbar_markers.clone();
+            if self.options.enable_selection
+                && let Some(((l0, c0), (l1, c1))) = self.selection
+            {
+                let ((start, _), (end, _)) = normalize_sel_inclusive((l0, c0), (l1, c1));
+                markers.push(ScrollbarMarker::range(
+                    start as u32..end as u32 + 1,
+                    MarkerKind::Selection,
+                ));
+            }
+            render::render_scrollbar_markers(track, buf, &self.state, &markers, &theme.chrome);
+        }
+        if let Some(track) = layout.horizontal {
+            let track = Rect {
+                x: track.x + gutter_w,
+                width: track.width - gutter_w,
+                ..track
+            };
+            render::render_scrollbar_x(track, buf, &self.state, theme.chrome.scrollbar);
+        }
+        theme.color_profile.apply_to_buffer(buf, area);
+    }
+
+    /// Sets the markers drawn on the vertical scrollbar (search hits, diagnostics, ...), in
+    /// source lines. The selection is marked automatically.
+    pub fn set_scrollbar_markers(&mut self, markers: Vec<ScrollbarMarker>) {
+        self.scrollbar_markers = markers;
+    }
+
+    fn scrollbar_layout(&self, area: Rect) -> ScrollbarLayout {
+        ScrollbarLayout::new(
+            area,
+            self.options.show_scrollbar,
+            self.options.show_scrollbar_x,
+        )
     }
 
     pub fn as_text(&self, theme: &Theme) -> Text<'static> {
@@ -483,14 +700,11 @@ impl CodeView {
         for (idx, line) in self.lines.iter().enumerate() {
             if self.options.show_line_numbers {
                 out.push(ratatui::text::Line::from(vec![
-                    Span::styled(format!("{:>4} ", idx + 1), theme.text_muted),
-                    Span::styled(line.clone(), theme.text_primary),
+                    Span::styled(format!("{:>4} ", idx + 1), theme.code.line_number),
+                    Span::styled(line.clone(), theme.code.text),
                 ]));
             } else {
-                out.push(ratatui::text::Line::styled(
-                    line.clone(),
-                    theme.text_primary,
-                ));
+                out.push(ratatui::text::Line::styled(line.clone(), theme.code.text));
             }
         }
         Text::from(out)
@@ -543,6 +757,26 @@ impl CodeView {
     }
 }
 
+impl Component for CodeView {
+    type Action = SelectionAction;
+
+    fn handle_event(&mut self, area: Rect, event: InputEvent) -> SelectionAction {
+        self.handle_event_action_in_area(area, event)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        self.render_ref(area, buf, theme);
+    }
+
+    fn bindings(&self) -> Vec<BindingGroup> {
+        self.options.help_groups()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        CodeView::set_focused(self, focused);
+    }
+}
+
 fn compute_hash(language: &Option<String>, lines: &[String], start: usize, end: usize) -> u64 {
     let mut h = std::collections::hash_map::DefaultHasher::new();
     language.hash(&mut h);
@@ -566,14 +800,6 @@ fn digits(mut n: usize) -> usize {
     d
 }
 
-fn normalize_tabs(s: &str) -> String {
-    if s.contains('\t') {
-        s.replace('\t', "    ")
-    } else {
-        s.to_string()
-    }
-}
-
 fn normalize_sel(a: (usize, u32), b: (usize, u32)) -> ((usize, u32), (usize, u32)) {
     if a.0 < b.0 || (a.0 == b.0 && a.1 <= b.1) {
         (a, b)
@@ -590,6 +816,7 @@ fn normalize_sel_inclusive(a: (usize, u32), b: (usize, u32)) -> ((usize, u32), (
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::color::ColorProfile;
     use ratatui::buffer::Buffer;
     use ratatui::style::Color;
     use ratatui::style::Style;
@@ -643,6 +870,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn code_view_downsamples_highlighter_colors_to_theme_profile() {
+        struct RgbHighlighter;
+
+        impl CodeHighlighter for RgbHighlighter {
+            fn highlight_lines(
+                &self,
+                _language: Option<&str>,
+                lines: &[&str],
+            ) -> Vec<Vec<Span<'static>>> {
+                let style = Style::default().fg(Color::Rgb(250, 10, 10));
+                lines
+                    .iter()
+                    .map(|l| vec![Span::styled((*l).to_string(), style)])
+                    .collect()
+            }
+        }
+
+        let mut v = CodeView::with_options(CodeViewOptions {
+            show_line_numbers: false,
+            show_scrollbar: false,
+            ..Default::default()
+        });
+        v.set_code("hi");
+        v.set_highlighter(Some(Arc::new(RgbHighlighter)));
+
+        let theme = Theme::default().with_color_profile(ColorProfile::Ansi16);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+        v.render_ref(buf.area, &mut buf, &theme);
+
+        assert_eq!(buf[(0, 0)].fg, Color::LightRed);
+    }
+
     #[test]
     fn mouse_click_selects_single_cell() {
         let mut v = CodeView::with_options(CodeViewOptions {
@@ -719,4 +979,310 @@ mod tests {
 
         assert_eq!(v.selected_text().as_deref(), Some("bcd"));
     }
+
+    #[test]
+    fn double_click_selects_word_and_triple_click_selects_line() {
+        let mut v = CodeView::with_options(CodeViewOptions {
+            show_line_numbers: false,
+            show_scrollbar: false,
+            ..Default::default()
+        });
+        v.set_code("let foo_bar = 1;\n");
+
+        let area = Rect::new(0, 0, 20, 1);
+        let t0 = Instant::now();
+        let click = |v: &mut CodeView, n: u64| {
+            for kind in [
+                MouseEventKind::Down(MouseButton::Left),
+                MouseEventKind::Up(MouseButton::Left),
+            ] {
+                v.handle_mouse_event_at(
+                    area,
+                    MouseEvent {
+                        x: 6,
+                        y: 0,
+                        kind,
+                        modifiers: crate::input::KeyModifiers::none(),
+                    },
+                    t0 + std::time::Duration::from_millis(50 * n),
+                );
+            }
+        };
+        click(&mut v, 0);
+        click(&mut v, 1);
+        assert_eq!(v.selected_text().as_deref(), Some("foo_bar"));
+        click(&mut v, 2);
+        assert_eq!(v.selected_text().as_deref(), Some("let foo_bar = 1;"));
+    }
+
+    #[test]
+    fn horizontal_wheel_scrolls_and_motion_tracks_hovered_line() {
+        let mut v = CodeView::with_options(CodeViewOptions {
+            show_line_numbers: false,
+            show_scrollbar: false,
+            ..Default::default()
+        });
+        v.set_code("0123456789abcdef\nb\nc\n");
+
+        let area = Rect::new(0, 0, 4, 2);
+        let mouse = |x, y, kind| MouseEvent {
+            x,
+            y,
+            kind,
+            modifiers: crate::input::KeyModifiers::none(),
+        };
+
+        assert!(v.handle_mouse_event(area, mouse(0, 0, MouseEventKind::ScrollRight)));
+        assert_eq!(v.state.x, v.options.scroll.horiz_step as u32);
+        assert!(v.handle_mouse_event(area, mouse(0, 0, MouseEventKind::ScrollLeft)));
+        assert_eq!(v.state.x, 0);
+
+        assert!(v.handle_mouse_event(area, mouse(1, 1, MouseEventKind::Moved)));
+        assert_eq!(v.hovered_line(), Some(1));
+        assert!(!v.handle_mouse_event(area, mouse(2, 1, MouseEventKind::Moved)));
+        v.state.scroll_y_by(1);
+        assert_eq!(v.hovered_line(), Some(2));
+
+        let theme = Theme::default();
+        let mut buf = Buffer::empty(area);
+        v.render_ref(area, &mut buf, &theme);
+        assert_eq!(buf[(0, 1)].bg, theme.chrome.hover.bg.unwrap());
+        assert_ne!(buf[(0, 0)].bg, theme.chrome.hover.bg.unwrap());
+
+        assert!(v.handle_mouse_event(area, mouse(9, 9, MouseEventKind::Moved)));
+        assert_eq!(v.hovered_line(), None);
+    }
+
+    #[test]
+    fn smooth_wheel_scrolls_on_ticks() {
+        let mut v = CodeView::with_options(CodeViewOptions {
+            scroll: ScrollBindings {
+                smooth: Some(crate::viewport::SmoothScroll::default()),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        v.set_code(&"x\n".repeat(50));
+        let area = Rect::new(0, 0, 20, 5);
+        let wheel = MouseEvent {
+            x: 0,
+            y: 0,
+            kind: MouseEventKind::ScrollDown,
+            modifiers: crate::input::KeyModifiers::none(),
+        };
+
+        let t0 = Instant::now();
+        assert!(v.handle_mouse_event_at(area, wheel, t0));
+        assert_eq!(v.state.y, 0);
+        let tick = |ms| InputEvent::Tick(t0 + std::time::Duration::from_millis(ms));
+        assert_eq!(v.handle_event_action(tick(500)), SelectionAction::Redraw);
+        assert_eq!(v.state.y, 3);
+        assert_eq!(v.handle_event_action(tick(600)), SelectionAction::None);
+    }
+
+    #[test]
+    fn pending_chord_resolves_on_tick_after_timeout() {
+        let mut scroll = ScrollBindings::default();
+        scroll.bottom.push(crate::keymap::key_char('g'));
+        let mut v = CodeView::with_options(CodeViewOptions {
+            scroll,
+            ..Default::default()
+        });
+        v.set_code(&"x\n".repeat(50));
+        v.set_viewport(Rect::new(0, 0, 20, 5));
+
+        let t0 = Instant::now();
+        let g = crate::keymap::key_char('g');
+        assert_eq!(v.handle_key_event_at(g, t0), SelectionAction::Redraw);
+        let tick = |ms| InputEvent::Tick(t0 + std::time::Duration::from_millis(ms));
+        assert_eq!(v.handle_event_action(tick(500)), SelectionAction::None);
+        assert_eq!(v.state.y, 0);
+        assert_eq!(v.handle_event_action(tick(1500)), SelectionAction::Redraw);
+        assert!(v.state.y > 0);
+    }
+
+    #[test]
+    fn interrupted_bound_prefix_applies_both_keys() {
+        let mut scroll = ScrollBindings::default();
+        scroll.top.push(crate::keymap::key_char('g'));
+        let mut v = CodeView::with_options(CodeViewOptions {
+            scroll,
+            ..Default::default()
+        });
+        v.set_code(&"x\n".repeat(50));
+        v.set_viewport(Rect::new(0, 0, 20, 5));
+        v.state.y = 10;
+
+        let now = Instant::now();
+        let g = crate::keymap::key_char('g');
+        assert_eq!(v.handle_key_event_at(g, now), SelectionAction::Redraw);
+        assert_eq!(v.state.y, 10);
+        let j = crate::keymap::key_char('j');
+        assert_eq!(v.handle_key_event_at(j, now), SelectionAction::Redraw);
+        assert_eq!(v.state.y, 1);
+    }
+
+    #[test]
+    fn set_options_rebuilds_the_scroll_keymap() {
+        let mut v = CodeView::new();
+        v.set_code(&"x\n".repeat(50));
+        v.set_viewport(Rect::new(0, 0, 20, 5));
+        let x = crate::keymap::key_char('x');
+        let now = Instant::now();
+        assert_eq!(v.handle_key_event_at(x.clone(), now), SelectionAction::None);
+
+        let mut options = v.options().clone();
+        options.scroll.bottom.push(x.clone());
+        v.set_options(options);
+        assert_eq!(v.handle_key_event_at(x, now), SelectionAction::Redraw);
+        assert!(v.state.y > 0);
+    }
+
+    #[test]
+    fn scrollbars_show_horizontal_thumb_and_markers() {
+        let mut v = CodeView::with_options(CodeViewOptions {
+            show_line_numbers: false,
+            show_scrollbar_x: true,
+            ..Default::default()
+        });
+        let lines = (0..20).map(|i| format!("{i} {}", "x".repeat(30))).collect();
+        v.set_lines(lines);
+        v.set_scrollbar_markers(vec![ScrollbarMarker::new(19, MarkerKind::Diagnostic)]);
+        v.state.x = 100;
+
+        let theme = Theme::default();
+        let area = Rect::new(0, 0, 11, 5);
+        let mut buf = Buffer::empty(area);
+        v.render_ref(area, &mut buf, &theme);
+
+        assert_eq!(v.state.viewport_h, 4);
+        let bottom: String = (0..10).map(|x| buf[(x, 4)].symbol()).collect();
+        assert!(
+            bottom.ends_with('█') && bottom.starts_with(' '),
+            "{bottom:?}"
+        );
+        assert_eq!(buf[(10, 3)].symbol(), MarkerKind::Diagnostic.glyph());
+        assert_eq!(buf[(10, 4)].symbol(), " ");
+    }
+
+    #[test]
+    fn keyboard_visual_modes_yank_the_selection() {
+        let mut v = CodeView::with_options(CodeViewOptions {
+            show_line_numbers: false,
+            ..Default::default()
+        });
+        v.set_code("alpha beta\ngamma delta\n");
+        let theme = Theme::default();
+        let area = Rect::new(0, 0, 20, 3);
+        let mut buf = Buffer::empty(area);
+        v.render_ref(area, &mut buf, &theme);
+
+        let mut keys = |keys: &[crate::input::KeyEvent]| {
+            keys.iter()
+                .map(|k| v.handle_event_action(InputEvent::Key(k.clone())))
+                .last()
+                .unwrap()
+        };
+        let key = crate::keymap::key_char;
+        let yank = |text: &str| SelectionAction::CopyRequested(text.to_string());
+
+        assert_eq!(keys(&[key('v'), key('w'), key('y')]), yank("alpha b"));
+        assert_eq!(
+            keys(&[key('V'), key('j'), key('y')]),
+            yank("alpha beta\ngamma delta")
+        );
+        let ctrl_v = crate::keymap::key_ctrl('v');
+        // The caret stays where the last mode left it.
+        assert_eq!(
+            keys(&[ctrl_v, key('k'), key('0'), key('y')]),
+            yank("alpha b\ngamma d")
+        );
+        assert_eq!(keys(&[key('j')]), SelectionAction::Redraw);
+        assert_eq!(v.selection, None);
+    }
+
+    #[test]
+    fn tab_glyph_is_drawn_but_not_copied() {
+        let mut v = CodeView::with_options(CodeViewOptions {
+            show_line_numbers: false,
+            tabs: TabPolicy::default().with_glyph(Some(crate::tabs::TAB_GLYPH)),
+            ..Default::default()
+        });
+        v.set_code("a\tb\n");
+        let area = Rect::new(0, 0, 10, 2);
+        let mut buf = Buffer::empty(area);
+        v.render_ref(area, &mut buf, &Theme::default());
+        assert_eq!(buf[(1, 0)].symbol(), "→");
+        assert_eq!(buf[(2, 0)].symbol(), " ");
+        assert_eq!(buf[(4, 0)].symbol(), "b");
+
+        for k in ['V', 'y'] {
+            let action = v.handle_event_action(InputEvent::Key(crate::keymap::key_char(k)));
+            if k == 'y' {
+                assert_eq!(action, SelectionAction::CopyRequested("a   b".to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn caret_is_muted_while_unfocused() {
+        let mut v = CodeView::with_options(CodeViewOptions {
+            show_line_numbers: false,
+            ..Default::default()
+        });
+        v.set_code("abc\n");
+        let theme = Theme::default();
+        let area = Rect::new(0, 0, 10, 2);
+        v.handle_event_action(InputEvent::Key(crate::keymap::key_char('v')));
+
+        let mut buf = Buffer::empty(area);
+        v.render_ref(area, &mut buf, &theme);
+        assert_eq!(buf[(0, 0)].fg, theme.chrome.cursor.fg.unwrap());
+
+        Component::set_focused(&mut v, false);
+        assert!(!v.is_focused());
+        let mut buf = Buffer::empty(area);
+        v.render_ref(area, &mut buf, &theme);
+        assert_eq!(buf[(0, 0)].fg, theme.chrome.cursor_unfocused.fg.unwrap());
+    }
+
+    #[test]
+    fn visual_selection_copies_whole_wide_graphemes() {
+        let mut v = CodeView::new();
+        v.set_code("a界b\n");
+        v.set_viewport(Rect::new(0, 0, 10, 2));
+        for c in ['v', 'l', 'y'] {
+            if let SelectionAction::CopyRequested(text) =
+                v.handle_event_action(InputEvent::Key(crate::keymap::key_char(c)))
+            {
+                assert_eq!(text, "a界");
+                return;
+            }
+        }
+        panic!("expected a copy request");
+    }
+
+    #[test]
+    fn focus_lost_drops_pending_chord_and_drag() {
+        let mut v = CodeView::new();
+        v.set_code(&"x\n".repeat(50));
+        v.set_viewport(Rect::new(0, 0, 20, 5));
+        v.state.y = 10;
+        let now = Instant::now();
+        let g = crate::keymap::key_char('g');
+        assert_eq!(
+            v.handle_key_event_at(g.clone(), now),
+            SelectionAction::Redraw
+        );
+        v.selection_anchor = Some((0, 0));
+
+        assert_eq!(
+            v.handle_event_action(InputEvent::FocusLost),
+            SelectionAction::Redraw
+        );
+        assert_eq!(v.selection_anchor, None);
+        // The next `g` starts a new chord instead of completing `g g`.
+        assert_eq!(v.handle_key_event_at(g, now), SelectionAction::Redraw);
+        assert_eq!(v.state.y, 10);
+    }
 }
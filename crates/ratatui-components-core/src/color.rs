@@ -0,0 +1,284 @@
+//! Terminal color profiles and downsampling.
+//!
+//! Themes, highlighters and ANSI input may produce any ratatui [`Color`], including truecolor
+//! `Rgb` values. A [`ColorProfile`] maps those colors onto what the terminal can display. Views
+//! apply [`Theme::color_profile`](crate::theme::Theme::color_profile) as a post-pass over the
+//! buffer region they rendered, so every source of color (theme tokens, syntax highlighting,
+//! parsed ANSI escapes) is downsampled consistently. Span-producing code (e.g. highlighters used
+//! outside a view) can use [`ColorProfile::adapt_line`] / [`ColorProfile::adapt_text`] instead.
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::text::Text;
+
+/// The color capabilities of a terminal, from most to least capable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum ColorProfile {
+    /// 24-bit colors; nothing is converted.
+    #[default]
+    TrueColor,
+    /// The xterm 256-color palette: `Rgb` colors map to the nearest index.
+    Ansi256,
+    /// The 16 basic ANSI colors: `Rgb` and `Indexed` colors map to the nearest named color.
+    Ansi16,
+    /// No colors at all; text modifiers are kept.
+    NoColor,
+}
+
+impl ColorProfile {
+    /// Guesses the profile from the environment: a non-empty `NO_COLOR` (see <https://no-color.org>)
+    /// selects [`Self::NoColor`], otherwise `COLORTERM` and `TERM` decide.
+    pub fn detect() -> Self {
+        if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return Self::NoColor;
+        }
+        let colorterm = std::env::var("COLORTERM").ok();
+        let term = std::env::var("TERM").ok();
+        Self::from_env_vars(colorterm.as_deref(), term.as_deref())
+    }
+
+    /// Like [`Self::detect`], for explicit `COLORTERM` / `TERM` values.
+    pub fn from_env_vars(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return Self::TrueColor;
+        }
+        match term {
+            None | Some("" | "dumb") => Self::NoColor,
+            Some(t) if t.contains("truecolor") || t.contains("direct") => Self::TrueColor,
+            Some(t) if t.contains("256color") => Self::Ansi256,
+            Some(_) => Self::Ansi16,
+        }
+    }
+
+    /// Converts `color` for this profile. `None` means the color cannot be shown at all.
+    ///
+    /// `Color::Reset` is always kept: it selects the terminal's default color.
+    pub fn adapt_color(self, color: Color) -> Option<Color> {
+        match (self, color) {
+            (_, Color::Reset) => Some(Color::Reset),
+            (Self::TrueColor, c) => Some(c),
+            (Self::NoColor, _) => None,
+            (Self::Ansi256, Color::Rgb(r, g, b)) => Some(Color::Indexed(rgb_to_ansi256(r, g, b))),
+            (Self::Ansi256, c) => Some(c),
+            (Self::Ansi16, Color::Indexed(i)) if i < 16 => Some(ANSI16[i as usize].0),
+            (Self::Ansi16, c) => match to_rgb(c) {
+                Some((r, g, b)) => Some(rgb_to_ansi16(r, g, b)),
+                None => Some(c),
+            },
+        }
+    }
+
+    /// Converts the colors of `style`; colors this profile cannot show are removed.
+    pub fn adapt_style(self, mut style: Style) -> Style {
+        if self == Self::TrueColor {
+            return style;
+        }
+        style.fg = style.fg.and_then(|c| self.adapt_color(c));
+        style.bg = style.bg.and_then(|c| self.adapt_color(c));
+        style
+    }
+
+    pub fn adapt_span(self, span: &mut Span<'_>) {
+        span.style = self.adapt_style(span.style);
+    }
+
+    pub fn adapt_line(self, line: &mut Line<'_>) {
+        if self == Self::TrueColor {
+            return;
+        }
+        line.style = self.adapt_style(line.style);
+        for span in &mut line.spans {
+            self.adapt_span(span);
+        }
+    }
+
+    pub fn adapt_text(self, text: &mut Text<'_>) {
+        if self == Self::TrueColor {
+            return;
+        }
+        text.style = self.adapt_style(text.style);
+        for line in &mut text.lines {
+            self.adapt_line(line);
+        }
+    }
+
+    /// Converts every cell of `buf` inside `area` (clamped to the buffer).
+    pub fn apply_to_buffer(self, buf: &mut Buffer, area: Rect) {
+        if self == Self::TrueColor {
+            return;
+        }
+        let area = area.intersection(buf.area);
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                if let Some(cell) = buf.cell_mut((x, y)) {
+                    cell.fg = self.adapt_color(cell.fg).unwrap_or(Color::Reset);
+                    cell.bg = self.adapt_color(cell.bg).unwrap_or(Color::Reset);
+                }
+            }
+        }
+    }
+}
+
+/// The 16 named colors with their xterm default RGB values, in ANSI index order.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(i) => Some(ansi256_to_rgb(i)),
+        named => ANSI16
+            .iter()
+            .find(|(c, _)| *c == named)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+fn ansi256_to_rgb(i: u8) -> (u8, u8, u8) {
+    match i {
+        0..=15 => ANSI16[i as usize].1,
+        16..=231 => {
+            let i = i - 16;
+            (
+                CUBE_LEVELS[(i / 36) as usize],
+                CUBE_LEVELS[(i / 6 % 6) as usize],
+                CUBE_LEVELS[(i % 6) as usize],
+            )
+        }
+        _ => {
+            let v = 8 + (i - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |v: u8| {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, l)| (i32::from(**l) - i32::from(v)).abs())
+            .map(|(i, _)| i as u8)
+            .unwrap_or(0)
+    };
+    let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+
+    let avg = (u32::from(r) + u32::from(g) + u32::from(b)) / 3;
+    let gray = 232 + (avg.saturating_sub(3) / 10).min(23) as u8;
+
+    let rgb = (r, g, b);
+    if distance(ansi256_to_rgb(gray), rgb) < distance(ansi256_to_rgb(cube), rgb) {
+        gray
+    } else {
+        cube
+    }
+}
+
+fn rgb_to_ansi16(r: u8, g: u8, b: u8) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+        .map(|(c, _)| *c)
+        .unwrap_or(Color::Reset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Modifier;
+
+    #[test]
+    fn downsamples_rgb_per_profile() {
+        let orange = Color::Rgb(255, 135, 0);
+        assert_eq!(ColorProfile::TrueColor.adapt_color(orange), Some(orange));
+        assert_eq!(
+            ColorProfile::Ansi256.adapt_color(orange),
+            Some(Color::Indexed(208))
+        );
+        assert_eq!(
+            ColorProfile::Ansi256.adapt_color(Color::Rgb(128, 128, 128)),
+            Some(Color::Indexed(244))
+        );
+        assert_eq!(
+            ColorProfile::Ansi16.adapt_color(Color::Rgb(250, 10, 10)),
+            Some(Color::LightRed)
+        );
+        assert_eq!(
+            ColorProfile::Ansi16.adapt_color(Color::Indexed(4)),
+            Some(Color::Blue)
+        );
+        assert_eq!(
+            ColorProfile::Ansi16.adapt_color(Color::Indexed(236)),
+            Some(Color::Black)
+        );
+        assert_eq!(ColorProfile::NoColor.adapt_color(Color::Red), None);
+        assert_eq!(
+            ColorProfile::NoColor.adapt_color(Color::Reset),
+            Some(Color::Reset)
+        );
+    }
+
+    #[test]
+    fn no_color_keeps_modifiers() {
+        let style = Style::default()
+            .fg(Color::Red)
+            .bg(Color::Blue)
+            .add_modifier(Modifier::BOLD);
+        assert_eq!(
+            ColorProfile::NoColor.adapt_style(style),
+            Style::default().add_modifier(Modifier::BOLD)
+        );
+    }
+
+    #[test]
+    fn buffer_pass_only_touches_area() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+        let rgb = Style::default().fg(Color::Rgb(0, 0, 0));
+        buf.set_style(buf.area, rgb);
+        ColorProfile::Ansi16.apply_to_buffer(&mut buf, Rect::new(1, 0, 5, 5));
+        assert_eq!(buf[(0, 0)].fg, Color::Rgb(0, 0, 0));
+        assert_eq!(buf[(1, 0)].fg, Color::Black);
+    }
+
+    #[test]
+    fn detects_profile_from_env() {
+        let p = ColorProfile::from_env_vars;
+        assert_eq!(p(Some("truecolor"), Some("xterm")), ColorProfile::TrueColor);
+        assert_eq!(p(None, Some("xterm-256color")), ColorProfile::Ansi256);
+        assert_eq!(p(None, Some("xterm")), ColorProfile::Ansi16);
+        assert_eq!(p(None, Some("dumb")), ColorProfile::NoColor);
+        assert_eq!(p(None, None), ColorProfile::NoColor);
+    }
+}
@@ -0,0 +1,122 @@
+//! A common interface over the interactive views.
+//!
+//! Each view keeps its own inherent `handle_event*` / `render*` methods with view-specific
+//! signatures. [`Component`] wraps them behind one shape so containers (split panes, tab sets,
+//! focus managers) can hold views generically:
+//!
+//! ```
+//! use ratatui_components_core::code_view::CodeView;
+//! use ratatui_components_core::component::Component;
+//! use ratatui_components_core::selection::SelectionAction;
+//!
+//! let mut panes: Vec<Box<dyn Component<Action = SelectionAction>>> = Vec::new();
+//! panes.push(Box::new(CodeView::new()));
+//! ```
+use crate::help::BindingGroup;
+use crate::input::InputEvent;
+use crate::theme::Theme;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+
+/// An interactive, stateful view that handles [`InputEvent`]s and renders into a [`Buffer`].
+///
+/// `area` is the same rectangle for event handling and rendering, so mouse hit-testing matches
+/// what is on screen.
+pub trait Component {
+    /// What the view reports back after handling an event (redraw, copy request, submit, ...).
+    type Action;
+
+    /// Handles `event` for a view laid out in `area`.
+    fn handle_event(&mut self, area: Rect, event: InputEvent) -> Self::Action;
+
+    /// Renders the view into `area`.
+    fn render(&mut self, area: Rect, buf: &mut Buffer, theme: &Theme);
+
+    /// The view's key bindings, grouped for help screens (see [`crate::help::HelpPanel`]).
+    fn bindings(&self) -> Vec<BindingGroup> {
+        Vec::new()
+    }
+
+    /// Where the terminal cursor should be placed, if the view shows one.
+    fn cursor_position(&self, area: Rect) -> Option<(u16, u16)> {
+        let _ = area;
+        None
+    }
+
+    /// Tells the view whether it has input focus (see [`crate::focus::FocusManager`]).
+    ///
+    /// Views with a cursor render it in a muted style while unfocused; the default ignores focus.
+    fn set_focused(&mut self, focused: bool) {
+        let _ = focused;
+    }
+}
+
+/// Whether an action means the view used the event.
+///
+/// Containers use this to decide if an event should fall through to global handling, e.g.
+/// [`crate::app::Model::on_input`]. Each view's no-op action (`None`) reports `false`.
+pub trait ComponentAction {
+    fn is_consumed(&self) -> bool;
+}
+
+impl<C: Component + ?Sized> Component for Box<C> {
+    type Action = C::Action;
+
+    fn handle_event(&mut self, area: Rect, event: InputEvent) -> Self::Action {
+        (**self).handle_event(area, event)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        (**self).render(area, buf, theme)
+    }
+
+    fn bindings(&self) -> Vec<BindingGroup> {
+        (**self).bindings()
+    }
+
+    fn cursor_position(&self, area: Rect) -> Option<(u16, u16)> {
+        (**self).cursor_position(area)
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        (**self).set_focused(focused)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code_view::CodeView;
+    use crate::input::KeyCode;
+    use crate::input::KeyEvent;
+    use crate::selection::SelectionAction;
+    use crate::textarea::TextArea;
+    use crate::textarea::TextAreaAction;
+
+    #[test]
+    fn boxed_views_are_driven_through_the_trait() {
+        let mut code = CodeView::new();
+        code.set_code("a\nb\nc\nd\n");
+        let mut panes: Vec<Box<dyn Component<Action = SelectionAction>>> = vec![Box::new(code)];
+
+        let area = Rect::new(0, 0, 10, 2);
+        let mut buf = Buffer::empty(area);
+        let theme = Theme::default();
+        for pane in &mut panes {
+            pane.render(area, &mut buf, &theme);
+            let action = pane.handle_event(area, InputEvent::Key(KeyEvent::new(KeyCode::Down)));
+            assert_eq!(action, SelectionAction::Redraw);
+            assert!(!pane.bindings().is_empty());
+            assert_eq!(pane.cursor_position(area), None);
+        }
+    }
+
+    #[test]
+    fn text_area_reports_its_cursor() {
+        let mut ta = TextArea::new();
+        let area = Rect::new(2, 3, 10, 2);
+        let action = Component::handle_event(&mut ta, area, InputEvent::Paste("hi".to_string()));
+        assert_eq!(action, TextAreaAction::Changed);
+        assert_eq!(ta.cursor_position(area), Some((4, 3)));
+    }
+}
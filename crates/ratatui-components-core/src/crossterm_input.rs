@@ -1,24 +1,44 @@
 use crate::input::InputEvent;
 use crate::input::KeyCode;
 use crate::input::KeyEvent;
+use crate::input::KeyEventKind;
 use crate::input::KeyModifiers;
+use crate::input::MediaKeyCode;
 use crate::input::MouseButton;
 use crate::input::MouseEvent;
 use crate::input::MouseEventKind;
 
+/// Converts a crossterm event, keeping key presses and repeats.
+///
+/// Key releases are dropped: crossterm reports them on Windows (and with the kitty keyboard
+/// protocol), and most apps only want to react once per key. Use
+/// [`input_event_from_crossterm_with_releases`] to receive them too.
 pub fn input_event_from_crossterm(ev: crossterm::event::Event) -> Option<InputEvent> {
     match ev {
-        crossterm::event::Event::Key(key) => {
-            if key.kind != crossterm::event::KeyEventKind::Press {
-                return None;
-            }
-            Some(InputEvent::Key(key_event_from_crossterm(key)?))
+        crossterm::event::Event::Key(key)
+            if key.kind == crossterm::event::KeyEventKind::Release =>
+        {
+            None
         }
+        other => input_event_from_crossterm_with_releases(other),
+    }
+}
+
+/// Like [`input_event_from_crossterm`], but also forwards key release events.
+///
+/// Use [`KeyEvent::kind`] to tell presses, repeats and releases apart.
+pub fn input_event_from_crossterm_with_releases(ev: crossterm::event::Event) -> Option<InputEvent> {
+    match ev {
+        crossterm::event::Event::Key(key) => Some(InputEvent::Key(key_event_from_crossterm(key)?)),
         crossterm::event::Event::Paste(s) => Some(InputEvent::Paste(s)),
+        crossterm::event::Event::Resize(width, height) => {
+            Some(InputEvent::Resize { width, height })
+        }
+        crossterm::event::Event::FocusGained => Some(InputEvent::FocusGained),
+        crossterm::event::Event::FocusLost => Some(InputEvent::FocusLost),
         crossterm::event::Event::Mouse(m) => {
             Some(InputEvent::Mouse(mouse_event_from_crossterm(m)?))
         }
-        _ => None,
     }
 }
 
@@ -38,12 +58,30 @@ pub fn key_event_from_crossterm(key: crossterm::event::KeyEvent) -> Option<KeyEv
         crossterm::event::KeyCode::End => KeyCode::End,
         crossterm::event::KeyCode::PageUp => KeyCode::PageUp,
         crossterm::event::KeyCode::PageDown => KeyCode::PageDown,
+        crossterm::event::KeyCode::F(n) => KeyCode::F(n),
+        crossterm::event::KeyCode::Insert => KeyCode::Insert,
+        crossterm::event::KeyCode::BackTab => KeyCode::BackTab,
+        crossterm::event::KeyCode::Menu => KeyCode::Menu,
+        crossterm::event::KeyCode::Media(m) => KeyCode::Media(media_key_from_crossterm(m)),
         _ => return None,
     };
 
+    let kind = match key.kind {
+        crossterm::event::KeyEventKind::Press => KeyEventKind::Press,
+        crossterm::event::KeyEventKind::Repeat => KeyEventKind::Repeat,
+        crossterm::event::KeyEventKind::Release => KeyEventKind::Release,
+    };
+
+    let mut modifiers = modifiers_from_crossterm(key.modifiers);
+    if code == KeyCode::BackTab {
+        // crossterm reports BackTab with SHIFT held; the key itself already implies it.
+        modifiers.shift = false;
+    }
+
     Some(KeyEvent {
         code,
-        modifiers: modifiers_from_crossterm(key.modifiers),
+        modifiers,
+        kind,
     })
 }
 
@@ -60,7 +98,9 @@ pub fn mouse_event_from_crossterm(m: crossterm::event::MouseEvent) -> Option<Mou
         }
         crossterm::event::MouseEventKind::ScrollUp => MouseEventKind::ScrollUp,
         crossterm::event::MouseEventKind::ScrollDown => MouseEventKind::ScrollDown,
-        _ => return None,
+        crossterm::event::MouseEventKind::ScrollLeft => MouseEventKind::ScrollLeft,
+        crossterm::event::MouseEventKind::ScrollRight => MouseEventKind::ScrollRight,
+        crossterm::event::MouseEventKind::Moved => MouseEventKind::Moved,
     };
 
     Some(MouseEvent {
@@ -76,6 +116,27 @@ fn modifiers_from_crossterm(m: crossterm::event::KeyModifiers) -> KeyModifiers {
         shift: m.contains(crossterm::event::KeyModifiers::SHIFT),
         ctrl: m.contains(crossterm::event::KeyModifiers::CONTROL),
         alt: m.contains(crossterm::event::KeyModifiers::ALT),
+        super_: m.contains(crossterm::event::KeyModifiers::SUPER),
+        hyper: m.contains(crossterm::event::KeyModifiers::HYPER),
+        meta: m.contains(crossterm::event::KeyModifiers::META),
+    }
+}
+
+fn media_key_from_crossterm(m: crossterm::event::MediaKeyCode) -> MediaKeyCode {
+    match m {
+        crossterm::event::MediaKeyCode::Play => MediaKeyCode::Play,
+        crossterm::event::MediaKeyCode::Pause => MediaKeyCode::Pause,
+        crossterm::event::MediaKeyCode::PlayPause => MediaKeyCode::PlayPause,
+        crossterm::event::MediaKeyCode::Reverse => MediaKeyCode::Reverse,
+        crossterm::event::MediaKeyCode::Stop => MediaKeyCode::Stop,
+        crossterm::event::MediaKeyCode::FastForward => MediaKeyCode::FastForward,
+        crossterm::event::MediaKeyCode::Rewind => MediaKeyCode::Rewind,
+        crossterm::event::MediaKeyCode::TrackNext => MediaKeyCode::TrackNext,
+        crossterm::event::MediaKeyCode::TrackPrevious => MediaKeyCode::TrackPrevious,
+        crossterm::event::MediaKeyCode::Record => MediaKeyCode::Record,
+        crossterm::event::MediaKeyCode::LowerVolume => MediaKeyCode::LowerVolume,
+        crossterm::event::MediaKeyCode::RaiseVolume => MediaKeyCode::RaiseVolume,
+        crossterm::event::MediaKeyCode::MuteVolume => MediaKeyCode::MuteVolume,
     }
 }
 
@@ -86,3 +147,86 @@ fn mouse_button_from_crossterm(b: crossterm::event::MouseButton) -> Option<Mouse
         crossterm::event::MouseButton::Middle => Some(MouseButton::Middle),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ct_key(
+        code: crossterm::event::KeyCode,
+        modifiers: crossterm::event::KeyModifiers,
+        kind: crossterm::event::KeyEventKind,
+    ) -> crossterm::event::Event {
+        crossterm::event::Event::Key(crossterm::event::KeyEvent::new_with_kind(
+            code, modifiers, kind,
+        ))
+    }
+
+    #[test]
+    fn maps_extended_keys_and_kinds() {
+        let ev = ct_key(
+            crossterm::event::KeyCode::F(5),
+            crossterm::event::KeyModifiers::SUPER,
+            crossterm::event::KeyEventKind::Repeat,
+        );
+        let Some(InputEvent::Key(key)) = input_event_from_crossterm(ev) else {
+            panic!("expected a key event");
+        };
+        assert_eq!(key.code, KeyCode::F(5));
+        assert!(key.modifiers.super_);
+        assert_eq!(key.kind, KeyEventKind::Repeat);
+
+        let release = ct_key(
+            crossterm::event::KeyCode::BackTab,
+            crossterm::event::KeyModifiers::SHIFT,
+            crossterm::event::KeyEventKind::Release,
+        );
+        assert_eq!(input_event_from_crossterm(release.clone()), None);
+        let Some(InputEvent::Key(key)) = input_event_from_crossterm_with_releases(release) else {
+            panic!("expected a key event");
+        };
+        assert_eq!(key.code, KeyCode::BackTab);
+        assert!(!key.modifiers.shift);
+        assert!(key.is_release());
+    }
+
+    #[test]
+    fn maps_horizontal_wheel_and_motion() {
+        let mouse = |kind| {
+            mouse_event_from_crossterm(crossterm::event::MouseEvent {
+                kind,
+                column: 4,
+                row: 2,
+                modifiers: crossterm::event::KeyModifiers::NONE,
+            })
+            .map(|m| m.kind)
+        };
+        assert_eq!(
+            mouse(crossterm::event::MouseEventKind::ScrollLeft),
+            Some(MouseEventKind::ScrollLeft)
+        );
+        assert_eq!(
+            mouse(crossterm::event::MouseEventKind::ScrollRight),
+            Some(MouseEventKind::ScrollRight)
+        );
+        assert_eq!(
+            mouse(crossterm::event::MouseEventKind::Moved),
+            Some(MouseEventKind::Moved)
+        );
+    }
+
+    #[test]
+    fn maps_resize_and_focus() {
+        assert_eq!(
+            input_event_from_crossterm(crossterm::event::Event::Resize(80, 24)),
+            Some(InputEvent::Resize {
+                width: 80,
+                height: 24
+            })
+        );
+        assert_eq!(
+            input_event_from_crossterm(crossterm::event::Event::FocusLost),
+            Some(InputEvent::FocusLost)
+        );
+    }
+}
@@ -1,12 +1,16 @@
+use crate::component::Component;
+use crate::component::ComponentAction;
 use crate::input::InputEvent;
 use crate::input::KeyCode;
 use crate::input::KeyEvent;
 use crate::render;
+use crate::render::MarkerKind;
+use crate::render::ScrollbarLayout;
+use crate::render::ScrollbarMarker;
 use crate::theme::Theme;
 use crate::viewport::ViewportState;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::text::Span;
 use virtualizer::Align;
@@ -21,6 +25,12 @@ pub enum DataGridAction {
     SelectionChanged,
 }
 
+impl ComponentAction for DataGridAction {
+    fn is_consumed(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+}
+
 /// A grid cell address.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Cell {
@@ -37,6 +47,15 @@ pub enum Selection {
 }
 
 impl Selection {
+    /// The rows the selection spans.
+    pub fn rows(&self) -> Option<std::ops::RangeInclusive<usize>> {
+        match *self {
+            Selection::None => None,
+            Selection::Single(c) => Some(c.row..=c.row),
+            Selection::Rect { start, end } => Some(start.row.min(end.row)..=start.row.max(end.row)),
+        }
+    }
+
     pub fn contains(&self, cell: Cell) -> bool {
         match *self {
             Selection::None => false,
@@ -82,15 +101,21 @@ impl DataGridColumn {
 pub struct DataGridViewOptions {
     pub show_header: bool,
     pub show_scrollbar_y: bool,
+    /// Draws a horizontal scrollbar below the body.
+    pub show_scrollbar_x: bool,
     pub overscan_rows: usize,
     pub overscan_cols: usize,
     pub row_height: u32,
     pub col_gap: u32,
     pub style: Style,
+    /// Patched over [`crate::theme::ChromeTheme::header`].
     pub header_style: Style,
     pub grid_line_style: Style,
+    /// Defaults to [`crate::theme::ChromeTheme::scrollbar`] when left as `Style::default()`.
     pub scrollbar_style: Style,
+    /// Patched over [`crate::theme::ChromeTheme::cursor`] (or `cursor_unfocused`).
     pub cursor_style: Style,
+    /// Patched over [`crate::theme::ChromeTheme::selected`].
     pub selected_style: Style,
     pub selection_follows_cursor: bool,
     pub multi_select: bool,
@@ -101,16 +126,17 @@ impl Default for DataGridViewOptions {
         Self {
             show_header: true,
             show_scrollbar_y: true,
+            show_scrollbar_x: false,
             overscan_rows: 2,
             overscan_cols: 2,
             row_height: 1,
             col_gap: 1,
             style: Style::default(),
-            header_style: Style::default().add_modifier(Modifier::BOLD),
+            header_style: Style::default(),
             grid_line_style: Style::default(),
             scrollbar_style: Style::default(),
-            cursor_style: Style::default().add_modifier(Modifier::REVERSED),
-            selected_style: Style::default().add_modifier(Modifier::BOLD),
+            cursor_style: Style::default(),
+            selected_style: Style::default(),
             selection_follows_cursor: true,
             multi_select: false,
         }
@@ -147,10 +173,12 @@ pub struct DataGridView {
     cursor: Option<Cell>,
     selection: Selection,
     selection_anchor: Option<Cell>,
+    focused: bool,
     row_v: Virtualizer,
     col_v: Virtualizer,
     row_items: Vec<virtualizer::VirtualItem>,
     col_items: Vec<virtualizer::VirtualItem>,
+    scrollbar_markers: Vec<ScrollbarMarker>,
 }
 
 impl Default for DataGridView {
@@ -179,10 +207,12 @@ impl Default for DataGridView {
             cursor: None,
             selection: Selection::None,
             selection_anchor: None,
+            focused: true,
             row_v,
             col_v,
             row_items: Vec::new(),
             col_items: Vec::new(),
+            scrollbar_markers: Vec::new(),
         }
     }
 }
@@ -226,6 +256,16 @@ impl DataGridView {
         &self.options
     }
 
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Sets focus. While unfocused the cursor is drawn with
+    /// [`crate::theme::ChromeTheme::cursor_unfocused`].
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
     pub fn set_options(&mut self, options: DataGridViewOptions) {
         self.options = options;
         self.rebuild_row_virtualizer();
@@ -294,6 +334,11 @@ impl DataGridView {
     pub fn handle_event(&mut self, event: InputEvent) -> DataGridAction {
         match event {
             InputEvent::Paste(_) => DataGridAction::None,
+            // Terminal focus is not pane focus; containers call `set_focused` instead.
+            InputEvent::Resize { .. }
+            | InputEvent::Tick(_)
+            | InputEvent::FocusGained
+            | InputEvent::FocusLost => DataGridAction::None,
             InputEvent::Key(key) => self.handle_key(key),
             InputEvent::Mouse(_) => DataGridAction::None,
         }
@@ -336,14 +381,12 @@ impl DataGridView {
         let header_h = if self.options.show_header { 1u16 } else { 0u16 };
         let header_h = header_h.min(area.height);
 
-        let (content_area, scrollbar_x) = if self.options.show_scrollbar_y && area.width >= 2 {
-            (
-                Rect::new(area.x, area.y, area.width - 1, area.height),
-                Some(area.x + area.width - 1),
-            )
-        } else {
-            (area, None)
-        };
+        let layout = ScrollbarLayout::new(
+            area,
+            self.options.show_scrollbar_y,
+            self.options.show_scrollbar_x,
+        );
+        let content_area = layout.content;
 
         let header_area = Rect::new(content_area.x, content_area.y, content_area.width, header_h);
         let body_area = Rect::new(
@@ -358,14 +401,22 @@ impl DataGridView {
         } else {
             self.options.style
         };
-        let header_style = self.options.header_style.patch(theme.accent);
+        let header_style = theme.chrome.header.patch(self.options.header_style);
         let grid_line_style = if self.options.grid_line_style == Style::default() {
-            theme.text_muted
+            theme.chrome.grid_line
         } else {
             self.options.grid_line_style
         };
-        let cursor_style = self.options.cursor_style.patch(theme.accent);
-        let selected_style = self.options.selected_style.patch(theme.accent);
+        let cursor_style = theme
+            .chrome
+            .cursor_style(self.focused)
+            .patch(self.options.cursor_style);
+        let selected_style = theme.chrome.selected.patch(self.options.selected_style);
+        let scrollbar_style = if self.options.scrollbar_style == Style::default() {
+            theme.chrome.scrollbar
+        } else {
+            self.options.scrollbar_style
+        };
 
         buf.set_style(content_area, base_style);
         buf.set_style(header_area, header_style);
@@ -401,24 +452,44 @@ impl DataGridView {
             &mut render_cell,
         );
 
-        if let Some(sb_x) = scrollbar_x {
-            render::render_scrollbar(
-                Rect::new(sb_x, body_area.y, 1, body_area.height),
-                buf,
-                &ViewportState {
-                    x: 0,
-                    y: self.state.y,
-                    viewport_w: 1,
-                    viewport_h: body_area.height,
-                    content_w: 1,
-                    content_h: self.state.content_h,
-                },
-                self.options.scrollbar_style,
-            );
+        if let Some(track) = layout.vertical {
+            let track = Rect::new(track.x, body_area.y, 1, body_area.height);
+            render::render_scrollbar(track, buf, &self.state, scrollbar_style);
+            // Markers are fed in rows; the viewport counts cells.
+            let row_height = self.options.row_height.max(1);
+            let to_cells = |rows: std::ops::Range<u32>| {
+                rows.start.saturating_mul(row_height)..rows.end.saturating_mul(row_height)
+            };
+            let mut markers: Vec<ScrollbarMarker> = self
+                .scrollbar_markers
+                .iter()
+                .map(|m| ScrollbarMarker::range(to_cells(m.lines.clone()), m.kind))
+                .collect();
+            if let Some(rows) = self.selection.rows() {
+                let rows = *rows.start() as u32..*rows.end() as u32 + 1;
+                markers.push(ScrollbarMarker::range(
+                    to_cells(rows),
+                    MarkerKind::Selection,
+                ));
+            }
+            render::render_scrollbar_markers(track, buf, &self.state, &markers, &theme.chrome);
+        }
+        if let Some(track) = layout.horizontal {
+            render::render_scrollbar_x(track, buf, &self.state, scrollbar_style);
         }
+        theme.color_profile.apply_to_buffer(buf, area);
+    }
+
+    /// Sets the markers drawn on the vertical scrollbar (search hits, diagnostics, ...), in
+    /// rows. The selected rows are marked automatically.
+    pub fn set_scrollbar_markers(&mut self, markers: Vec<ScrollbarMarker>) {
+        self.scrollbar_markers = markers;
     }
 
     fn handle_key(&mut self, key: KeyEvent) -> DataGridAction {
+        if key.is_release() {
+            return DataGridAction::None;
+        }
         if self.rows == 0 || self.columns.is_empty() {
             self.cursor = None;
             self.selection = Selection::None;
@@ -790,6 +861,40 @@ impl DataGridView {
     }
 }
 
+/// A [`DataGridView`] bundled with its cell renderer, usable as a [`Component`].
+pub struct DataGridComponent<F> {
+    pub view: DataGridView,
+    render_cell: F,
+}
+
+impl<F> DataGridComponent<F>
+where
+    F: FnMut(Rect, DataGridCellContext, &mut Buffer, &Theme),
+{
+    pub fn new(view: DataGridView, render_cell: F) -> Self {
+        Self { view, render_cell }
+    }
+}
+
+impl<F> Component for DataGridComponent<F>
+where
+    F: FnMut(Rect, DataGridCellContext, &mut Buffer, &Theme),
+{
+    type Action = DataGridAction;
+
+    fn handle_event(&mut self, _area: Rect, event: InputEvent) -> DataGridAction {
+        self.view.handle_event(event)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        self.view.render(area, buf, theme, &mut self.render_cell);
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.view.set_focused(focused);
+    }
+}
+
 fn clamp_cursor(cursor: Option<Cell>, rows: usize, cols: usize) -> Option<Cell> {
     if rows == 0 || cols == 0 {
         return None;
@@ -864,8 +969,7 @@ mod tests {
     fn key_shift(code: KeyCode) -> KeyEvent {
         KeyEvent::new(code).with_modifiers(KeyModifiers {
             shift: true,
-            ctrl: false,
-            alt: false,
+            ..KeyModifiers::none()
         })
     }
 
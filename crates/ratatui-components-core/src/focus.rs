@@ -0,0 +1,314 @@
+//! Keyboard focus tracking for multi-pane apps.
+//!
+//! [`FocusManager`] keeps an ordered list of focusable ids, cycles through them with
+//! [`FocusBindings`] (Tab / BackTab by default), and supports nested scopes: pushing a scope (e.g.
+//! for a modal) traps focus inside it until it is popped, which restores the previous focus.
+//!
+//! The manager only tracks ids; after a focus change, tell each view whether it is focused (see
+//! [`crate::component::Component::set_focused`]) and pick border styles via
+//! [`crate::theme::ChromeTheme::border_style`].
+use crate::input::InputEvent;
+use crate::input::KeyCode;
+use crate::input::KeyEvent;
+use crate::input::KeyModifiers;
+use crate::keymap;
+use crate::keymap::Binding;
+
+/// Key bindings for moving focus between panes.
+///
+/// Defaults: `Tab` focuses the next pane, `BackTab` (Shift+Tab) the previous one.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct FocusBindings {
+    pub next: Vec<KeyEvent>,
+    pub prev: Vec<KeyEvent>,
+}
+
+impl Default for FocusBindings {
+    fn default() -> Self {
+        let shift = KeyModifiers {
+            shift: true,
+            ..KeyModifiers::none()
+        };
+        Self {
+            next: vec![KeyEvent::new(KeyCode::Tab)],
+            prev: vec![
+                KeyEvent::new(KeyCode::BackTab),
+                KeyEvent::new(KeyCode::BackTab).with_modifiers(shift),
+                KeyEvent::new(KeyCode::Tab).with_modifiers(shift),
+            ],
+        }
+    }
+}
+
+impl FocusBindings {
+    /// Returns `true` if `key` matches any configured focus-next binding.
+    pub fn is_next(&self, key: &KeyEvent) -> bool {
+        self.next.iter().any(|p| keymap::key_event_matches(p, key))
+    }
+
+    /// Returns `true` if `key` matches any configured focus-previous binding.
+    pub fn is_prev(&self, key: &KeyEvent) -> bool {
+        self.prev.iter().any(|p| keymap::key_event_matches(p, key))
+    }
+
+    /// Describes the configured bindings for help screens.
+    pub fn help_bindings(&self) -> Vec<Binding> {
+        [(&self.next, "next pane"), (&self.prev, "previous pane")]
+            .into_iter()
+            .filter(|(keys, _)| !keys.is_empty())
+            .map(|(keys, desc)| Binding::from_keys(desc, keys.clone()))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct FocusScope<Id> {
+    ids: Vec<Id>,
+    focused: Option<usize>,
+}
+
+impl<Id> FocusScope<Id> {
+    fn new(ids: Vec<Id>) -> Self {
+        let focused = (!ids.is_empty()).then_some(0);
+        Self { ids, focused }
+    }
+}
+
+/// Tracks which of a set of focusable ids has keyboard focus.
+///
+/// Ids are focused in the order they were added. Only the innermost scope is active: cycling and
+/// [`Self::focus`] never leave it.
+#[derive(Clone, Debug)]
+pub struct FocusManager<Id> {
+    scopes: Vec<FocusScope<Id>>,
+    bindings: FocusBindings,
+}
+
+impl<Id: Clone + PartialEq> Default for FocusManager<Id> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<Id: Clone + PartialEq> FocusManager<Id> {
+    /// Creates a manager over `ids`, focusing the first one.
+    pub fn new(ids: Vec<Id>) -> Self {
+        Self {
+            scopes: vec![FocusScope::new(ids)],
+            bindings: FocusBindings::default(),
+        }
+    }
+
+    pub fn with_bindings(mut self, bindings: FocusBindings) -> Self {
+        self.bindings = bindings;
+        self
+    }
+
+    pub fn bindings(&self) -> &FocusBindings {
+        &self.bindings
+    }
+
+    pub fn set_bindings(&mut self, bindings: FocusBindings) {
+        self.bindings = bindings;
+    }
+
+    /// The focusable ids of the active scope, in cycling order.
+    pub fn ids(&self) -> &[Id] {
+        &self.scope().ids
+    }
+
+    /// Appends `id` to the active scope, focusing it if nothing else is.
+    pub fn add(&mut self, id: Id) {
+        let scope = self.scope_mut();
+        if scope.ids.contains(&id) {
+            return;
+        }
+        scope.ids.push(id);
+        if scope.focused.is_none() {
+            scope.focused = Some(scope.ids.len() - 1);
+        }
+    }
+
+    /// Removes `id` from the active scope. If it was focused, focus moves to the next id.
+    pub fn remove(&mut self, id: &Id) -> bool {
+        let scope = self.scope_mut();
+        let Some(idx) = scope.ids.iter().position(|i| i == id) else {
+            return false;
+        };
+        scope.ids.remove(idx);
+        scope.focused = match scope.focused {
+            _ if scope.ids.is_empty() => None,
+            Some(f) if f > idx => Some(f - 1),
+            Some(f) => Some(f.min(scope.ids.len() - 1)),
+            None => None,
+        };
+        true
+    }
+
+    pub fn focused(&self) -> Option<&Id> {
+        let scope = self.scope();
+        scope.focused.map(|i| &scope.ids[i])
+    }
+
+    pub fn is_focused(&self, id: &Id) -> bool {
+        self.focused() == Some(id)
+    }
+
+    /// Focuses `id` if it belongs to the active scope. Returns `true` if focus changed.
+    pub fn focus(&mut self, id: &Id) -> bool {
+        let scope = self.scope_mut();
+        let Some(idx) = scope.ids.iter().position(|i| i == id) else {
+            return false;
+        };
+        let changed = scope.focused != Some(idx);
+        scope.focused = Some(idx);
+        changed
+    }
+
+    /// Focuses the next id in the active scope, wrapping around. Returns `true` if focus changed.
+    pub fn focus_next(&mut self) -> bool {
+        self.step(true)
+    }
+
+    /// Focuses the previous id in the active scope, wrapping around. Returns `true` if focus
+    /// changed.
+    pub fn focus_prev(&mut self) -> bool {
+        self.step(false)
+    }
+
+    /// Opens a nested scope over `ids` (e.g. a modal), focusing its first id. Focus stays inside
+    /// the scope until [`Self::pop_scope`].
+    pub fn push_scope(&mut self, ids: Vec<Id>) {
+        self.scopes.push(FocusScope::new(ids));
+    }
+
+    /// Closes the innermost scope, restoring the focus it trapped. The root scope is never popped.
+    pub fn pop_scope(&mut self) -> Option<Vec<Id>> {
+        if self.scopes.len() <= 1 {
+            return None;
+        }
+        self.scopes.pop().map(|s| s.ids)
+    }
+
+    /// Number of scopes pushed on top of the root scope.
+    pub fn scope_depth(&self) -> usize {
+        self.scopes.len() - 1
+    }
+
+    /// Moves focus if `key` matches a focus binding. Returns `true` if the key was consumed.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        if self.bindings.is_next(key) {
+            self.focus_next();
+            true
+        } else if self.bindings.is_prev(key) {
+            self.focus_prev();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Like [`Self::handle_key`], ignoring non-key events.
+    pub fn handle_event(&mut self, event: &InputEvent) -> bool {
+        match event {
+            InputEvent::Key(key) => self.handle_key(key),
+            _ => false,
+        }
+    }
+
+    fn step(&mut self, forward: bool) -> bool {
+        let scope = self.scope_mut();
+        let len = scope.ids.len();
+        if len == 0 {
+            return false;
+        }
+        let next = match scope.focused {
+            None if forward => 0,
+            None => len - 1,
+            Some(f) if forward => (f + 1) % len,
+            Some(f) => (f + len - 1) % len,
+        };
+        let changed = scope.focused != Some(next);
+        scope.focused = Some(next);
+        changed
+    }
+
+    fn scope(&self) -> &FocusScope<Id> {
+        self.scopes.last().expect("root scope")
+    }
+
+    fn scope_mut(&mut self) -> &mut FocusScope<Id> {
+        self.scopes.last_mut().expect("root scope")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code)
+    }
+
+    #[test]
+    fn cycles_with_tab_and_backtab() {
+        let mut f = FocusManager::new(vec!["a", "b", "c"]);
+        assert!(f.is_focused(&"a"));
+
+        assert!(f.handle_key(&key(KeyCode::Tab)));
+        assert_eq!(f.focused(), Some(&"b"));
+        f.focus_next();
+        f.focus_next();
+        assert_eq!(f.focused(), Some(&"a"));
+
+        assert!(f.handle_key(&key(KeyCode::BackTab)));
+        assert_eq!(f.focused(), Some(&"c"));
+        assert!(!f.handle_key(&key(KeyCode::Enter)));
+        assert_eq!(f.focused(), Some(&"c"));
+    }
+
+    #[test]
+    fn add_and_remove_keep_focus_consistent() {
+        let mut f = FocusManager::default();
+        assert_eq!(f.focused(), None);
+        assert!(!f.focus_next());
+
+        f.add(1);
+        f.add(2);
+        f.add(3);
+        assert_eq!(f.focused(), Some(&1));
+        assert!(f.focus(&3));
+        assert!(!f.focus(&3));
+        assert!(!f.focus(&9));
+
+        assert!(f.remove(&1));
+        assert_eq!(f.focused(), Some(&3));
+        assert!(f.remove(&3));
+        assert_eq!(f.focused(), Some(&2));
+        assert!(f.remove(&2));
+        assert_eq!(f.focused(), None);
+    }
+
+    #[test]
+    fn scopes_trap_focus_and_restore_on_pop() {
+        let mut f = FocusManager::new(vec!["left", "right"]);
+        f.focus(&"right");
+
+        f.push_scope(vec!["ok", "cancel"]);
+        assert_eq!(f.scope_depth(), 1);
+        assert!(f.is_focused(&"ok"));
+        assert!(!f.focus(&"left"));
+        f.focus_next();
+        f.focus_next();
+        assert!(f.is_focused(&"ok"));
+
+        assert_eq!(f.pop_scope(), Some(vec!["ok", "cancel"]));
+        assert!(f.is_focused(&"right"));
+        assert_eq!(f.pop_scope(), None);
+    }
+}
@@ -0,0 +1,92 @@
+//! Grapheme-cluster helpers for column math.
+//!
+//! A terminal cell shows a whole user-perceived character: ZWJ emoji sequences, flags, base
+//! letters with combining accents and Hangul jamo sequences are all single clusters made of
+//! several `char`s. Wrapping, slicing, selection and cursor movement iterate clusters (extended
+//! grapheme clusters, via `unicode-segmentation`) so they never split one.
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Iterates the extended grapheme clusters of `s`.
+pub fn graphemes(s: &str) -> unicode_segmentation::Graphemes<'_> {
+    s.graphemes(true)
+}
+
+/// Iterates the extended grapheme clusters of `s` with their byte offsets.
+pub fn grapheme_indices(s: &str) -> unicode_segmentation::GraphemeIndices<'_> {
+    s.grapheme_indices(true)
+}
+
+/// Display width of a single cluster in terminal cells (tabs are not expanded).
+pub fn width(grapheme: &str) -> usize {
+    UnicodeWidthStr::width(grapheme)
+}
+
+/// Number of clusters in `s`.
+pub fn count(s: &str) -> usize {
+    graphemes(s).count()
+}
+
+/// Byte offset of the cluster at index `idx`, or `s.len()` past the end.
+pub fn byte_index(s: &str, idx: usize) -> usize {
+    grapheme_indices(s).nth(idx).map_or(s.len(), |(b, _)| b)
+}
+
+/// Whether `s` is whitespace (checked on the cluster's first `char`).
+pub fn is_whitespace(grapheme: &str) -> bool {
+    grapheme.chars().next().is_some_and(char::is_whitespace)
+}
+
+/// Tricky Unicode inputs shared by the property tests of the modules built on these helpers.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use proptest::prelude::*;
+
+    pub(crate) const CLUSTERS: &[&str] = &[
+        "a",
+        "Z",
+        " ",
+        "-",
+        ".",
+        "\t",
+        "你",
+        "👍",
+        "👨\u{200d}👩\u{200d}👧\u{200d}👦",
+        "🏳\u{fe0f}\u{200d}🌈",
+        "🇯🇵",
+        "🇺🇸",
+        "e\u{301}",
+        "a\u{308}\u{331}",
+        "\u{1100}\u{1161}\u{11a8}",
+        "❤\u{fe0f}",
+        "👍🏽",
+    ];
+
+    /// Strings assembled from [`CLUSTERS`], so every cluster boundary is known.
+    pub(crate) fn text() -> impl Strategy<Value = String> {
+        prop::collection::vec(prop::sample::select(CLUSTERS), 0..24)
+            .prop_map(|parts| parts.concat())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clusters_are_single_units() {
+        for s in fixtures::CLUSTERS {
+            assert_eq!(count(s), 1, "{s:?}");
+        }
+        assert_eq!(
+            count(&fixtures::CLUSTERS.concat()),
+            fixtures::CLUSTERS.len()
+        );
+        assert_eq!(width("👨\u{200d}👩\u{200d}👧\u{200d}👦"), 2);
+        assert_eq!(width("🇯🇵"), 2);
+        assert_eq!(width("e\u{301}"), 1);
+        assert_eq!(width("\u{1100}\u{1161}\u{11a8}"), 2);
+        assert_eq!(byte_index("e\u{301}x", 1), 3);
+        assert_eq!(byte_index("ab", 5), 2);
+    }
+}
@@ -1,9 +1,21 @@
+use crate::input::KeyEvent;
+use crate::keymap;
 use crate::keymap::Binding;
+use crate::keymap::Continuation;
+use crate::keymap::Keymap;
+use crate::keymap::KeymapStack;
 use crate::render;
+use crate::scroll::ScrollBindings;
+use crate::selection::SelectionBindings;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Style;
 use ratatui::text::Span;
+use ratatui::widgets::Block;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Widget;
+use unicode_width::UnicodeWidthChar;
+use unicode_width::UnicodeWidthStr;
 
 #[derive(Clone, Debug)]
 pub struct HelpBarOptions {
@@ -81,6 +93,564 @@ impl HelpBar {
     }
 }
 
+/// A titled section of bindings in a [`HelpPanel`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BindingGroup {
+    pub title: String,
+    pub bindings: Vec<Binding>,
+}
+
+impl BindingGroup {
+    pub fn new(title: impl Into<String>, bindings: Vec<Binding>) -> Self {
+        Self {
+            title: title.into(),
+            bindings,
+        }
+    }
+}
+
+/// Builds the standard "Scroll" and "Selection" groups for a scrollable view.
+///
+/// View options expose this as `help_groups()` so help screens always reflect the configured
+/// bindings. Pass `None` for `selection` when selection is disabled.
+pub fn view_help_groups(
+    scroll: &ScrollBindings,
+    selection: Option<&SelectionBindings>,
+) -> Vec<BindingGroup> {
+    let mut groups = vec![BindingGroup::new("Scroll", scroll.help_bindings())];
+    if let Some(selection) = selection {
+        groups.push(BindingGroup::new("Selection", selection.help_bindings()));
+    }
+    groups.retain(|g| !g.bindings.is_empty());
+    groups
+}
+
+#[derive(Clone, Debug)]
+pub struct HelpPanelOptions {
+    pub style: Style,
+    pub key_style: Style,
+    pub title_style: Style,
+    /// Separator between key and description.
+    pub space: String,
+    /// Separator between bindings in short mode.
+    pub separator: String,
+    pub column_gap: u16,
+    pub ellipsis: String,
+    /// Hint appended in short mode when not every binding fits.
+    pub more_hint: String,
+}
+
+impl Default for HelpPanelOptions {
+    fn default() -> Self {
+        Self {
+            style: Style::default(),
+            key_style: Style::default(),
+            title_style: Style::default(),
+            space: " ".to_string(),
+            separator: " • ".to_string(),
+            column_gap: 4,
+            ellipsis: "…".to_string(),
+            more_hint: "? more".to_string(),
+        }
+    }
+}
+
+/// A full help screen listing grouped bindings in balanced columns.
+///
+/// In short mode it renders a single line like [`HelpBar`], but truncates with an ellipsis and a
+/// "? more" hint instead of clipping silently.
+#[derive(Clone, Debug, Default)]
+pub struct HelpPanel {
+    groups: Vec<BindingGroup>,
+    options: HelpPanelOptions,
+    short: bool,
+}
+
+impl HelpPanel {
+    pub fn new(groups: Vec<BindingGroup>) -> Self {
+        Self {
+            groups,
+            options: HelpPanelOptions::default(),
+            short: false,
+        }
+    }
+
+    pub fn with_options(groups: Vec<BindingGroup>, options: HelpPanelOptions) -> Self {
+        Self {
+            groups,
+            options,
+            short: false,
+        }
+    }
+
+    pub fn set_groups(&mut self, groups: Vec<BindingGroup>) {
+        self.groups = groups;
+    }
+
+    pub fn groups(&self) -> &[BindingGroup] {
+        &self.groups
+    }
+
+    pub fn is_short(&self) -> bool {
+        self.short
+    }
+
+    pub fn set_short(&mut self, short: bool) {
+        self.short = short;
+    }
+
+    pub fn toggle_short(&mut self) {
+        self.short = !self.short;
+    }
+
+    pub fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        buf.set_style(area, self.options.style);
+        if self.short {
+            self.render_short(area, buf);
+        } else {
+            self.render_full(area, buf);
+        }
+    }
+
+    fn render_short(&self, area: Rect, buf: &mut Buffer) {
+        let o = &self.options;
+        let max = area.width as usize;
+        let all: Vec<&Binding> = self.groups.iter().flat_map(|g| &g.bindings).collect();
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        let mut used = 0usize;
+        for (i, b) in all.iter().enumerate() {
+            let sep = if i > 0 { o.separator.as_str() } else { "" };
+            let w = sep.width() + b.help_key.width() + o.space.width() + b.help_desc.width();
+            let is_last = i + 1 == all.len();
+            let reserve = if is_last {
+                0
+            } else {
+                o.space.width() + o.ellipsis.width() + o.space.width() + o.more_hint.width()
+            };
+            if used + w + reserve > max {
+                let hint = format!("{}{}{}{}", o.space, o.ellipsis, o.space, o.more_hint);
+                let hint = if used == 0 {
+                    hint.trim_start().to_string()
+                } else {
+                    hint
+                };
+                spans.push(Span::styled(hint, o.style));
+                break;
+            }
+            if i > 0 {
+                spans.push(Span::styled(o.separator.clone(), o.style));
+            }
+            spans.push(Span::styled(b.help_key.clone(), o.key_style));
+            spans.push(Span::styled(o.space.clone(), o.style));
+            spans.push(Span::styled(b.help_desc.clone(), o.style));
+            used += w;
+        }
+        render::render_spans_clipped(area.x, area.y, 0, area.width, buf, &spans, o.style);
+    }
+
+    fn render_full(&self, area: Rect, buf: &mut Buffer) {
+        let o = &self.options;
+        let columns = self.layout_columns(area);
+        let mut x = area.x;
+        for column in columns {
+            if x >= area.right() {
+                break;
+            }
+            let width = column.width.min(area.right() - x);
+            let mut y = area.y;
+            for (gi, group) in column.groups.iter().map(|&i| &self.groups[i]).enumerate() {
+                if gi > 0 {
+                    y = y.saturating_add(1);
+                }
+                if y >= area.bottom() {
+                    break;
+                }
+                let title = truncate_to_width(&group.title, width as usize, &o.ellipsis);
+                render::render_str_clipped(x, y, 0, width, buf, &title, o.title_style);
+                y += 1;
+                for b in &group.bindings {
+                    if y >= area.bottom() {
+                        break;
+                    }
+                    let pad = column.key_width.saturating_sub(b.help_key.width());
+                    let desc_max =
+                        (width as usize).saturating_sub(column.key_width + o.space.width());
+                    let spans = vec![
+                        Span::styled(b.help_key.clone(), o.key_style),
+                        Span::styled(format!("{}{}", " ".repeat(pad), o.space), o.style),
+                        Span::styled(
+                            truncate_to_width(&b.help_desc, desc_max, &o.ellipsis),
+                            o.style,
+                        ),
+                    ];
+                    render::render_spans_clipped(x, y, 0, width, buf, &spans, o.style);
+                    y += 1;
+                }
+            }
+            x = x.saturating_add(width).saturating_add(o.column_gap);
+        }
+    }
+
+    /// Splits groups into the fewest balanced columns that fit `area`'s height, using more
+    /// columns only while they still fit its width.
+    fn layout_columns(&self, area: Rect) -> Vec<HelpColumn> {
+        let n = self.groups.len();
+        let mut best: Option<Vec<HelpColumn>> = None;
+        for cols in 1..=n.max(1) {
+            let columns = self.partition(cols);
+            let total_w: usize = columns.iter().map(|c| c.width as usize).sum::<usize>()
+                + columns.len().saturating_sub(1) * self.options.column_gap as usize;
+            if total_w > area.width as usize && best.is_some() {
+                break;
+            }
+            let fits_h = columns.iter().all(|c| c.height <= area.height as usize);
+            best = Some(columns);
+            if fits_h {
+                break;
+            }
+        }
+        best.unwrap_or_default()
+    }
+
+    fn partition(&self, cols: usize) -> Vec<HelpColumn> {
+        let heights: Vec<usize> = self.groups.iter().map(|g| 1 + g.bindings.len()).collect();
+        let total: usize = heights.iter().sum::<usize>() + heights.len().saturating_sub(1);
+        let target = total.div_ceil(cols.max(1));
+        let mut columns: Vec<Vec<usize>> = vec![Vec::new()];
+        let mut h = 0usize;
+        for (i, gh) in heights.iter().enumerate() {
+            let last = columns.last().expect("non-empty");
+            if !last.is_empty() && h + 1 + gh > target && columns.len() < cols {
+                columns.push(Vec::new());
+                h = 0;
+            }
+            let cur = columns.last_mut().expect("non-empty");
+            if !cur.is_empty() {
+                h += 1;
+            }
+            cur.push(i);
+            h += gh;
+        }
+        columns
+            .into_iter()
+            .filter(|c| !c.is_empty())
+            .map(|groups| self.measure_column(groups))
+            .collect()
+    }
+
+    fn measure_column(&self, groups: Vec<usize>) -> HelpColumn {
+        let space = self.options.space.width();
+        let bindings = || groups.iter().flat_map(|&i| &self.groups[i].bindings);
+        let key_width = bindings().map(|b| b.help_key.width()).max().unwrap_or(0);
+        let desc_width = bindings().map(|b| b.help_desc.width()).max().unwrap_or(0);
+        let title_width = groups
+            .iter()
+            .map(|&i| self.groups[i].title.width())
+            .max()
+            .unwrap_or(0);
+        let height = groups
+            .iter()
+            .map(|&i| 1 + self.groups[i].bindings.len())
+            .sum::<usize>()
+            + groups.len().saturating_sub(1);
+        let width = title_width.max(key_width + space + desc_width);
+        HelpColumn {
+            groups,
+            key_width,
+            width: width.min(u16::MAX as usize) as u16,
+            height,
+        }
+    }
+}
+
+struct HelpColumn {
+    groups: Vec<usize>,
+    key_width: usize,
+    width: u16,
+    height: usize,
+}
+
+fn truncate_to_width(s: &str, max: usize, ellipsis: &str) -> String {
+    if s.width() <= max {
+        return s.to_string();
+    }
+    let budget = max.saturating_sub(ellipsis.width());
+    let mut out = String::new();
+    let mut used = 0usize;
+    for ch in s.chars() {
+        let w = ch.width().unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        out.push(ch);
+        used += w;
+    }
+    if ellipsis.width() <= max {
+        out.push_str(ellipsis);
+    }
+    out
+}
+
+/// Where an overlay is placed inside the area it is rendered into.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverlayAnchor {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+    /// Bottom edge, spanning the full width.
+    Bottom,
+    Center,
+}
+
+impl OverlayAnchor {
+    fn place(self, area: Rect, width: u16, height: u16) -> Rect {
+        let width = width.min(area.width);
+        let height = height.min(area.height);
+        let left = area.x;
+        let right = area.x + area.width - width;
+        let top = area.y;
+        let bottom = area.y + area.height - height;
+        match self {
+            OverlayAnchor::TopLeft => Rect::new(left, top, width, height),
+            OverlayAnchor::TopRight => Rect::new(right, top, width, height),
+            OverlayAnchor::BottomLeft => Rect::new(left, bottom, width, height),
+            OverlayAnchor::BottomRight => Rect::new(right, bottom, width, height),
+            OverlayAnchor::Bottom => Rect::new(left, bottom, area.width, height),
+            OverlayAnchor::Center => Rect::new(
+                area.x + (area.width - width) / 2,
+                area.y + (area.height - height) / 2,
+                width,
+                height,
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WhichKeyOptions {
+    pub anchor: OverlayAnchor,
+    pub style: Style,
+    pub border_style: Style,
+    pub key_style: Style,
+    pub group_style: Style,
+    pub separator: String,
+    pub column_gap: u16,
+    /// Maximum number of rows (excluding the border). Entries that do not fit in the rows and
+    /// the columns the area allows are summarized by a final `+N more` cell.
+    pub max_rows: u16,
+    /// Description shown for keys that only lead to longer sequences.
+    pub group_desc: String,
+}
+
+impl Default for WhichKeyOptions {
+    fn default() -> Self {
+        Self {
+            anchor: OverlayAnchor::default(),
+            style: Style::default(),
+            border_style: Style::default(),
+            key_style: Style::default(),
+            group_style: Style::default(),
+            separator: " → ".to_string(),
+            column_gap: 3,
+            max_rows: 8,
+            group_desc: "+more".to_string(),
+        }
+    }
+}
+
+/// A which-key style overlay listing the continuations of a pending key sequence.
+///
+/// Entries are plain [`Binding`]s, so the same `help_key`/`help_desc` metadata used by
+/// [`HelpBar`] is shown here. Build it from a [`Keymap<Binding>`] or [`KeymapStack<Binding>`]
+/// while a prefix is pending, and render it over the main UI.
+#[derive(Clone, Debug, Default)]
+pub struct WhichKey {
+    pending: Vec<KeyEvent>,
+    entries: Vec<Binding>,
+    options: WhichKeyOptions,
+}
+
+impl WhichKey {
+    pub fn new(pending: Vec<KeyEvent>, entries: Vec<Binding>) -> Self {
+        Self {
+            pending,
+            entries,
+            options: WhichKeyOptions::default(),
+        }
+    }
+
+    pub fn with_options(mut self, options: WhichKeyOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Builds the overlay from the keys that can follow `pending` in `keymap`.
+    pub fn from_keymap(keymap: &Keymap<Binding>, pending: &[KeyEvent]) -> Self {
+        Self::new(
+            pending.to_vec(),
+            continuation_bindings(keymap.continuations(pending)),
+        )
+    }
+
+    /// Builds the overlay from the stack's current pending sequence.
+    pub fn from_stack(stack: &KeymapStack<Binding>) -> Self {
+        Self::new(
+            stack.pending().to_vec(),
+            continuation_bindings(stack.continuations()),
+        )
+    }
+
+    pub fn entries(&self) -> &[Binding] {
+        &self.entries
+    }
+
+    /// Returns `true` when there is nothing to show (no pending prefix or no continuations).
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty() || self.entries.is_empty()
+    }
+
+    /// Computes the overlay rect inside `area`, or `None` if it would be empty or not fit.
+    pub fn popup_area(&self, area: Rect) -> Option<Rect> {
+        let layout = self.layout(area)?;
+        Some(self.options.anchor.place(area, layout.width, layout.height))
+    }
+
+    /// Renders the overlay at its anchor inside `area` (usually the whole frame).
+    pub fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let Some(layout) = self.layout(area) else {
+            return;
+        };
+        let popup = self.options.anchor.place(area, layout.width, layout.height);
+        Clear.render(popup, buf);
+        let block = Block::bordered()
+            .border_style(self.options.border_style)
+            .style(self.options.style)
+            .title(Span::styled(
+                format!(" {} ", keymap::format_key_sequence(&self.pending)),
+                self.options.key_style,
+            ));
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let step = layout.col_width + self.options.column_gap;
+        let cell = |i: usize| {
+            let (col, row) = (i / layout.rows, i % layout.rows);
+            let x = inner.x + (col as u16) * step;
+            let y = inner.y + row as u16;
+            (y < inner.bottom() && x < inner.right())
+                .then(|| (x, y, layout.col_width.min(inner.right() - x)))
+        };
+        // When entries are cut, the last cell says how many are hidden.
+        let capacity = layout.rows * layout.cols;
+        let shown = if self.entries.len() > capacity {
+            capacity - 1
+        } else {
+            self.entries.len()
+        };
+        for (i, b) in self.entries.iter().take(shown).enumerate() {
+            let Some((x, y, max_cols)) = cell(i) else {
+                continue;
+            };
+            let desc_style = if is_group(b) {
+                self.options.group_style
+            } else {
+                self.options.style
+            };
+            let pad = layout.key_width.saturating_sub(b.help_key.width());
+            let spans = vec![
+                Span::styled(b.help_key.clone(), self.options.key_style),
+                Span::styled(" ".repeat(pad), self.options.style),
+                Span::styled(self.options.separator.clone(), self.options.style),
+                Span::styled(self.desc(b).to_string(), desc_style),
+            ];
+            render::render_spans_clipped(x, y, 0, max_cols, buf, &spans, self.options.style);
+        }
+        if shown < self.entries.len()
+            && let Some((x, y, max_cols)) = cell(shown)
+        {
+            let more = format!("+{} more", self.entries.len() - shown);
+            render::render_str_clipped(x, y, 0, max_cols, buf, &more, self.options.group_style);
+        }
+    }
+
+    fn desc<'a>(&'a self, b: &'a Binding) -> &'a str {
+        if is_group(b) {
+            &self.options.group_desc
+        } else {
+            &b.help_desc
+        }
+    }
+
+    fn layout(&self, area: Rect) -> Option<WhichKeyLayout> {
+        if self.is_empty() || area.width < 3 || area.height < 3 {
+            return None;
+        }
+        let key_width = self
+            .entries
+            .iter()
+            .map(|b| b.help_key.width())
+            .max()
+            .unwrap_or(0);
+        let desc_width = self
+            .entries
+            .iter()
+            .map(|b| self.desc(b).width())
+            .max()
+            .unwrap_or(0);
+        let inner_w = area.width - 2;
+        let cell_w =
+            (key_width + self.options.separator.width() + desc_width).min(inner_w as usize) as u16;
+        let gap = self.options.column_gap;
+        let max_rows = (self.options.max_rows.max(1)).min(area.height - 2) as usize;
+        let fit_cols = ((inner_w + gap) / (cell_w + gap).max(1)).max(1) as usize;
+        let n = self.entries.len();
+        let cols = n.div_ceil(max_rows).clamp(1, fit_cols);
+        let rows = n.div_ceil(cols).min(max_rows);
+        let width = (cols as u16) * cell_w + (cols as u16 - 1) * gap + 2;
+        Some(WhichKeyLayout {
+            rows,
+            cols,
+            key_width,
+            col_width: cell_w,
+            width,
+            height: rows as u16 + 2,
+        })
+    }
+}
+
+struct WhichKeyLayout {
+    rows: usize,
+    cols: usize,
+    key_width: usize,
+    col_width: u16,
+    width: u16,
+    height: u16,
+}
+
+/// Group entries (keys that only lead to longer sequences) have no `keys`; the overlay shows
+/// [`WhichKeyOptions::group_desc`] for them.
+fn is_group(b: &Binding) -> bool {
+    b.keys.is_empty()
+}
+
+fn continuation_bindings(continuations: Vec<Continuation<'_, Binding>>) -> Vec<Binding> {
+    continuations
+        .into_iter()
+        .map(|c| {
+            let help_key = keymap::format_key(&c.key);
+            match c.action {
+                Some(b) => Binding::new(help_key, b.help_desc.clone(), vec![c.key]),
+                None => Binding::new(help_key, "", Vec::new()),
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,4 +668,126 @@ mod tests {
         let mut buf = Buffer::empty(Rect::new(0, 0, 3, 1));
         hb.render_ref(Rect::new(0, 0, 3, 1), &mut buf);
     }
+
+    fn row_text(buf: &Buffer, y: u16) -> String {
+        (0..buf.area.width)
+            .map(|x| buf[(x, y)].symbol())
+            .collect::<String>()
+    }
+
+    fn group(title: &str, keys: &[(char, &str)]) -> BindingGroup {
+        BindingGroup::new(
+            title,
+            keys.iter()
+                .map(|(c, d)| Binding::from_keys(*d, vec![keymap::key_char(*c)]))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn help_panel_balances_groups_into_columns() {
+        let panel = HelpPanel::new(vec![
+            group("Move", &[('j', "down"), ('k', "up")]),
+            group("Edit", &[('i', "insert"), ('x', "cut")]),
+        ]);
+        // Too short for one column (3 + 1 + 3 rows), so the groups go side by side.
+        let area = Rect::new(0, 0, 30, 3);
+        let mut buf = Buffer::empty(area);
+        panel.render_ref(area, &mut buf);
+        assert_eq!(row_text(&buf, 0).trim_end(), "Move      Edit");
+        assert_eq!(row_text(&buf, 1).trim_end(), "j down    i insert");
+    }
+
+    #[test]
+    fn help_panel_short_mode_truncates_with_hint() {
+        let mut panel = HelpPanel::new(vec![group(
+            "Keys",
+            &[('j', "down"), ('k', "up"), ('q', "quit")],
+        )]);
+        panel.set_short(true);
+        let area = Rect::new(0, 0, 20, 1);
+        let mut buf = Buffer::empty(area);
+        panel.render_ref(area, &mut buf);
+        assert_eq!(row_text(&buf, 0).trim_end(), "j down … ? more");
+    }
+
+    #[test]
+    fn view_help_groups_follow_options() {
+        let scroll = ScrollBindings {
+            page_down: vec![keymap::key_char(' ')],
+            ..Default::default()
+        };
+        let groups = view_help_groups(&scroll, None);
+        assert_eq!(groups.len(), 1);
+        assert!(
+            groups[0]
+                .bindings
+                .iter()
+                .any(|b| b.help_key == "space" && b.help_desc == "page down")
+        );
+        let groups = view_help_groups(&scroll, Some(&SelectionBindings::default()));
+        assert_eq!(groups[1].bindings[0].help_key, "y");
+    }
+
+    #[test]
+    fn which_key_lists_continuations_at_anchor() {
+        let mut map = Keymap::new();
+        for (seq, desc) in [("g g", "top"), ("g e", "end of word"), ("g c c", "comment")] {
+            let keys = keymap::parse_key_sequence(seq).unwrap();
+            map.bind(&keys, Binding::new(seq, desc, keys.clone()));
+        }
+        let wk = WhichKey::from_keymap(&map, &[keymap::key_char('g')]);
+        assert_eq!(wk.entries().len(), 3);
+        assert!(WhichKey::from_keymap(&map, &[]).is_empty());
+
+        let area = Rect::new(0, 0, 30, 8);
+        let popup = wk.popup_area(area).unwrap();
+        assert_eq!(popup.bottom(), area.bottom());
+        assert_eq!(popup.right(), area.right());
+        assert_eq!(popup.height, 5);
+
+        let mut buf = Buffer::empty(area);
+        wk.render_ref(area, &mut buf);
+        let rows: Vec<String> = (popup.y..popup.bottom())
+            .map(|y| row_text(&buf, y))
+            .collect();
+        assert!(rows[0].contains(" g "));
+        assert!(rows[1].contains("g → top"));
+        assert!(rows[2].contains("e → end of word"));
+        assert!(rows[3].contains("c → +more"));
+    }
+
+    #[test]
+    fn which_key_wraps_into_columns_when_short() {
+        let entries: Vec<Binding> = ('a'..='f')
+            .map(|c| Binding::from_keys("x", vec![keymap::key_char(c)]))
+            .collect();
+        let wk = WhichKey::new(vec![keymap::key_char('z')], entries);
+        let area = Rect::new(0, 0, 40, 5);
+        let popup = wk.popup_area(area).unwrap();
+        // 3 rows fit, so 6 entries become 2 columns of `a → x` (5 cols) plus a 3-col gap.
+        assert_eq!(popup.height, 5);
+        assert_eq!(popup.width, 5 + 3 + 5 + 2);
+    }
+
+    #[test]
+    fn which_key_counts_entries_that_do_not_fit() {
+        let entries: Vec<Binding> = ('a'..='z')
+            .map(|c| Binding::from_keys("jump", vec![keymap::key_char(c)]))
+            .collect();
+        let wk = WhichKey::new(vec![keymap::key_char('z')], entries);
+        let area = Rect::new(0, 0, 20, 5);
+        let popup = wk.popup_area(area).unwrap();
+        // One column of 3 rows: two entries and the overflow count.
+        assert_eq!(popup.height, 5);
+
+        let mut buf = Buffer::empty(area);
+        wk.render_ref(area, &mut buf);
+        let rows: Vec<String> = (popup.y + 1..popup.bottom() - 1)
+            .map(|y| row_text(&buf, y))
+            .collect();
+        assert!(rows[0].contains("a → jump"));
+        assert!(rows[1].contains("b → jump"));
+        assert!(rows[2].contains("+24 more"));
+    }
 }
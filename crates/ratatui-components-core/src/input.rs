@@ -1,17 +1,31 @@
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+use ratatui::layout::Position;
+use ratatui::layout::Rect;
+use std::time::Duration;
+use std::time::Instant;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct KeyModifiers {
     pub shift: bool,
     pub ctrl: bool,
     pub alt: bool,
+    /// The Super/Windows/Command key (`super` is a Rust keyword).
+    pub super_: bool,
+    pub hyper: bool,
+    pub meta: bool,
 }
 
 impl KeyModifiers {
     pub fn none() -> Self {
         Self::default()
     }
+
+    /// Whether a modifier other than Shift is held, i.e. the key is a shortcut rather than text.
+    pub fn has_command_modifier(&self) -> bool {
+        self.ctrl || self.alt || self.super_ || self.hyper || self.meta
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum KeyCode {
     Char(char),
     Enter,
@@ -27,12 +41,51 @@ pub enum KeyCode {
     End,
     PageUp,
     PageDown,
+    /// Function keys, `F(1)` through `F(24)`.
+    F(u8),
+    Insert,
+    /// Shift+Tab, as reported by most terminals.
+    BackTab,
+    Menu,
+    Media(MediaKeyCode),
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// Media keys, typically only reported with the kitty keyboard protocol.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MediaKeyCode {
+    Play,
+    Pause,
+    PlayPause,
+    Reverse,
+    Stop,
+    FastForward,
+    Rewind,
+    TrackNext,
+    TrackPrevious,
+    Record,
+    LowerVolume,
+    RaiseVolume,
+    MuteVolume,
+}
+
+/// Whether a key was pressed, auto-repeated, or released.
+///
+/// Most terminals only report presses. Repeats and releases require an enhanced protocol such as
+/// the kitty keyboard protocol. Bindings match presses and repeats but never releases (see
+/// [`crate::keymap::key_event_matches`]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum KeyEventKind {
+    #[default]
+    Press,
+    Repeat,
+    Release,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct KeyEvent {
     pub code: KeyCode,
     pub modifiers: KeyModifiers,
+    pub kind: KeyEventKind,
 }
 
 impl KeyEvent {
@@ -40,6 +93,7 @@ impl KeyEvent {
         Self {
             code,
             modifiers: KeyModifiers::none(),
+            kind: KeyEventKind::Press,
         }
     }
 
@@ -47,6 +101,15 @@ impl KeyEvent {
         self.modifiers = modifiers;
         self
     }
+
+    pub fn with_kind(mut self, kind: KeyEventKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn is_release(&self) -> bool {
+        self.kind == KeyEventKind::Release
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -54,6 +117,18 @@ pub enum InputEvent {
     Key(KeyEvent),
     Paste(String),
     Mouse(MouseEvent),
+    /// The terminal was resized to `width` x `height` cells.
+    Resize {
+        width: u16,
+        height: u16,
+    },
+    FocusGained,
+    FocusLost,
+    /// A timer tick carrying the caller's current time.
+    ///
+    /// Terminals never emit ticks; apps send them from their event loop to drive animations,
+    /// cursor blink and timeouts.
+    Tick(Instant),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -70,6 +145,12 @@ pub enum MouseEventKind {
     Up(MouseButton),
     ScrollUp,
     ScrollDown,
+    /// Horizontal wheel (or trackpad swipe) towards the start of the line.
+    ScrollLeft,
+    ScrollRight,
+    /// The pointer moved with no button held. Only reported when the terminal tracks all motion
+    /// (e.g. crossterm's `EnableMouseCapture`).
+    Moved,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -79,3 +160,175 @@ pub struct MouseEvent {
     pub kind: MouseEventKind,
     pub modifiers: KeyModifiers,
 }
+
+impl MouseEvent {
+    /// Returns the pointer position relative to `area`'s top-left corner, or `None` if the
+    /// pointer lies outside `area`.
+    pub fn position_in(&self, area: Rect) -> Option<(u16, u16)> {
+        area.contains(Position::new(self.x, self.y))
+            .then(|| (self.x - area.x, self.y - area.y))
+    }
+}
+
+/// A mouse press annotated with how many rapid presses it completes.
+///
+/// `count` is 1 for a single click, 2 for a double-click and 3 for a triple-click.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Click {
+    pub x: u16,
+    pub y: u16,
+    pub button: MouseButton,
+    pub count: u8,
+}
+
+/// Detects double- and triple-clicks from a stream of mouse events.
+///
+/// Terminals only report individual presses and releases. Feed every mouse event to
+/// [`ClickTracker::on_mouse`] together with the time it was received; each `Down` yields a
+/// [`Click`] whose `count` grows while presses land on the same cell with the same button within
+/// the click interval. Dragging, or releasing on a different cell, ends the streak. After a
+/// triple-click the count starts over at 1.
+#[derive(Clone, Debug)]
+pub struct ClickTracker {
+    interval: Duration,
+    last: Option<(Click, Instant)>,
+}
+
+impl Default for ClickTracker {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(500),
+            last: None,
+        }
+    }
+}
+
+impl ClickTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum delay between presses that still counts as one multi-click.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Records `event`, returning a [`Click`] for button presses.
+    pub fn on_mouse(&mut self, event: &MouseEvent, now: Instant) -> Option<Click> {
+        match event.kind {
+            MouseEventKind::Down(button) => {
+                let count = match self.last {
+                    Some((prev, at))
+                        if prev.button == button
+                            && prev.x == event.x
+                            && prev.y == event.y
+                            && prev.count < 3
+                            && now.saturating_duration_since(at) <= self.interval =>
+                    {
+                        prev.count + 1
+                    }
+                    _ => 1,
+                };
+                let click = Click {
+                    x: event.x,
+                    y: event.y,
+                    button,
+                    count,
+                };
+                self.last = Some((click, now));
+                Some(click)
+            }
+            MouseEventKind::Drag(_) => {
+                self.last = None;
+                None
+            }
+            MouseEventKind::Up(_) => {
+                if self
+                    .last
+                    .is_some_and(|(prev, _)| prev.x != event.x || prev.y != event.y)
+                {
+                    self.last = None;
+                }
+                None
+            }
+            MouseEventKind::ScrollUp
+            | MouseEventKind::ScrollDown
+            | MouseEventKind::ScrollLeft
+            | MouseEventKind::ScrollRight
+            | MouseEventKind::Moved => None,
+        }
+    }
+
+    /// Forgets the current click streak.
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mouse(kind: MouseEventKind, x: u16) -> MouseEvent {
+        MouseEvent {
+            x,
+            y: 0,
+            kind,
+            modifiers: KeyModifiers::none(),
+        }
+    }
+
+    #[test]
+    fn counts_rapid_clicks_on_the_same_cell() {
+        let mut t = ClickTracker::new();
+        let t0 = Instant::now();
+        let down = mouse(MouseEventKind::Down(MouseButton::Left), 3);
+        let up = mouse(MouseEventKind::Up(MouseButton::Left), 3);
+
+        let mut counts = Vec::new();
+        for i in 0..4 {
+            let at = t0 + Duration::from_millis(100 * i);
+            counts.push(t.on_mouse(&down, at).unwrap().count);
+            assert_eq!(t.on_mouse(&up, at), None);
+        }
+        assert_eq!(counts, vec![1, 2, 3, 1]);
+    }
+
+    #[test]
+    fn streak_breaks_on_timeout_move_or_drag() {
+        let mut t = ClickTracker::new();
+        let t0 = Instant::now();
+        let down = |x| mouse(MouseEventKind::Down(MouseButton::Left), x);
+
+        assert_eq!(t.on_mouse(&down(1), t0).unwrap().count, 1);
+        let late = t0 + Duration::from_secs(1);
+        assert_eq!(t.on_mouse(&down(1), late).unwrap().count, 1);
+        assert_eq!(t.on_mouse(&down(2), late).unwrap().count, 1);
+
+        t.on_mouse(&mouse(MouseEventKind::Drag(MouseButton::Left), 4), late);
+        assert_eq!(t.on_mouse(&down(2), late).unwrap().count, 1);
+
+        let right = mouse(MouseEventKind::Down(MouseButton::Right), 2);
+        assert_eq!(t.on_mouse(&right, late).unwrap().count, 1);
+    }
+
+    #[test]
+    fn position_in_is_relative_to_area() {
+        let area = Rect::new(2, 1, 4, 3);
+        let at = |x, y| MouseEvent {
+            x,
+            y,
+            kind: MouseEventKind::Moved,
+            modifiers: KeyModifiers::none(),
+        };
+        assert_eq!(at(2, 1).position_in(area), Some((0, 0)));
+        assert_eq!(at(5, 3).position_in(area), Some((3, 2)));
+        assert_eq!(at(6, 3).position_in(area), None);
+        assert_eq!(at(1, 2).position_in(area), None);
+    }
+}
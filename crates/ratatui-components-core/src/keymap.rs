@@ -1,8 +1,43 @@
+//! Key bindings and key spec strings.
+//!
+//! Bindings can be built in code via [`key_char`]/[`key_ctrl`], or parsed from human-readable
+//! key specs such as `"ctrl+shift+k"`, `"alt+enter"` or `"G"` via [`parse_key`]. The inverse,
+//! [`format_key`], produces the canonical spelling, which is also what [`Binding::from_keys`]
+//! uses to derive `help_key`.
+//!
+//! ## Key spec syntax
+//!
+//! - Modifiers are joined with `+` and come before the key: `ctrl`, `alt`, `shift`, `super`,
+//!   `hyper`, `meta` (aliases: `control`, `option`/`opt`, `cmd`/`win`). Modifier names are
+//!   case-insensitive.
+//! - Named keys are case-insensitive: `enter`, `backspace`, `delete`, `tab`, `backtab`, `esc`,
+//!   `left`, `right`, `up`, `down`, `home`, `end`, `pageup`, `pagedown`, `insert`, `menu`,
+//!   `space`, `f1`–`f24`, and media keys such as `playpause` or `volumeup` (plus a few aliases
+//!   like `return`, `escape`, `pgup`).
+//! - Any other single character is taken literally, so `"G"` and `"g"` are different keys.
+//!   Use `"+"` (or `"ctrl++"`) for the plus key itself.
+//!
+//! Multi-key sequences (`"g g"`, `"ctrl+x ctrl+s"`) are whitespace-separated key specs; see
+//! [`parse_key_sequence`] and [`Keymap`] for matching them incrementally.
+//!
+//! Apps that combine a global keymap, per-pane keymaps and modal overlays can stack them in a
+//! [`KeymapStack`] of [`KeymapLayer`]s, with per-layer [`Mode`]s, pass-through and
+//! [`KeymapStack::validate`] for reporting overlapping bindings at startup.
+//!
+//! With the `serde` feature, [`KeyEvent`] (de)serializes as a key spec string, so bindings can
+//! be loaded from TOML/JSON config files.
 use crate::input::KeyCode;
 use crate::input::KeyEvent;
+use crate::input::KeyEventKind;
 use crate::input::KeyModifiers;
+use crate::input::MediaKeyCode;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use std::time::Instant;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Binding {
     pub keys: Vec<KeyEvent>,
     pub help_key: String,
@@ -22,17 +57,46 @@ impl Binding {
         }
     }
 
+    /// Creates a binding whose `help_key` is derived from `keys` (e.g. `"up/k"`).
+    pub fn from_keys(help_desc: impl Into<String>, keys: Vec<KeyEvent>) -> Self {
+        Self {
+            help_key: format_keys(&keys),
+            help_desc: help_desc.into(),
+            keys,
+        }
+    }
+
+    /// Parses each entry of `specs` with [`parse_key`] and derives `help_key` from the result.
+    pub fn parse(help_desc: impl Into<String>, specs: &[&str]) -> Result<Self, KeyParseError> {
+        let keys = parse_keys(specs)?;
+        Ok(Self::from_keys(help_desc, keys))
+    }
+
     pub fn matches(&self, event: &KeyEvent) -> bool {
         self.keys.iter().any(|k| key_event_matches(k, event))
     }
 }
 
+/// Returns `true` if `event` has the same code and modifiers as `pattern`.
+///
+/// Press and repeat events match; release events never do, so widgets don't act twice when an
+/// enhanced keyboard protocol reports both.
 pub fn key_event_matches(pattern: &KeyEvent, event: &KeyEvent) -> bool {
-    pattern.code == event.code && modifiers_match(pattern.modifiers, event.modifiers)
+    event.kind != KeyEventKind::Release && same_key(pattern, event)
+}
+
+/// Whether two bindings name the same key, ignoring the event kind.
+fn same_key(a: &KeyEvent, b: &KeyEvent) -> bool {
+    a.code == b.code && modifiers_match(a.modifiers, b.modifiers)
 }
 
 fn modifiers_match(pattern: KeyModifiers, event: KeyModifiers) -> bool {
-    pattern.shift == event.shift && pattern.ctrl == event.ctrl && pattern.alt == event.alt
+    pattern.shift == event.shift
+        && pattern.ctrl == event.ctrl
+        && pattern.alt == event.alt
+        && pattern.super_ == event.super_
+        && pattern.hyper == event.hyper
+        && pattern.meta == event.meta
 }
 
 pub fn key_char(c: char) -> KeyEvent {
@@ -41,12 +105,922 @@ pub fn key_char(c: char) -> KeyEvent {
 
 pub fn key_ctrl(c: char) -> KeyEvent {
     KeyEvent::new(KeyCode::Char(c)).with_modifiers(KeyModifiers {
-        shift: false,
         ctrl: true,
-        alt: false,
+        ..KeyModifiers::none()
     })
 }
 
+/// Errors returned by [`parse_key`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeyParseError {
+    /// The spec was empty (or only whitespace).
+    Empty,
+    /// A `+`-separated prefix was not a known modifier name.
+    UnknownModifier(String),
+    /// The final component was neither a named key nor a single character.
+    UnknownKey(String),
+}
+
+impl fmt::Display for KeyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyParseError::Empty => write!(f, "empty key spec"),
+            KeyParseError::UnknownModifier(m) => write!(f, "unknown modifier `{m}`"),
+            KeyParseError::UnknownKey(k) => write!(f, "unknown key `{k}`"),
+        }
+    }
+}
+
+impl std::error::Error for KeyParseError {}
+
+/// Parses a key spec like `"ctrl+shift+k"`, `"alt+enter"` or `"G"` into a [`KeyEvent`].
+///
+/// See the [module docs](self) for the accepted syntax.
+pub fn parse_key(spec: &str) -> Result<KeyEvent, KeyParseError> {
+    let spec = spec.trim();
+    if spec.is_empty() {
+        return Err(KeyParseError::Empty);
+    }
+
+    // A trailing `+` that follows another `+` (or stands alone) is the plus key itself.
+    let (mods_part, key_part) = if spec == "+" {
+        ("", "+")
+    } else if let Some(mods) = spec.strip_suffix("++") {
+        (mods, "+")
+    } else {
+        match spec.rsplit_once('+') {
+            Some((mods, key)) => (mods, key),
+            None => ("", spec),
+        }
+    };
+
+    let mut modifiers = KeyModifiers::none();
+    if !mods_part.is_empty() {
+        for m in mods_part.split('+') {
+            match m.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers.ctrl = true,
+                "alt" | "option" | "opt" => modifiers.alt = true,
+                "shift" => modifiers.shift = true,
+                "super" | "cmd" | "command" | "win" => modifiers.super_ = true,
+                "hyper" => modifiers.hyper = true,
+                "meta" => modifiers.meta = true,
+                _ => return Err(KeyParseError::UnknownModifier(m.trim().to_string())),
+            }
+        }
+    }
+
+    let code = parse_key_code(key_part.trim())?;
+    Ok(KeyEvent::new(code).with_modifiers(modifiers))
+}
+
+/// Parses a list of key specs, stopping at the first error.
+pub fn parse_keys(specs: &[&str]) -> Result<Vec<KeyEvent>, KeyParseError> {
+    specs.iter().map(|s| parse_key(s)).collect()
+}
+
+fn parse_key_code(key: &str) -> Result<KeyCode, KeyParseError> {
+    if key.is_empty() {
+        return Err(KeyParseError::Empty);
+    }
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(KeyCode::Char(c));
+    }
+    let lower = key.to_ascii_lowercase();
+    if let Some(n) = lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok())
+        && (1..=24).contains(&n)
+    {
+        return Ok(KeyCode::F(n));
+    }
+    let code = match lower.as_str() {
+        "enter" | "return" | "cr" => KeyCode::Enter,
+        "backspace" | "bs" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "insert" | "ins" => KeyCode::Insert,
+        "menu" => KeyCode::Menu,
+        "esc" | "escape" => KeyCode::Esc,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" | "pgdown" => KeyCode::PageDown,
+        "space" => KeyCode::Char(' '),
+        "plus" => KeyCode::Char('+'),
+        "play" => KeyCode::Media(MediaKeyCode::Play),
+        "pause" => KeyCode::Media(MediaKeyCode::Pause),
+        "playpause" => KeyCode::Media(MediaKeyCode::PlayPause),
+        "reverse" => KeyCode::Media(MediaKeyCode::Reverse),
+        "stop" => KeyCode::Media(MediaKeyCode::Stop),
+        "fastforward" => KeyCode::Media(MediaKeyCode::FastForward),
+        "rewind" => KeyCode::Media(MediaKeyCode::Rewind),
+        "tracknext" | "next" => KeyCode::Media(MediaKeyCode::TrackNext),
+        "trackprevious" | "prev" => KeyCode::Media(MediaKeyCode::TrackPrevious),
+        "record" => KeyCode::Media(MediaKeyCode::Record),
+        "volumedown" => KeyCode::Media(MediaKeyCode::LowerVolume),
+        "volumeup" => KeyCode::Media(MediaKeyCode::RaiseVolume),
+        "mute" => KeyCode::Media(MediaKeyCode::MuteVolume),
+        _ => return Err(KeyParseError::UnknownKey(key.to_string())),
+    };
+    Ok(code)
+}
+
+/// Formats `key` as its canonical key spec (the inverse of [`parse_key`]).
+///
+/// Modifiers are emitted in `ctrl`, `alt`, `shift`, `super`, `hyper`, `meta` order and named keys are lowercase, e.g.
+/// `"ctrl+shift+k"` or `"pagedown"`.
+pub fn format_key(key: &KeyEvent) -> String {
+    let mut out = String::new();
+    if key.modifiers.ctrl {
+        out.push_str("ctrl+");
+    }
+    if key.modifiers.alt {
+        out.push_str("alt+");
+    }
+    if key.modifiers.shift {
+        out.push_str("shift+");
+    }
+    if key.modifiers.super_ {
+        out.push_str("super+");
+    }
+    if key.modifiers.hyper {
+        out.push_str("hyper+");
+    }
+    if key.modifiers.meta {
+        out.push_str("meta+");
+    }
+    match &key.code {
+        KeyCode::Char(' ') => out.push_str("space"),
+        KeyCode::Char(c) => out.push(*c),
+        KeyCode::F(n) => out.push_str(&format!("f{n}")),
+        code => out.push_str(key_code_name(code)),
+    }
+    out
+}
+
+/// Formats `keys` joined with `/`, suitable for [`Binding::help_key`].
+pub fn format_keys(keys: &[KeyEvent]) -> String {
+    keys.iter().map(format_key).collect::<Vec<_>>().join("/")
+}
+
+fn key_code_name(code: &KeyCode) -> &'static str {
+    match code {
+        KeyCode::Char(_) | KeyCode::F(_) => "",
+        KeyCode::Enter => "enter",
+        KeyCode::Backspace => "backspace",
+        KeyCode::Delete => "delete",
+        KeyCode::Tab => "tab",
+        KeyCode::Esc => "esc",
+        KeyCode::Left => "left",
+        KeyCode::Right => "right",
+        KeyCode::Up => "up",
+        KeyCode::Down => "down",
+        KeyCode::Home => "home",
+        KeyCode::End => "end",
+        KeyCode::PageUp => "pageup",
+        KeyCode::PageDown => "pagedown",
+        KeyCode::Insert => "insert",
+        KeyCode::BackTab => "backtab",
+        KeyCode::Menu => "menu",
+        KeyCode::Media(m) => match m {
+            MediaKeyCode::Play => "play",
+            MediaKeyCode::Pause => "pause",
+            MediaKeyCode::PlayPause => "playpause",
+            MediaKeyCode::Reverse => "reverse",
+            MediaKeyCode::Stop => "stop",
+            MediaKeyCode::FastForward => "fastforward",
+            MediaKeyCode::Rewind => "rewind",
+            MediaKeyCode::TrackNext => "tracknext",
+            MediaKeyCode::TrackPrevious => "trackprevious",
+            MediaKeyCode::Record => "record",
+            MediaKeyCode::LowerVolume => "volumedown",
+            MediaKeyCode::RaiseVolume => "volumeup",
+            MediaKeyCode::MuteVolume => "mute",
+        },
+    }
+}
+
+/// Parses a whitespace-separated key sequence like `"ctrl+x ctrl+s"` or `"g g"`.
+pub fn parse_key_sequence(spec: &str) -> Result<Vec<KeyEvent>, KeyParseError> {
+    let keys = spec
+        .split_whitespace()
+        .map(parse_key)
+        .collect::<Result<Vec<_>, _>>()?;
+    if keys.is_empty() {
+        return Err(KeyParseError::Empty);
+    }
+    Ok(keys)
+}
+
+/// Formats a key sequence as space-separated key specs (the inverse of [`parse_key_sequence`]).
+pub fn format_key_sequence(keys: &[KeyEvent]) -> String {
+    keys.iter().map(format_key).collect::<Vec<_>>().join(" ")
+}
+
+/// A key sequence bound to an action.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chord<A> {
+    pub keys: Vec<KeyEvent>,
+    pub action: A,
+}
+
+impl<A> Chord<A> {
+    pub fn new(keys: Vec<KeyEvent>, action: A) -> Self {
+        Self { keys, action }
+    }
+}
+
+/// Result of feeding a key into a [`Keymap`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChordMatch<A> {
+    /// The keys so far are a prefix of at least one bound sequence; wait for more input.
+    Pending,
+    /// A bound sequence was completed.
+    Matched(A),
+    /// The key does not match any binding (any pending prefix has been discarded).
+    NoMatch,
+}
+
+/// A key that can follow a pending prefix, as returned by [`Keymap::continuations`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Continuation<'a, A> {
+    pub key: KeyEvent,
+    /// The action bound to `prefix + key`, if any.
+    pub action: Option<&'a A>,
+    /// Whether longer sequences start with `prefix + key`.
+    pub is_prefix: bool,
+}
+
+/// The in-progress prefix of a multi-key sequence.
+///
+/// A [`Keymap`] is usually stored in (cloneable) options while `ChordState` lives next to the
+/// widget state, so one keymap can drive many widgets.
+#[derive(Clone, Debug, Default)]
+pub struct ChordState {
+    pending: Vec<KeyEvent>,
+    last_key_at: Option<Instant>,
+    replay: Option<KeyEvent>,
+}
+
+impl ChordState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keys typed so far for the current (incomplete) sequence.
+    pub fn pending(&self) -> &[KeyEvent] {
+        &self.pending
+    }
+
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.last_key_at = None;
+        self.replay = None;
+    }
+
+    /// Takes the key that interrupted a bound prefix, if the last feed left one.
+    ///
+    /// When a pending prefix is bound itself (e.g. `g` next to `g g`) and the next key cannot
+    /// continue it, feeding reports the prefix's action and leaves that key unhandled here.
+    /// Feed it again after applying the action so it is not lost.
+    pub fn take_replay(&mut self) -> Option<KeyEvent> {
+        self.replay.take()
+    }
+
+    /// Ends the pending prefix; if `action` (the prefix's own binding) is set, `key` is kept
+    /// for [`Self::take_replay`] and `action` is reported.
+    fn interrupt<A>(&mut self, action: Option<A>, key: &KeyEvent) -> Option<ChordMatch<A>> {
+        self.clear();
+        let action = action?;
+        self.replay = Some(key.clone());
+        Some(ChordMatch::Matched(action))
+    }
+
+    fn is_expired(&self, timeout: Option<Duration>, now: Option<Instant>) -> bool {
+        match (timeout, now, self.last_key_at) {
+            (Some(timeout), Some(now), Some(last)) => {
+                now.saturating_duration_since(last) >= timeout
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct KeymapNode<A> {
+    action: Option<A>,
+    children: Vec<(KeyEvent, KeymapNode<A>)>,
+}
+
+impl<A> Default for KeymapNode<A> {
+    fn default() -> Self {
+        Self {
+            action: None,
+            children: Vec::new(),
+        }
+    }
+}
+
+impl<A> KeymapNode<A> {
+    fn child(&self, key: &KeyEvent) -> Option<&KeymapNode<A>> {
+        self.children
+            .iter()
+            .find(|(k, _)| key_event_matches(k, key))
+            .map(|(_, n)| n)
+    }
+}
+
+/// A trie of key sequences, matched incrementally one key at a time.
+///
+/// Single keys and multi-key sequences can be mixed freely. When a sequence is both bound itself
+/// and a prefix of a longer one (e.g. `g` and `g g`), feeding it reports
+/// [`ChordMatch::Pending`]; the shorter binding fires from [`Keymap::expire`] once the timeout
+/// has elapsed, or from [`Keymap::feed`] when the next key cannot continue it.
+///
+/// Timeouts are driven by caller-supplied timestamps so the keymap stays event-loop agnostic and
+/// deterministic in tests. Passing `None` as `now` disables the timeout for that call.
+#[derive(Clone, Debug)]
+pub struct Keymap<A> {
+    root: KeymapNode<A>,
+    timeout: Option<Duration>,
+}
+
+impl<A> Default for Keymap<A> {
+    fn default() -> Self {
+        Self {
+            root: KeymapNode::default(),
+            timeout: None,
+        }
+    }
+}
+
+impl<A: Clone> Keymap<A> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long a pending prefix stays alive between keys.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Binds `keys` to `action`, replacing any existing binding for the same sequence.
+    ///
+    /// Empty sequences are ignored.
+    pub fn bind(&mut self, keys: &[KeyEvent], action: A) {
+        if keys.is_empty() {
+            return;
+        }
+        let mut node = &mut self.root;
+        for key in keys {
+            let idx = match node.children.iter().position(|(k, _)| same_key(k, key)) {
+                Some(idx) => idx,
+                None => {
+                    node.children.push((key.clone(), KeymapNode::default()));
+                    node.children.len() - 1
+                }
+            };
+            node = &mut node.children[idx].1;
+        }
+        node.action = Some(action);
+    }
+
+    /// Binds every key in `keys` as a single-key sequence.
+    pub fn bind_each(&mut self, keys: &[KeyEvent], action: A) {
+        for key in keys {
+            self.bind(std::slice::from_ref(key), action.clone());
+        }
+    }
+
+    /// Looks up the action bound to exactly `keys`.
+    pub fn get(&self, keys: &[KeyEvent]) -> Option<&A> {
+        let mut node = &self.root;
+        for key in keys {
+            node = node.child(key)?;
+        }
+        node.action.as_ref()
+    }
+
+    /// Feeds one key, advancing (or resetting) `state`.
+    ///
+    /// If a pending prefix cannot be continued by `key` (or has timed out), the prefix ends. A
+    /// prefix that is bound itself reports its own action, as [`Self::expire`] would, and leaves
+    /// `key` for [`ChordState::take_replay`]. Otherwise `key` is matched again from the root, so
+    /// a stray prefix never swallows the next command.
+    pub fn feed(
+        &self,
+        state: &mut ChordState,
+        key: &KeyEvent,
+        now: Option<Instant>,
+    ) -> ChordMatch<A> {
+        state.replay = None;
+        if state.is_pending() {
+            let prefix = self.node_for(&state.pending);
+            if !state.is_expired(self.timeout, now)
+                && let Some(node) = prefix.and_then(|n| n.child(key))
+            {
+                return self.step(state, node, key, now);
+            }
+            let action = prefix.and_then(|n| n.action.clone());
+            if let Some(matched) = state.interrupt(action, key) {
+                return matched;
+            }
+        }
+        match self.root.child(key) {
+            Some(node) => self.step(state, node, key, now),
+            None => ChordMatch::NoMatch,
+        }
+    }
+
+    /// Resolves a pending prefix whose timeout has elapsed.
+    ///
+    /// Returns the prefix's own action if it is bound (e.g. `g` while `g g` was possible),
+    /// otherwise [`ChordMatch::NoMatch`]. Returns [`ChordMatch::Pending`] while still waiting.
+    pub fn expire(&self, state: &mut ChordState, now: Instant) -> ChordMatch<A> {
+        if !state.is_pending() {
+            return ChordMatch::NoMatch;
+        }
+        if !state.is_expired(self.timeout, Some(now)) {
+            return ChordMatch::Pending;
+        }
+        let action = self.node_for(&state.pending).and_then(|n| n.action.clone());
+        state.clear();
+        action.map_or(ChordMatch::NoMatch, ChordMatch::Matched)
+    }
+
+    /// Returns every bound sequence with its action, in depth-first binding order.
+    pub fn bindings(&self) -> Vec<(Vec<KeyEvent>, &A)> {
+        fn walk<'a, A>(
+            node: &'a KeymapNode<A>,
+            prefix: &mut Vec<KeyEvent>,
+            out: &mut Vec<(Vec<KeyEvent>, &'a A)>,
+        ) {
+            if let Some(action) = &node.action {
+                out.push((prefix.clone(), action));
+            }
+            for (key, child) in &node.children {
+                prefix.push(key.clone());
+                walk(child, prefix, out);
+                prefix.pop();
+            }
+        }
+        let mut out = Vec::new();
+        walk(&self.root, &mut Vec::new(), &mut out);
+        out
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.children.is_empty()
+    }
+
+    /// Lists the keys that can follow `prefix`, e.g. to show a which-key style hint.
+    pub fn continuations(&self, prefix: &[KeyEvent]) -> Vec<Continuation<'_, A>> {
+        let Some(node) = self.node_for(prefix) else {
+            return Vec::new();
+        };
+        node.children
+            .iter()
+            .map(|(key, child)| Continuation {
+                key: key.clone(),
+                action: child.action.as_ref(),
+                is_prefix: !child.children.is_empty(),
+            })
+            .collect()
+    }
+
+    fn node_for(&self, keys: &[KeyEvent]) -> Option<&KeymapNode<A>> {
+        let mut node = &self.root;
+        for key in keys {
+            node = node.child(key)?;
+        }
+        Some(node)
+    }
+
+    fn step(
+        &self,
+        state: &mut ChordState,
+        node: &KeymapNode<A>,
+        key: &KeyEvent,
+        now: Option<Instant>,
+    ) -> ChordMatch<A> {
+        if node.children.is_empty() {
+            state.clear();
+            return node
+                .action
+                .clone()
+                .map_or(ChordMatch::NoMatch, ChordMatch::Matched);
+        }
+        state.pending.push(key.clone());
+        state.last_key_at = now;
+        ChordMatch::Pending
+    }
+}
+
+/// An input mode a [`KeymapLayer`] can be restricted to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mode {
+    #[default]
+    Normal,
+    Insert,
+    Visual,
+    /// An app-defined mode.
+    Custom(String),
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mode::Normal => f.write_str("normal"),
+            Mode::Insert => f.write_str("insert"),
+            Mode::Visual => f.write_str("visual"),
+            Mode::Custom(name) => f.write_str(name),
+        }
+    }
+}
+
+/// A named [`Keymap`] that participates in a [`KeymapStack`].
+#[derive(Clone, Debug)]
+pub struct KeymapLayer<A> {
+    pub name: String,
+    pub keymap: Keymap<A>,
+    /// Modes in which this layer is active. Empty means "all modes".
+    pub modes: Vec<Mode>,
+    /// Whether keys this layer does not bind fall through to the layers below it.
+    ///
+    /// Defaults to `true`. Modal overlays typically set this to `false` so nothing underneath
+    /// reacts while they are open.
+    pub pass_through: bool,
+    pub enabled: bool,
+}
+
+impl<A: Clone> KeymapLayer<A> {
+    pub fn new(name: impl Into<String>, keymap: Keymap<A>) -> Self {
+        Self {
+            name: name.into(),
+            keymap,
+            modes: Vec::new(),
+            pass_through: true,
+            enabled: true,
+        }
+    }
+
+    pub fn with_modes(mut self, modes: Vec<Mode>) -> Self {
+        self.modes = modes;
+        self
+    }
+
+    pub fn with_pass_through(mut self, pass_through: bool) -> Self {
+        self.pass_through = pass_through;
+        self
+    }
+
+    pub fn is_active_in(&self, mode: &Mode) -> bool {
+        self.enabled && (self.modes.is_empty() || self.modes.contains(mode))
+    }
+
+    fn shares_mode_with(&self, other: &KeymapLayer<A>) -> bool {
+        self.modes.is_empty()
+            || other.modes.is_empty()
+            || self.modes.iter().any(|m| other.modes.contains(m))
+    }
+}
+
+/// How two bindings in different layers overlap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// Both layers bind the same sequence; the upper layer always wins.
+    Shadowed,
+    /// One sequence is a strict prefix of the other (e.g. `g` and `g g`), so the shorter one
+    /// only fires after a timeout, or never.
+    Prefix,
+}
+
+/// An overlap between bindings of two layers, reported by [`KeymapStack::validate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeymapConflict {
+    pub kind: ConflictKind,
+    /// The layer with higher precedence.
+    pub upper: String,
+    pub upper_keys: Vec<KeyEvent>,
+    /// The layer with lower precedence.
+    pub lower: String,
+    pub lower_keys: Vec<KeyEvent>,
+}
+
+impl fmt::Display for KeymapConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.kind {
+            ConflictKind::Shadowed => "shadows",
+            ConflictKind::Prefix => "overlaps",
+        };
+        write!(
+            f,
+            "`{}` in layer `{}` {} `{}` in layer `{}`",
+            format_key_sequence(&self.upper_keys),
+            self.upper,
+            what,
+            format_key_sequence(&self.lower_keys),
+            self.lower,
+        )
+    }
+}
+
+enum Resolved<A> {
+    Prefix,
+    Action(A),
+}
+
+/// A stack of [`KeymapLayer`]s matched top-down, with a current [`Mode`].
+///
+/// Layers pushed later take precedence. For each key, the topmost active layer that binds (or
+/// starts) the current sequence wins; a layer with `pass_through == false` stops the search even
+/// if it binds nothing.
+///
+/// Like [`Keymap`], timeouts are driven by caller-supplied timestamps.
+#[derive(Clone, Debug)]
+pub struct KeymapStack<A> {
+    layers: Vec<KeymapLayer<A>>,
+    mode: Mode,
+    timeout: Option<Duration>,
+    state: ChordState,
+}
+
+impl<A> Default for KeymapStack<A> {
+    fn default() -> Self {
+        Self {
+            layers: Vec::new(),
+            mode: Mode::default(),
+            timeout: None,
+            state: ChordState::default(),
+        }
+    }
+}
+
+impl<A: Clone> KeymapStack<A> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Pushes `layer` on top of the stack (highest precedence).
+    pub fn push(&mut self, layer: KeymapLayer<A>) {
+        self.layers.push(layer);
+    }
+
+    pub fn pop(&mut self) -> Option<KeymapLayer<A>> {
+        self.state.clear();
+        self.layers.pop()
+    }
+
+    /// Removes the topmost layer named `name`.
+    pub fn remove(&mut self, name: &str) -> Option<KeymapLayer<A>> {
+        let idx = self.layers.iter().rposition(|l| l.name == name)?;
+        self.state.clear();
+        Some(self.layers.remove(idx))
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&KeymapLayer<A>> {
+        self.layers.iter().rev().find(|l| l.name == name)
+    }
+
+    pub fn layer_mut(&mut self, name: &str) -> Option<&mut KeymapLayer<A>> {
+        self.layers.iter_mut().rev().find(|l| l.name == name)
+    }
+
+    /// Layers from bottom (lowest precedence) to top.
+    pub fn layers(&self) -> &[KeymapLayer<A>] {
+        &self.layers
+    }
+
+    pub fn mode(&self) -> &Mode {
+        &self.mode
+    }
+
+    /// Switches mode, discarding any pending sequence.
+    pub fn set_mode(&mut self, mode: Mode) {
+        if self.mode != mode {
+            self.mode = mode;
+            self.state.clear();
+        }
+    }
+
+    /// Keys typed so far for the current (incomplete) sequence.
+    pub fn pending(&self) -> &[KeyEvent] {
+        self.state.pending()
+    }
+
+    pub fn clear_pending(&mut self) {
+        self.state.clear();
+    }
+
+    /// Feeds one key through the active layers. See [`Keymap::feed`] for the sequence rules.
+    pub fn feed(&mut self, key: &KeyEvent, now: Option<Instant>) -> ChordMatch<A> {
+        self.state.replay = None;
+        if self.state.is_pending() {
+            if !self.state.is_expired(self.timeout, now) {
+                let mut seq = self.state.pending.clone();
+                seq.push(key.clone());
+                if let Some(resolved) = self.resolve(&seq) {
+                    return self.finish(resolved, key, now);
+                }
+            }
+            let action = self.prefix_action();
+            if let Some(matched) = self.state.interrupt(action, key) {
+                return matched;
+            }
+        }
+        match self.resolve(std::slice::from_ref(key)) {
+            Some(resolved) => self.finish(resolved, key, now),
+            None => ChordMatch::NoMatch,
+        }
+    }
+
+    /// Resolves a pending prefix whose timeout has elapsed. See [`Keymap::expire`].
+    pub fn expire(&mut self, now: Instant) -> ChordMatch<A> {
+        if !self.state.is_pending() {
+            return ChordMatch::NoMatch;
+        }
+        if !self.state.is_expired(self.timeout, Some(now)) {
+            return ChordMatch::Pending;
+        }
+        let action = self.prefix_action();
+        self.state.clear();
+        action.map_or(ChordMatch::NoMatch, ChordMatch::Matched)
+    }
+
+    /// Takes the key that interrupted a bound prefix. See [`ChordState::take_replay`].
+    pub fn take_replay(&mut self) -> Option<KeyEvent> {
+        self.state.take_replay()
+    }
+
+    /// Reports bindings that overlap between layers sharing at least one mode.
+    ///
+    /// Intended to be called once at startup (e.g. after loading user config) to surface
+    /// remaps that can never fire.
+    pub fn validate(&self) -> Vec<KeymapConflict> {
+        let mut out = Vec::new();
+        for (hi_idx, upper) in self.layers.iter().enumerate().rev() {
+            let upper_bindings = upper.keymap.bindings();
+            for lower in self.layers[..hi_idx].iter().rev() {
+                if !upper.shares_mode_with(lower) {
+                    continue;
+                }
+                for (lower_keys, _) in lower.keymap.bindings() {
+                    for (upper_keys, _) in &upper_bindings {
+                        let kind = if sequences_match(upper_keys, &lower_keys) {
+                            ConflictKind::Shadowed
+                        } else if is_prefix(upper_keys, &lower_keys)
+                            || is_prefix(&lower_keys, upper_keys)
+                        {
+                            ConflictKind::Prefix
+                        } else {
+                            continue;
+                        };
+                        out.push(KeymapConflict {
+                            kind,
+                            upper: upper.name.clone(),
+                            upper_keys: upper_keys.clone(),
+                            lower: lower.name.clone(),
+                            lower_keys: lower_keys.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Lists the keys that can follow the pending prefix across all active layers.
+    ///
+    /// When several layers bind the same continuation, the one with higher precedence wins.
+    pub fn continuations(&self) -> Vec<Continuation<'_, A>> {
+        let mut out: Vec<Continuation<'_, A>> = Vec::new();
+        for layer in self.active_layers() {
+            for c in layer.keymap.continuations(&self.state.pending) {
+                if !out.iter().any(|o| key_event_matches(&o.key, &c.key)) {
+                    out.push(c);
+                }
+            }
+            if !layer.pass_through {
+                break;
+            }
+        }
+        out
+    }
+
+    fn active_layers(&self) -> impl Iterator<Item = &KeymapLayer<A>> {
+        self.layers
+            .iter()
+            .rev()
+            .filter(|l| l.is_active_in(&self.mode))
+    }
+
+    /// The action bound to the pending prefix itself, from the first active layer binding it.
+    fn prefix_action(&self) -> Option<A> {
+        for layer in self.active_layers() {
+            if let Some(a) = layer.keymap.get(&self.state.pending) {
+                return Some(a.clone());
+            }
+            if !layer.pass_through {
+                break;
+            }
+        }
+        None
+    }
+
+    fn resolve(&self, seq: &[KeyEvent]) -> Option<Resolved<A>> {
+        for layer in self.active_layers() {
+            if let Some(node) = layer.keymap.node_for(seq) {
+                if !node.children.is_empty() {
+                    return Some(Resolved::Prefix);
+                }
+                if let Some(action) = &node.action {
+                    return Some(Resolved::Action(action.clone()));
+                }
+            }
+            if !layer.pass_through {
+                return None;
+            }
+        }
+        None
+    }
+
+    fn finish(
+        &mut self,
+        resolved: Resolved<A>,
+        key: &KeyEvent,
+        now: Option<Instant>,
+    ) -> ChordMatch<A> {
+        match resolved {
+            Resolved::Prefix => {
+                self.state.pending.push(key.clone());
+                self.state.last_key_at = now;
+                ChordMatch::Pending
+            }
+            Resolved::Action(action) => {
+                self.state.clear();
+                ChordMatch::Matched(action)
+            }
+        }
+    }
+}
+
+fn sequences_match(a: &[KeyEvent], b: &[KeyEvent]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| key_event_matches(x, y))
+}
+
+fn is_prefix(prefix: &[KeyEvent], seq: &[KeyEvent]) -> bool {
+    prefix.len() < seq.len() && sequences_match(prefix, &seq[..prefix.len()])
+}
+
+impl FromStr for KeyEvent {
+    type Err = KeyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_key(s)
+    }
+}
+
+impl fmt::Display for KeyEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_key(self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for KeyEvent {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_key(self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for KeyEvent {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let spec = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        parse_key(&spec).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,4 +1031,319 @@ mod tests {
         assert!(b.matches(&key_char('q')));
         assert!(!b.matches(&key_ctrl('q')));
     }
+
+    #[test]
+    fn parses_modifiers_and_named_keys() {
+        assert_eq!(parse_key("ctrl+d"), Ok(key_ctrl('d')));
+        assert_eq!(parse_key("G"), Ok(key_char('G')));
+        assert_eq!(
+            parse_key("Alt+Enter"),
+            Ok(KeyEvent::new(KeyCode::Enter).with_modifiers(KeyModifiers {
+                alt: true,
+                ..KeyModifiers::none()
+            }))
+        );
+        assert_eq!(
+            parse_key("ctrl+shift+k"),
+            Ok(
+                KeyEvent::new(KeyCode::Char('k')).with_modifiers(KeyModifiers {
+                    shift: true,
+                    ctrl: true,
+                    ..KeyModifiers::none()
+                })
+            )
+        );
+        assert_eq!(parse_key("space"), Ok(key_char(' ')));
+        assert_eq!(parse_key("+"), Ok(key_char('+')));
+        assert_eq!(
+            parse_key("ctrl++"),
+            Ok(
+                KeyEvent::new(KeyCode::Char('+')).with_modifiers(KeyModifiers {
+                    ctrl: true,
+                    ..KeyModifiers::none()
+                })
+            )
+        );
+    }
+
+    #[test]
+    fn release_events_never_match() {
+        let b = Binding::parse("quit", &["q"]).unwrap();
+        assert!(b.matches(&key_char('q').with_kind(KeyEventKind::Repeat)));
+        assert!(!b.matches(&key_char('q').with_kind(KeyEventKind::Release)));
+        assert_eq!(parse_key("F5"), Ok(KeyEvent::new(KeyCode::F(5))));
+        assert!(parse_key("f25").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_specs() {
+        assert_eq!(parse_key(""), Err(KeyParseError::Empty));
+        assert_eq!(
+            parse_key("fn+x"),
+            Err(KeyParseError::UnknownModifier("fn".to_string()))
+        );
+        assert_eq!(
+            parse_key("ctrl+nope"),
+            Err(KeyParseError::UnknownKey("nope".to_string()))
+        );
+    }
+
+    #[test]
+    fn format_round_trips_through_parse() {
+        for spec in [
+            "q",
+            "G",
+            "ctrl+d",
+            "alt+enter",
+            "ctrl+alt+shift+pagedown",
+            "space",
+            "ctrl++",
+            "shift+f12",
+            "super+insert",
+            "ctrl+hyper+meta+backtab",
+            "playpause",
+        ] {
+            let key = parse_key(spec).unwrap();
+            assert_eq!(format_key(&key), spec);
+            assert_eq!(parse_key(&format_key(&key)), Ok(key));
+        }
+    }
+
+    #[test]
+    fn from_keys_derives_help_key() {
+        let b = Binding::parse("page down", &["pagedown", "ctrl+d"]).unwrap();
+        assert_eq!(b.help_key, "pagedown/ctrl+d");
+        assert!(b.matches(&key_ctrl('d')));
+    }
+
+    #[test]
+    fn keymap_matches_sequences() {
+        let mut map = Keymap::new();
+        map.bind(&parse_key_sequence("g g").unwrap(), "top");
+        map.bind(&parse_key_sequence("ctrl+x ctrl+s").unwrap(), "save");
+        map.bind(&[key_char('j')], "down");
+
+        let mut st = ChordState::new();
+        assert_eq!(
+            map.feed(&mut st, &key_char('j'), None),
+            ChordMatch::Matched("down")
+        );
+        assert_eq!(map.feed(&mut st, &key_char('g'), None), ChordMatch::Pending);
+        assert_eq!(st.pending(), &[key_char('g')]);
+        assert_eq!(
+            map.feed(&mut st, &key_char('g'), None),
+            ChordMatch::Matched("top")
+        );
+        assert!(!st.is_pending());
+
+        assert_eq!(map.feed(&mut st, &key_ctrl('x'), None), ChordMatch::Pending);
+        assert_eq!(
+            map.feed(&mut st, &key_ctrl('s'), None),
+            ChordMatch::Matched("save")
+        );
+
+        // A broken prefix is dropped and the key is matched from the root.
+        assert_eq!(map.feed(&mut st, &key_char('g'), None), ChordMatch::Pending);
+        assert_eq!(
+            map.feed(&mut st, &key_char('j'), None),
+            ChordMatch::Matched("down")
+        );
+        assert_eq!(map.feed(&mut st, &key_char('x'), None), ChordMatch::NoMatch);
+    }
+
+    #[test]
+    fn keymap_rebinding_a_matching_key_replaces_it() {
+        let mut map = Keymap::new();
+        map.bind(&[key_char('q')], "quit");
+        map.bind(&[key_char('q').with_kind(KeyEventKind::Repeat)], "close");
+
+        let bindings = map.bindings();
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(*bindings[0].1, "close");
+        assert_eq!(
+            map.feed(&mut ChordState::new(), &key_char('q'), None),
+            ChordMatch::Matched("close")
+        );
+    }
+
+    #[test]
+    fn keymap_timeout_uses_caller_timestamps() {
+        let mut map = Keymap::new().with_timeout(Duration::from_millis(500));
+        map.bind(&[key_char('g')], "g");
+        map.bind(&parse_key_sequence("g g").unwrap(), "gg");
+
+        let t0 = Instant::now();
+        let mut st = ChordState::new();
+        assert_eq!(
+            map.feed(&mut st, &key_char('g'), Some(t0)),
+            ChordMatch::Pending
+        );
+        assert_eq!(
+            map.expire(&mut st, t0 + Duration::from_millis(100)),
+            ChordMatch::Pending
+        );
+        assert_eq!(
+            map.expire(&mut st, t0 + Duration::from_millis(600)),
+            ChordMatch::Matched("g")
+        );
+
+        // A second `g` after the timeout fires the expired `g` instead of completing `g g`, and
+        // is left to start a new sequence.
+        assert_eq!(
+            map.feed(&mut st, &key_char('g'), Some(t0)),
+            ChordMatch::Pending
+        );
+        let late = t0 + Duration::from_secs(1);
+        assert_eq!(
+            map.feed(&mut st, &key_char('g'), Some(late)),
+            ChordMatch::Matched("g")
+        );
+        let replay = st.take_replay().unwrap();
+        assert_eq!(map.feed(&mut st, &replay, Some(late)), ChordMatch::Pending);
+        assert_eq!(
+            map.feed(&mut st, &key_char('g'), Some(late)),
+            ChordMatch::Matched("gg")
+        );
+    }
+
+    #[test]
+    fn keymap_interrupted_bound_prefix_fires_and_replays_the_key() {
+        let mut map = Keymap::new();
+        map.bind(&[key_char('g')], "g");
+        map.bind(&parse_key_sequence("g g").unwrap(), "gg");
+        map.bind(&[key_char('j')], "down");
+
+        let mut st = ChordState::new();
+        assert_eq!(map.feed(&mut st, &key_char('g'), None), ChordMatch::Pending);
+        assert_eq!(
+            map.feed(&mut st, &key_char('j'), None),
+            ChordMatch::Matched("g")
+        );
+        assert!(!st.is_pending());
+        let replay = st.take_replay().unwrap();
+        assert_eq!(replay, key_char('j'));
+        assert_eq!(
+            map.feed(&mut st, &replay, None),
+            ChordMatch::Matched("down")
+        );
+        assert_eq!(st.take_replay(), None);
+
+        // An unbound prefix is still dropped and the key matched from the root.
+        map.bind(&parse_key_sequence("z z").unwrap(), "center");
+        assert_eq!(map.feed(&mut st, &key_char('z'), None), ChordMatch::Pending);
+        assert_eq!(
+            map.feed(&mut st, &key_char('j'), None),
+            ChordMatch::Matched("down")
+        );
+        assert_eq!(st.take_replay(), None);
+    }
+
+    #[test]
+    fn stack_respects_precedence_modes_and_pass_through() {
+        let mut global = Keymap::new();
+        global.bind(&[key_char('q')], "quit");
+        global.bind(&[key_char('j')], "global-down");
+        let mut pane = Keymap::new();
+        pane.bind(&[key_char('j')], "pane-down");
+        pane.bind(&parse_key_sequence("g g").unwrap(), "top");
+        let mut modal = Keymap::new();
+        modal.bind(&[KeyEvent::new(KeyCode::Esc)], "close");
+
+        let mut stack = KeymapStack::new();
+        stack.push(KeymapLayer::new("global", global));
+        stack.push(KeymapLayer::new("pane", pane).with_modes(vec![Mode::Normal]));
+
+        assert_eq!(
+            stack.feed(&key_char('j'), None),
+            ChordMatch::Matched("pane-down")
+        );
+        assert_eq!(
+            stack.feed(&key_char('q'), None),
+            ChordMatch::Matched("quit")
+        );
+        assert_eq!(stack.feed(&key_char('g'), None), ChordMatch::Pending);
+        assert_eq!(stack.feed(&key_char('g'), None), ChordMatch::Matched("top"));
+
+        stack.set_mode(Mode::Insert);
+        assert_eq!(
+            stack.feed(&key_char('j'), None),
+            ChordMatch::Matched("global-down")
+        );
+
+        stack.set_mode(Mode::Normal);
+        stack.push(KeymapLayer::new("modal", modal).with_pass_through(false));
+        assert_eq!(stack.feed(&key_char('q'), None), ChordMatch::NoMatch);
+        assert_eq!(
+            stack.feed(&KeyEvent::new(KeyCode::Esc), None),
+            ChordMatch::Matched("close")
+        );
+        stack.pop();
+        assert_eq!(
+            stack.feed(&key_char('q'), None),
+            ChordMatch::Matched("quit")
+        );
+    }
+
+    #[test]
+    fn stack_interrupted_bound_prefix_fires_and_replays_the_key() {
+        let mut global = Keymap::new();
+        global.bind(&[key_char('g')], "global-g");
+        global.bind(&[key_char('j')], "down");
+        let mut pane = Keymap::new();
+        pane.bind(&parse_key_sequence("g g").unwrap(), "top");
+
+        let mut stack = KeymapStack::new();
+        stack.push(KeymapLayer::new("global", global));
+        stack.push(KeymapLayer::new("pane", pane));
+
+        assert_eq!(stack.feed(&key_char('g'), None), ChordMatch::Pending);
+        assert_eq!(
+            stack.feed(&key_char('j'), None),
+            ChordMatch::Matched("global-g")
+        );
+        let replay = stack.take_replay().unwrap();
+        assert_eq!(stack.feed(&replay, None), ChordMatch::Matched("down"));
+        assert_eq!(stack.take_replay(), None);
+    }
+
+    #[test]
+    fn stack_validate_reports_overlaps() {
+        let mut global = Keymap::new();
+        global.bind(&[key_char('q')], ());
+        global.bind(&[key_char('g')], ());
+        global.bind(&[key_char('i')], ());
+        let mut pane = Keymap::new();
+        pane.bind(&[key_char('q')], ());
+        pane.bind(&parse_key_sequence("g g").unwrap(), ());
+        let mut insert = Keymap::new();
+        insert.bind(&[key_char('i')], ());
+
+        let mut stack = KeymapStack::new();
+        stack.push(KeymapLayer::new("global", global).with_modes(vec![Mode::Normal]));
+        stack.push(KeymapLayer::new("pane", pane));
+        stack.push(KeymapLayer::new("insert", insert).with_modes(vec![Mode::Insert]));
+
+        let conflicts = stack.validate();
+        assert_eq!(conflicts.len(), 2);
+        assert!(
+            conflicts
+                .iter()
+                .all(|c| c.upper == "pane" && c.lower == "global")
+        );
+        assert!(conflicts.iter().any(|c| c.kind == ConflictKind::Shadowed
+            && c.to_string() == "`q` in layer `pane` shadows `q` in layer `global`"));
+        assert!(conflicts.iter().any(|c| c.kind == ConflictKind::Prefix));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn key_events_deserialize_from_spec_strings() {
+        let keys: Vec<KeyEvent> = serde_json::from_str(r#"["ctrl+u", "pageup"]"#).unwrap();
+        assert_eq!(keys, vec![key_ctrl('u'), KeyEvent::new(KeyCode::PageUp)]);
+        assert_eq!(
+            serde_json::to_string(&keys).unwrap(),
+            r#"["ctrl+u","pageup"]"#
+        );
+        assert!(serde_json::from_str::<KeyEvent>(r#""ctrl+nope""#).is_err());
+    }
 }
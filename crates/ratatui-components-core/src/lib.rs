@@ -22,6 +22,10 @@
 //! - [`virtual_list::VirtualListView`]: large list virtualization with keyboard navigation.
 //! - [`datagrid::view::DataGridView`]: virtualized 2D grid.
 //! - [`code_render::render_code_lines`]: render core for custom layouts.
+//! - [`component::Component`]: common interface implemented by every interactive view.
+//! - [`focus::FocusManager`]: Tab/BackTab focus cycling with nested (modal) scopes.
+//! - [`color::ColorProfile`]: downsampling of rendered colors for 256/16-color terminals.
+//! - `app` (feature: `app`): optional Elm / Bubble Tea style `Model` / `update` / `view` driver.
 //!
 //! ## Selection / copy
 //!
@@ -31,23 +35,35 @@
 //!
 //! Your app can map `CopyRequested(text)` into a clipboard action (or just show the copied text in
 //! the UI).
+pub mod color;
 pub mod theme;
 
+#[cfg(feature = "app")]
+pub mod app;
+
 pub mod text;
 
 #[cfg(feature = "crossterm")]
 pub mod crossterm_input;
+#[cfg(feature = "termion")]
+pub mod termion_input;
+#[cfg(feature = "termwiz")]
+pub mod termwiz_input;
 
 pub mod render;
 pub mod scroll;
 pub mod selection;
+pub mod tabs;
 pub mod viewport;
 pub mod wrapping;
 
 pub mod code_render;
 
 pub mod code_view;
+pub mod component;
 pub mod datagrid;
+pub mod focus;
+pub mod grapheme;
 pub mod help;
 pub mod input;
 pub mod keymap;
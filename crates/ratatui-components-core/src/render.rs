@@ -3,45 +3,205 @@ use ratatui::layout::Rect;
 use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::text::Span;
-use unicode_width::UnicodeWidthChar;
 
+use crate::grapheme;
+use crate::tabs::TabPolicy;
 use crate::viewport::ViewportState;
 
-pub fn render_scrollbar(area: Rect, buf: &mut Buffer, state: &ViewportState, style: Style) {
-    buf.set_style(area, style);
-    if area.height == 0 {
-        return;
+use std::ops::Range;
+
+use crate::theme::ChromeTheme;
+
+/// Splits a view's area into content and scrollbar tracks.
+///
+/// The vertical track takes the rightmost column and the horizontal track the bottom row, each
+/// only when the area keeps at least one cell of content beside it. With both, the bottom-right
+/// corner belongs to neither.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScrollbarLayout {
+    pub content: Rect,
+    pub vertical: Option<Rect>,
+    pub horizontal: Option<Rect>,
+}
+
+impl ScrollbarLayout {
+    pub fn new(area: Rect, vertical: bool, horizontal: bool) -> Self {
+        let vertical = vertical && area.width >= 2;
+        let horizontal = horizontal && area.height >= 2;
+        let content = Rect::new(
+            area.x,
+            area.y,
+            area.width - u16::from(vertical),
+            area.height - u16::from(horizontal),
+        );
+        Self {
+            content,
+            vertical: vertical.then(|| Rect::new(content.right(), area.y, 1, content.height)),
+            horizontal: horizontal.then(|| Rect::new(area.x, content.bottom(), content.width, 1)),
+        }
     }
-    if state.content_h <= state.viewport_h as u32 || state.content_h == 0 {
-        for dy in 0..area.height {
-            buf.set_stringn(area.x, area.y + dy, " ", 1, style);
+}
+
+/// What a [`ScrollbarMarker`] points at; later kinds are drawn over earlier ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MarkerKind {
+    Selection,
+    Hunk,
+    Search,
+    Diagnostic,
+}
+
+impl MarkerKind {
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Self::Selection => "┃",
+            Self::Hunk => "─",
+            Self::Search => "━",
+            Self::Diagnostic => "■",
         }
-        return;
     }
+}
 
-    let track_h = area.height as f64;
-    let thumb_h = ((state.viewport_h as f64 / state.content_h as f64) * track_h)
-        .round()
-        .clamp(1.0, track_h) as u16;
+/// A mark on the vertical scrollbar track for a range of content lines (the same units as
+/// [`ViewportState::content_h`]); styled with [`ChromeTheme::marker_style`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScrollbarMarker {
+    pub lines: Range<u32>,
+    pub kind: MarkerKind,
+}
 
-    let max_y = state
-        .content_h
-        .saturating_sub(state.viewport_h as u32)
-        .max(1) as f64;
-    let thumb_top = ((state.y as f64 / max_y) * (track_h - thumb_h as f64))
+impl ScrollbarMarker {
+    pub fn new(line: u32, kind: MarkerKind) -> Self {
+        Self {
+            lines: line..line.saturating_add(1),
+            kind,
+        }
+    }
+
+    pub fn range(lines: Range<u32>, kind: MarkerKind) -> Self {
+        Self { lines, kind }
+    }
+}
+
+/// Start and length of the thumb on a track of `track` cells, or `None` if nothing scrolls.
+fn thumb(track: u16, content: u32, viewport: u16, offset: u32) -> Option<(u16, u16)> {
+    if track == 0 || content <= viewport as u32 {
+        return None;
+    }
+    let track_f = track as f64;
+    let len = ((viewport as f64 / content as f64) * track_f)
+        .round()
+        .clamp(1.0, track_f) as u16;
+    let max_offset = content.saturating_sub(viewport as u32).max(1) as f64;
+    let start = ((offset as f64 / max_offset) * (track_f - len as f64))
         .round()
-        .clamp(0.0, (track_h - thumb_h as f64).max(0.0)) as u16;
+        .clamp(0.0, (track_f - len as f64).max(0.0)) as u16;
+    Some((start, len))
+}
 
+pub fn render_scrollbar(area: Rect, buf: &mut Buffer, state: &ViewportState, style: Style) {
+    buf.set_style(area, style);
+    if area.height == 0 {
+        return;
+    }
+    let thumb = thumb(area.height, state.content_h, state.viewport_h, state.y);
     for dy in 0..area.height {
-        let ch = if dy >= thumb_top && dy < thumb_top + thumb_h {
-            "█"
-        } else {
-            " "
+        let ch = match thumb {
+            Some((top, len)) if dy >= top && dy < top + len => "█",
+            _ => " ",
         };
         buf.set_stringn(area.x, area.y + dy, ch, 1, style);
     }
 }
 
+/// Horizontal counterpart of [`render_scrollbar`], driven by `x`, `viewport_w` and `content_w`.
+pub fn render_scrollbar_x(area: Rect, buf: &mut Buffer, state: &ViewportState, style: Style) {
+    buf.set_style(area, style);
+    if area.width == 0 {
+        return;
+    }
+    let thumb = thumb(area.width, state.content_w, state.viewport_w, state.x);
+    for dx in 0..area.width {
+        let ch = match thumb {
+            Some((left, len)) if dx >= left && dx < left + len => "█",
+            _ => " ",
+        };
+        buf.set_stringn(area.x + dx, area.y, ch, 1, style);
+    }
+}
+
+/// Draws `markers` over a vertical track drawn by [`render_scrollbar`].
+///
+/// Content lines are scaled to the track, so every marker stays visible however long the
+/// content is; content shorter than the viewport maps one line per row.
+pub fn render_scrollbar_markers(
+    area: Rect,
+    buf: &mut Buffer,
+    state: &ViewportState,
+    markers: &[ScrollbarMarker],
+    chrome: &ChromeTheme,
+) {
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+    let span = state.content_h.max(state.viewport_h as u32).max(1) as u64;
+    let last = area.height as u64 - 1;
+    let row = |line: u32| ((line as u64 * area.height as u64) / span).min(last) as u16;
+
+    let mut sorted: Vec<&ScrollbarMarker> =
+        markers.iter().filter(|m| !m.lines.is_empty()).collect();
+    sorted.sort_by_key(|m| m.kind);
+    for marker in sorted {
+        let style = chrome.scrollbar.patch(chrome.marker_style(marker.kind));
+        for dy in row(marker.lines.start)..=row(marker.lines.end - 1) {
+            buf.set_stringn(area.x, area.y + dy, marker.kind.glyph(), 1, style);
+        }
+    }
+}
+
+/// Highlights the keyboard caret at content `(line, col)` of a view showing `state` in `area`.
+///
+/// Does nothing while the caret is scrolled out of view.
+pub fn render_caret(
+    area: Rect,
+    buf: &mut Buffer,
+    state: &ViewportState,
+    caret: (usize, u32),
+    style: Style,
+) {
+    let (line, col) = caret;
+    let (Some(dy), Some(dx)) = (
+        (line as u64).checked_sub(state.y as u64),
+        col.checked_sub(state.x),
+    ) else {
+        return;
+    };
+    if dy < area.height as u64 && dx < area.width as u32 {
+        buf.set_style(
+            Rect::new(area.x + dx as u16, area.y + dy as u16, 1, 1),
+            style,
+        );
+    }
+}
+
+/// Highlights content `line` of a view showing `state` in `area`, e.g. the row under the mouse.
+///
+/// Does nothing while the line is scrolled out of view.
+pub fn render_line_highlight(
+    area: Rect,
+    buf: &mut Buffer,
+    state: &ViewportState,
+    line: usize,
+    style: Style,
+) {
+    let Some(dy) = (line as u64).checked_sub(state.y as u64) else {
+        return;
+    };
+    if dy < area.height as u64 {
+        buf.set_style(Rect::new(area.x, area.y + dy as u16, area.width, 1), style);
+    }
+}
+
 pub fn render_str_clipped(
     x: u16,
     y: u16,
@@ -50,77 +210,36 @@ pub fn render_str_clipped(
     buf: &mut Buffer,
     input: &str,
     style: Style,
+) {
+    render_str_clipped_with_tabs(
+        x,
+        y,
+        start_col,
+        max_cols,
+        buf,
+        input,
+        style,
+        TabPolicy::default(),
+    )
+}
+
+/// Like [`render_str_clipped`], expanding tabs with `tabs`.
+#[allow(clippy::too_many_arguments)]
+pub fn render_str_clipped_with_tabs(
+    x: u16,
+    y: u16,
+    start_col: u32,
+    max_cols: u16,
+    buf: &mut Buffer,
+    input: &str,
+    style: Style,
+    tabs: TabPolicy,
 ) {
     if max_cols == 0 {
         return;
     }
-
-    let start_col = start_col as usize;
-    let max_cols = max_cols as usize;
-    let mut col = 0usize;
-    let mut out_cols = 0usize;
-    let mut dx = 0u16;
-
-    let mut tmp = [0u8; 4];
-
-    for ch in input.chars() {
-        if ch == '\t' {
-            for _ in 0..4 {
-                if col < start_col {
-                    col += 1;
-                    continue;
-                }
-                if out_cols + 1 > max_cols {
-                    return;
-                }
-                if let Some(cell) = buf.cell_mut((x + dx, y)) {
-                    cell.set_style(style);
-                    cell.set_symbol(" ");
-                }
-                dx += 1;
-                out_cols += 1;
-                col += 1;
-            }
-            continue;
-        }
-
-        let w = UnicodeWidthChar::width(ch).unwrap_or(0);
-        if w == 0 {
-            continue;
-        }
-        if col + w <= start_col {
-            col += w;
-            continue;
-        }
-        if col < start_col && col + w > start_col {
-            col += w;
-            continue;
-        }
-        if out_cols + w > max_cols {
-            return;
-        }
-
-        let s = ch.encode_utf8(&mut tmp);
-        if let Some(cell) = buf.cell_mut((x + dx, y)) {
-            cell.set_style(style);
-            cell.set_symbol(s);
-        }
-        dx += 1;
-        out_cols += 1;
-        col += w;
-
-        if w == 2 {
-            if out_cols >= max_cols {
-                return;
-            }
-            if let Some(cell) = buf.cell_mut((x + dx, y)) {
-                cell.set_style(style);
-                cell.set_symbol("");
-            }
-            dx += 1;
-            out_cols += 1;
-        }
-    }
+    let mut clip = Clip::new(start_col, max_cols, tabs);
+    clip.put_str(x, y, buf, input, style);
 }
 
 pub fn render_spans_clipped(
@@ -131,119 +250,181 @@ pub fn render_spans_clipped(
     buf: &mut Buffer,
     spans: &[Span<'static>],
     fallback_style: Style,
+) {
+    render_spans_clipped_with_tabs(
+        x,
+        y,
+        start_col,
+        max_cols,
+        buf,
+        spans,
+        fallback_style,
+        TabPolicy::default(),
+    )
+}
+
+/// Like [`render_spans_clipped`], expanding tabs with `tabs` (tab stops run across spans).
+#[allow(clippy::too_many_arguments)]
+pub fn render_spans_clipped_with_tabs(
+    x: u16,
+    y: u16,
+    start_col: u32,
+    max_cols: u16,
+    buf: &mut Buffer,
+    spans: &[Span<'static>],
+    fallback_style: Style,
+    tabs: TabPolicy,
 ) {
     if max_cols == 0 {
         return;
     }
 
-    let start_col = start_col as usize;
-    let max_cols = max_cols as usize;
-    let mut col = 0usize;
-    let mut out_cols = 0usize;
-    let mut dx = 0u16;
-    let mut tmp = [0u8; 4];
-
+    let mut clip = Clip::new(start_col, max_cols, tabs);
     for span in spans {
         let style = if span.style == Style::default() {
             fallback_style
         } else {
             span.style
         };
-        for ch in span.content.chars() {
-            if ch == '\t' {
-                for _ in 0..4 {
-                    if col < start_col {
-                        col += 1;
-                        continue;
-                    }
-                    if out_cols + 1 > max_cols {
-                        return;
-                    }
-                    if let Some(cell) = buf.cell_mut((x + dx, y)) {
-                        cell.set_style(style);
-                        cell.set_symbol(" ");
+        if !clip.put_str(x, y, buf, span.content.as_ref(), style) {
+            return;
+        }
+    }
+}
+
+/// Draws `tabs.glyph` over a row rendered from tab-expanded text, at the tab columns recorded by
+/// [`TabPolicy::tab_cols`]. `start_col` and `max_cols` match the call that rendered the row; cell
+/// styles are kept.
+pub fn render_tab_glyphs(
+    x: u16,
+    y: u16,
+    start_col: u32,
+    max_cols: u16,
+    buf: &mut Buffer,
+    tab_cols: &[u32],
+    tabs: TabPolicy,
+) {
+    let Some(glyph) = tabs.glyph else {
+        return;
+    };
+    let mut s = [0u8; 4];
+    let glyph = glyph.encode_utf8(&mut s);
+    let end = start_col.saturating_add(max_cols as u32);
+    for &col in tab_cols {
+        if col < start_col || col >= end {
+            continue;
+        }
+        if let Some(cell) = buf.cell_mut((x + (col - start_col) as u16, y)) {
+            cell.set_symbol(glyph);
+        }
+    }
+}
+
+/// Running position of a horizontally clipped single-row render.
+struct Clip {
+    start_col: usize,
+    max_cols: usize,
+    tabs: TabPolicy,
+    col: usize,
+    out_cols: usize,
+    dx: u16,
+}
+
+impl Clip {
+    fn new(start_col: u32, max_cols: u16, tabs: TabPolicy) -> Self {
+        Self {
+            start_col: start_col as usize,
+            max_cols: max_cols as usize,
+            tabs,
+            col: 0,
+            out_cols: 0,
+            dx: 0,
+        }
+    }
+
+    /// Renders `input` cluster by cluster. Returns `false` once the row is full.
+    fn put_str(&mut self, x: u16, y: u16, buf: &mut Buffer, input: &str, style: Style) -> bool {
+        for g in grapheme::graphemes(input) {
+            if g == "\t" {
+                let mut glyph = [0u8; 4];
+                let glyph = self.tabs.glyph.map(|c| &*c.encode_utf8(&mut glyph));
+                for i in 0..self.tabs.advance(self.col) {
+                    let cell = if i == 0 { glyph.unwrap_or(" ") } else { " " };
+                    if !self.put(x, y, buf, cell, 1, style) {
+                        return false;
                     }
-                    dx += 1;
-                    out_cols += 1;
-                    col += 1;
                 }
                 continue;
             }
-
-            let w = UnicodeWidthChar::width(ch).unwrap_or(0);
+            let w = grapheme::width(g);
             if w == 0 {
                 continue;
             }
-            if col + w <= start_col {
-                col += w;
-                continue;
-            }
-            if col < start_col && col + w > start_col {
-                col += w;
-                continue;
-            }
-            if out_cols + w > max_cols {
-                return;
+            if !self.put(x, y, buf, g, w, style) {
+                return false;
             }
+        }
+        true
+    }
 
-            let s = ch.encode_utf8(&mut tmp);
-            if let Some(cell) = buf.cell_mut((x + dx, y)) {
+    fn put(&mut self, x: u16, y: u16, buf: &mut Buffer, g: &str, w: usize, style: Style) -> bool {
+        // Clusters starting left of the viewport are skipped whole, even if they end inside it.
+        if self.col < self.start_col {
+            self.col += w;
+            return true;
+        }
+        if self.out_cols + w > self.max_cols {
+            return false;
+        }
+        for i in 0..w {
+            if let Some(cell) = buf.cell_mut((x + self.dx, y)) {
                 cell.set_style(style);
-                cell.set_symbol(s);
-            }
-            dx += 1;
-            out_cols += 1;
-            col += w;
-
-            if w == 2 {
-                if out_cols >= max_cols {
-                    return;
-                }
-                if let Some(cell) = buf.cell_mut((x + dx, y)) {
-                    cell.set_style(style);
-                    cell.set_symbol("");
-                }
-                dx += 1;
-                out_cols += 1;
+                cell.set_symbol(if i == 0 { g } else { "" });
             }
+            self.dx += 1;
         }
+        self.out_cols += w;
+        self.col += w;
+        true
     }
 }
 
 pub fn slice_by_cols(input: &str, start_col: u32, max_cols: u16) -> String {
+    slice_by_cols_with_tabs(input, start_col, max_cols, TabPolicy::default())
+}
+
+/// Like [`slice_by_cols`], expanding tabs with `tabs`.
+pub fn slice_by_cols_with_tabs(
+    input: &str,
+    start_col: u32,
+    max_cols: u16,
+    tabs: TabPolicy,
+) -> String {
     if max_cols == 0 {
         return String::new();
     }
 
     let start_col = start_col as usize;
     let max_cols = max_cols as usize;
-    let input = if input.contains('\t') {
-        std::borrow::Cow::Owned(input.replace('\t', "    "))
-    } else {
-        std::borrow::Cow::Borrowed(input)
-    };
+    let input = tabs.expand(input);
 
     let mut col = 0usize;
     let mut out_cols = 0usize;
     let mut out = String::new();
 
-    for ch in input.chars() {
-        let w = UnicodeWidthChar::width(ch).unwrap_or(0);
+    for g in grapheme::graphemes(&input) {
+        let w = grapheme::width(g);
         if w == 0 {
             continue;
         }
-        if col + w <= start_col {
-            col += w;
-            continue;
-        }
-        if col < start_col && col + w > start_col {
+        if col < start_col {
             col += w;
             continue;
         }
         if out_cols + w > max_cols {
             break;
         }
-        out.push(ch);
+        out.push_str(g);
         col += w;
         out_cols += w;
     }
@@ -255,14 +436,36 @@ pub fn slice_by_cols(input: &str, start_col: u32, max_cols: u16) -> String {
 ///
 /// This is useful for implementing "mouse drag to select and copy" for monospace text while still
 /// handling:
-/// - tabs (expanded as 4 spaces)
+/// - tabs (expanded to the next tab stop of the default [`TabPolicy`]; see
+///   [`byte_range_for_cols_with_tabs`])
 /// - wide characters (e.g. CJK, emoji) via `unicode-width`
+/// - multi-`char` grapheme clusters (ZWJ emoji, flags, combining accents), which are never split
 ///
 /// Notes:
 /// - Column coordinates are in terminal cell units, not bytes.
 /// - `end_col` is exclusive (like Rust ranges).
 /// - If the requested range is empty or cannot be represented cleanly, this returns `None`.
 pub fn byte_range_for_cols(input: &str, start_col: u32, end_col: u32) -> Option<(usize, usize)> {
+    byte_range_for_cols_with_tabs(input, start_col, end_col, TabPolicy::default())
+}
+
+/// Like [`byte_range_for_cols`], expanding tabs with `tabs`.
+pub fn byte_range_for_cols_with_tabs(
+    input: &str,
+    start_col: u32,
+    end_col: u32,
+    tabs: TabPolicy,
+) -> Option<(usize, usize)> {
+    byte_range_for_cols_in_parts([input], start_col, end_col, tabs)
+}
+
+/// Shared implementation of the `byte_range_for_cols*` helpers over concatenated `parts`.
+fn byte_range_for_cols_in_parts<'a>(
+    parts: impl IntoIterator<Item = &'a str>,
+    start_col: u32,
+    end_col: u32,
+    tabs: TabPolicy,
+) -> Option<(usize, usize)> {
     let start_col = start_col as usize;
     let end_col = end_col as usize;
 
@@ -273,54 +476,56 @@ pub fn byte_range_for_cols(input: &str, start_col: u32, end_col: u32) -> Option<
     let mut col = 0usize;
     let mut start_b: Option<usize> = None;
     let mut end_b: Option<usize> = None;
+    let mut total = 0usize;
+    let mut done = false;
+
+    for part in parts {
+        for (local_b, g) in grapheme::grapheme_indices(part) {
+            if done {
+                break;
+            }
+            let b = total + local_b;
+            if g == "\t" {
+                // Every cell of a tab maps to the tab itself.
+                let n = tabs.advance(col);
+                for c in col..col + n {
+                    if c == start_col && start_b.is_none() {
+                        start_b = Some(b);
+                    }
+                    if c == end_col && end_b.is_none() {
+                        end_b = Some(b);
+                    }
+                }
+                col += n;
+            } else {
+                let w = grapheme::width(g);
+                if w == 0 {
+                    continue;
+                }
 
-    for (b, ch) in input.char_indices() {
-        if ch == '\t' {
-            for _ in 0..4 {
                 if col == start_col && start_b.is_none() {
                     start_b = Some(b);
                 }
                 if col == end_col && end_b.is_none() {
                     end_b = Some(b);
                 }
-                col += 1;
-            }
-            if start_b.is_some() && end_b.is_some() {
-                break;
-            }
-            continue;
-        }
-
-        let w = UnicodeWidthChar::width(ch).unwrap_or(0);
-        if w == 0 {
-            continue;
-        }
-
-        if col == start_col && start_b.is_none() {
-            start_b = Some(b);
-        }
-        if col == end_col && end_b.is_none() {
-            end_b = Some(b);
-        }
 
-        if col < start_col && col + w > start_col {
-            start_b = Some(b + ch.len_utf8());
-        }
-        if col < end_col && col + w > end_col {
-            end_b = Some(b + ch.len_utf8());
-        }
+                if col < start_col && col + w > start_col {
+                    start_b = Some(b + g.len());
+                }
+                if col < end_col && col + w > end_col {
+                    end_b = Some(b + g.len());
+                }
 
-        col += w;
-        if start_b.is_some() && end_b.is_some() {
-            break;
-        }
-        if col > end_col {
-            break;
+                col += w;
+            }
+            done = (start_b.is_some() && end_b.is_some()) || col > end_col;
         }
+        total += part.len();
     }
 
-    let start_b = start_b.unwrap_or(input.len());
-    let end_b = end_b.unwrap_or(input.len());
+    let start_b = start_b.unwrap_or(total);
+    let end_b = end_b.unwrap_or(total);
     if start_b >= end_b {
         None
     } else {
@@ -328,6 +533,62 @@ pub fn byte_range_for_cols(input: &str, start_col: u32, end_col: u32) -> Option<
     }
 }
 
+/// Returns the inclusive column range of the word under `col` in `input`.
+///
+/// Columns are display columns, with tabs expanded like [`byte_range_for_cols`]. A "word" is a
+/// run of alphanumeric characters and `_`; clicking on whitespace or punctuation selects the
+/// surrounding run of the same kind. Returns `None` when `col` is past the end of the line.
+pub fn word_cols_at(input: &str, col: u32) -> Option<(u32, u32)> {
+    word_cols_at_with_tabs(input, col, TabPolicy::default())
+}
+
+/// Like [`word_cols_at`], expanding tabs with `tabs`.
+pub fn word_cols_at_with_tabs(input: &str, col: u32, tabs: TabPolicy) -> Option<(u32, u32)> {
+    let cells = word_cells(input, tabs);
+    let hit = cells.iter().position(|&(s, e, _)| s <= col && col <= e)?;
+    let kind = &cells[hit].2;
+    let first = cells[..hit]
+        .iter()
+        .rposition(|c| c.2 != *kind)
+        .map_or(0, |i| i + 1);
+    let last = cells[hit..]
+        .iter()
+        .position(|c| c.2 != *kind)
+        .map_or(cells.len() - 1, |i| hit + i - 1);
+    Some((cells[first].0, cells[last].1))
+}
+
+/// Character classes for word selection and word motions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum WordClass {
+    Word,
+    Space,
+    Other,
+}
+
+/// The non-empty cells of `input` as inclusive `(first, last, class)` column ranges.
+pub(crate) fn word_cells(input: &str, tabs: TabPolicy) -> Vec<(u32, u32, WordClass)> {
+    let mut cells = Vec::new();
+    let mut start = 0u32;
+    for g in grapheme::graphemes(input) {
+        let w = tabs.width_at(g, start as usize) as u32;
+        if w == 0 {
+            continue;
+        }
+        let ch = g.chars().next().unwrap_or(' ');
+        let class = if ch.is_alphanumeric() || ch == '_' {
+            WordClass::Word
+        } else if ch.is_whitespace() {
+            WordClass::Space
+        } else {
+            WordClass::Other
+        };
+        cells.push((start, start + w - 1, class));
+        start += w;
+    }
+    cells
+}
+
 /// Like [`byte_range_for_cols`], but for `Span`s concatenated in-order.
 ///
 /// The returned byte range is in the *joined* plain-text representation, i.e. as if all span
@@ -337,64 +598,22 @@ pub fn byte_range_for_cols_in_spans(
     start_col: u32,
     end_col: u32,
 ) -> Option<(usize, usize)> {
-    let start_col = start_col as usize;
-    let end_col = end_col as usize;
-    if start_col >= end_col {
-        return None;
-    }
-
-    let mut col = 0usize;
-    let mut start_b: Option<usize> = None;
-    let mut end_b: Option<usize> = None;
-    let mut global_b = 0usize;
-
-    for span in spans {
-        let s = span.content.as_ref();
-        for (local_b, ch) in s.char_indices() {
-            let abs_b = global_b + local_b;
-
-            if start_b.is_none() && col == start_col {
-                start_b = Some(abs_b);
-            }
-            if end_b.is_none() && col == end_col {
-                end_b = Some(abs_b);
-            }
-
-            let w = UnicodeWidthChar::width(ch).unwrap_or(0);
-            if w == 0 {
-                continue;
-            }
-
-            if col < start_col && col + w > start_col {
-                start_b = Some(abs_b + ch.len_utf8());
-            }
-            if col < end_col && col + w > end_col {
-                end_b = Some(abs_b + ch.len_utf8());
-            }
-
-            col += w;
-
-            if start_b.is_some() && end_b.is_some() {
-                break;
-            }
-            if col > end_col {
-                break;
-            }
-        }
-        global_b += s.len();
-        if start_b.is_some() && end_b.is_some() {
-            break;
-        }
-    }
+    byte_range_for_cols_in_spans_with_tabs(spans, start_col, end_col, TabPolicy::default())
+}
 
-    let total = global_b;
-    let start_b = start_b.unwrap_or(total);
-    let end_b = end_b.unwrap_or(total);
-    if start_b >= end_b {
-        None
-    } else {
-        Some((start_b, end_b))
-    }
+/// Like [`byte_range_for_cols_in_spans`], expanding tabs with `tabs`.
+pub fn byte_range_for_cols_in_spans_with_tabs(
+    spans: &[Span<'static>],
+    start_col: u32,
+    end_col: u32,
+    tabs: TabPolicy,
+) -> Option<(usize, usize)> {
+    byte_range_for_cols_in_parts(
+        spans.iter().map(|s| s.content.as_ref()),
+        start_col,
+        end_col,
+        tabs,
+    )
 }
 
 pub fn slice_spans_by_bytes(spans: &[Span<'static>], start_b: usize, end_b: usize) -> String {
@@ -434,6 +653,17 @@ pub fn apply_modifier_to_byte_ranges(
     spans: Vec<Span<'static>>,
     ranges: &[(usize, usize)],
     modifier: Modifier,
+) -> Vec<Span<'static>> {
+    apply_style_to_byte_ranges(spans, ranges, Style::default().add_modifier(modifier))
+}
+
+/// Patches `style` onto the parts of `spans` covered by the sorted byte `ranges`.
+///
+/// Used for selection and intraline highlights (e.g. [`crate::theme::ChromeTheme::selection`]).
+pub fn apply_style_to_byte_ranges(
+    spans: Vec<Span<'static>>,
+    ranges: &[(usize, usize)],
+    style: Style,
 ) -> Vec<Span<'static>> {
     if ranges.is_empty() {
         return spans;
@@ -471,7 +701,7 @@ pub fn apply_modifier_to_byte_ranges(
             if end_in > start_in {
                 out.push(Span::styled(
                     s[start_in..end_in].to_string(),
-                    span.style.add_modifier(modifier),
+                    span.style.patch(style),
                 ));
             }
 
@@ -540,6 +770,17 @@ mod tests {
         assert_eq!(slice_spans_by_bytes(&spans, a, b), "bc");
     }
 
+    #[test]
+    fn word_cols_at_groups_by_character_class() {
+        let s = "let 你好_x = a.b;";
+        assert_eq!(word_cols_at(s, 1), Some((0, 2)));
+        assert_eq!(word_cols_at(s, 5), Some((4, 9)));
+        assert_eq!(word_cols_at(s, 10), Some((10, 10)));
+        assert_eq!(word_cols_at(s, 14), Some((14, 14)));
+        assert_eq!(word_cols_at("\tx", 2), Some((0, 3)));
+        assert_eq!(word_cols_at(s, 40), None);
+    }
+
     #[test]
     fn render_scrollbar_does_not_panic() {
         let mut state = ViewportState::default();
@@ -548,4 +789,100 @@ mod tests {
         let mut buf = Buffer::empty(Rect::new(0, 0, 1, 5));
         render_scrollbar(Rect::new(0, 0, 1, 5), &mut buf, &state, Style::default());
     }
+
+    #[test]
+    fn horizontal_scrollbar_tracks_x() {
+        let mut state = ViewportState::default();
+        state.set_viewport(10, 5);
+        state.set_content(40, 5);
+        state.x = 30;
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 1));
+        render_scrollbar_x(buf.area, &mut buf, &state, Style::default());
+        let row: String = (0..8).map(|x| buf[(x, 0)].symbol()).collect();
+        assert_eq!(row, "      ██");
+    }
+
+    #[test]
+    fn scrollbar_markers_scale_lines_to_the_track() {
+        let mut state = ViewportState::default();
+        state.set_viewport(10, 4);
+        state.set_content(10, 100);
+        let chrome = crate::theme::Theme::default().chrome;
+        let mut buf = Buffer::empty(Rect::new(0, 0, 1, 4));
+        render_scrollbar(buf.area, &mut buf, &state, chrome.scrollbar);
+        render_scrollbar_markers(
+            buf.area,
+            &mut buf,
+            &state,
+            &[
+                ScrollbarMarker::range(50..99, MarkerKind::Selection),
+                ScrollbarMarker::new(60, MarkerKind::Search),
+                ScrollbarMarker::new(99, MarkerKind::Diagnostic),
+                ScrollbarMarker::range(5..5, MarkerKind::Hunk),
+            ],
+            &chrome,
+        );
+        let col: Vec<&str> = (0..4).map(|y| buf[(0, y)].symbol()).collect();
+        assert_eq!(col, ["█", " ", "━", "■"]);
+        assert_eq!(buf[(0, 2)].fg, chrome.marker_search.fg.unwrap());
+    }
+
+    #[test]
+    fn scrollbar_layout_leaves_the_corner_empty() {
+        let layout = ScrollbarLayout::new(Rect::new(2, 3, 10, 5), true, true);
+        assert_eq!(layout.content, Rect::new(2, 3, 9, 4));
+        assert_eq!(layout.vertical, Some(Rect::new(11, 3, 1, 4)));
+        assert_eq!(layout.horizontal, Some(Rect::new(2, 7, 9, 1)));
+        let tiny = ScrollbarLayout::new(Rect::new(0, 0, 1, 1), true, true);
+        assert_eq!((tiny.vertical, tiny.horizontal), (None, None));
+    }
+
+    #[test]
+    fn slicing_never_splits_grapheme_clusters() {
+        let s = "e\u{301}👨\u{200d}👩\u{200d}👧🇯🇵";
+        assert_eq!(slice_by_cols(s, 0, 3), "e\u{301}👨\u{200d}👩\u{200d}👧");
+        assert_eq!(slice_by_cols(s, 2, 2), "🇯🇵");
+        assert_eq!(
+            byte_range_for_cols(s, 1, 3).map(|(a, b)| &s[a..b]),
+            Some("👨\u{200d}👩\u{200d}👧")
+        );
+        assert_eq!(word_cols_at("cafe\u{301} au", 3), Some((0, 3)));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+        render_str_clipped(0, 0, 0, 4, &mut buf, s, Style::default());
+        assert_eq!(buf[(0, 0)].symbol(), "e\u{301}");
+        assert_eq!(buf[(1, 0)].symbol(), "👨\u{200d}👩\u{200d}👧");
+        assert_eq!(buf[(3, 0)].symbol(), " ");
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn column_slices_are_made_of_whole_clusters(
+            text in grapheme::fixtures::text(),
+            start in 0u32..40,
+            len in 1u16..20,
+        ) {
+            let sliced = slice_by_cols(&text, start, len);
+            proptest::prop_assert!(unicode_width::UnicodeWidthStr::width(sliced.as_str()) <= len as usize);
+            for g in grapheme::graphemes(&sliced) {
+                proptest::prop_assert!(grapheme::fixtures::CLUSTERS.contains(&g));
+            }
+
+            let boundary = |b: usize| b == text.len() || grapheme::grapheme_indices(&text).any(|(i, _)| i == b);
+            if let Some((a, b)) = byte_range_for_cols(&text, start, start + len as u32) {
+                proptest::prop_assert!(boundary(a) && boundary(b));
+            }
+            let spans = [Span::raw(text.clone())];
+            if let Some((a, b)) = byte_range_for_cols_in_spans(&spans, start, start + len as u32) {
+                proptest::prop_assert!(boundary(a) && boundary(b));
+            }
+
+            let mut buf = Buffer::empty(Rect::new(0, 0, len, 1));
+            render_str_clipped(0, 0, start, len, &mut buf, &text, Style::default());
+            for cell in buf.content() {
+                let sym = cell.symbol();
+                proptest::prop_assert!(sym.is_empty() || grapheme::fixtures::CLUSTERS.contains(&sym));
+            }
+        }
+    }
 }
@@ -1,9 +1,17 @@
 use crate::input::KeyCode;
 use crate::input::KeyEvent;
 use crate::keymap;
+use crate::keymap::Binding;
+use crate::keymap::Chord;
+use crate::keymap::Keymap;
+use crate::viewport::ScrollAnimator;
+use crate::viewport::SmoothScroll;
 use crate::viewport::ViewportState;
+use std::time::Duration;
+use std::time::Instant;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScrollAction {
     Up,
     Down,
@@ -16,8 +24,14 @@ pub enum ScrollAction {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct ScrollBindings {
     pub line_step: i32,
+    /// Columns per [`ScrollAction::Left`]/[`ScrollAction::Right`] and per horizontal wheel step.
     pub horiz_step: i32,
     pub up: Vec<KeyEvent>,
     pub down: Vec<KeyEvent>,
@@ -27,6 +41,20 @@ pub struct ScrollBindings {
     pub page_down: Vec<KeyEvent>,
     pub top: Vec<KeyEvent>,
     pub bottom: Vec<KeyEvent>,
+    /// Multi-key sequences, e.g. `g g` for [`ScrollAction::Top`].
+    ///
+    /// Only honored by [`ScrollBindings::keymap`]; [`ScrollBindings::action_for`] matches single
+    /// keys.
+    pub chords: Vec<Chord<ScrollAction>>,
+    /// How long a pending chord prefix waits for its next key; `None` waits forever.
+    ///
+    /// Views resolve an expired prefix on [`InputEvent::Tick`](crate::input::InputEvent::Tick).
+    pub chord_timeout: Option<Duration>,
+    /// Mouse-wheel acceleration and eased scrolling; `None` scrolls wheel steps immediately.
+    ///
+    /// Views ease on [`InputEvent::Tick`](crate::input::InputEvent::Tick), so the app has to
+    /// send ticks (e.g. every 16ms) while a view is animating.
+    pub smooth: Option<SmoothScroll>,
 }
 
 impl Default for ScrollBindings {
@@ -40,8 +68,14 @@ impl Default for ScrollBindings {
             right: vec![KeyEvent::new(KeyCode::Right), keymap::key_char('l')],
             page_up: vec![KeyEvent::new(KeyCode::PageUp), keymap::key_ctrl('u')],
             page_down: vec![KeyEvent::new(KeyCode::PageDown), keymap::key_ctrl('d')],
-            top: vec![KeyEvent::new(KeyCode::Home), keymap::key_char('g')],
+            top: vec![KeyEvent::new(KeyCode::Home)],
             bottom: vec![KeyEvent::new(KeyCode::End), keymap::key_char('G')],
+            chords: vec![Chord::new(
+                vec![keymap::key_char('g'), keymap::key_char('g')],
+                ScrollAction::Top,
+            )],
+            chord_timeout: Some(Duration::from_secs(1)),
+            smooth: None,
         }
     }
 }
@@ -87,6 +121,88 @@ impl ScrollBindings {
         None
     }
 
+    /// Builds a [`Keymap`] containing both the single-key bindings and [`Self::chords`].
+    ///
+    /// Views build it once per options value and keep it next to their [`ChordState`].
+    ///
+    /// [`ChordState`]: crate::keymap::ChordState
+    pub fn keymap(&self) -> Keymap<ScrollAction> {
+        let mut map = Keymap::new();
+        if let Some(timeout) = self.chord_timeout {
+            map = map.with_timeout(timeout);
+        }
+        map.bind_each(&self.up, ScrollAction::Up);
+        map.bind_each(&self.down, ScrollAction::Down);
+        map.bind_each(&self.left, ScrollAction::Left);
+        map.bind_each(&self.right, ScrollAction::Right);
+        map.bind_each(&self.page_up, ScrollAction::PageUp);
+        map.bind_each(&self.page_down, ScrollAction::PageDown);
+        map.bind_each(&self.top, ScrollAction::Top);
+        map.bind_each(&self.bottom, ScrollAction::Bottom);
+        for chord in &self.chords {
+            map.bind(&chord.keys, chord.action);
+        }
+        map
+    }
+
+    /// Describes the configured bindings for help screens, one [`Binding`] per action.
+    ///
+    /// Actions without any key are omitted. Each chord follows as its own entry whose `keys`
+    /// hold the sequence (like the entries of [`crate::help::WhichKey`]), so every entry's keys
+    /// match its label.
+    pub fn help_bindings(&self) -> Vec<Binding> {
+        let actions = [
+            (ScrollAction::Up, &self.up),
+            (ScrollAction::Down, &self.down),
+            (ScrollAction::Left, &self.left),
+            (ScrollAction::Right, &self.right),
+            (ScrollAction::PageUp, &self.page_up),
+            (ScrollAction::PageDown, &self.page_down),
+            (ScrollAction::Top, &self.top),
+            (ScrollAction::Bottom, &self.bottom),
+        ];
+        let singles = actions
+            .into_iter()
+            .filter(|(_, keys)| !keys.is_empty())
+            .map(|(action, keys)| Binding::from_keys(help_desc(action), keys.clone()));
+        let chords = self.chords.iter().filter(|c| !c.keys.is_empty()).map(|c| {
+            Binding::new(
+                keymap::format_key_sequence(&c.keys),
+                help_desc(c.action),
+                c.keys.clone(),
+            )
+        });
+        singles.chain(chords).collect()
+    }
+
+    /// Scrolls `lines` rows (negative = up) for a mouse-wheel event received at `now`, through
+    /// `animator` when [`Self::smooth`] is set.
+    pub fn wheel(
+        &self,
+        animator: &mut ScrollAnimator,
+        state: &mut ViewportState,
+        lines: i32,
+        now: Instant,
+    ) {
+        match &self.smooth {
+            Some(smooth) => animator.wheel(state, smooth, lines, now),
+            None => state.scroll_y_by(lines),
+        }
+    }
+
+    /// Advances a smooth scroll started by [`Self::wheel`]; returns whether `state.y` changed.
+    pub fn tick(
+        &self,
+        animator: &mut ScrollAnimator,
+        state: &mut ViewportState,
+        now: Instant,
+    ) -> bool {
+        match &self.smooth {
+            Some(smooth) => animator.tick(state, smooth, now),
+            None => false,
+        }
+    }
+
     pub fn apply(&self, state: &mut ViewportState, action: ScrollAction) {
         match action {
             ScrollAction::Up => state.scroll_y_by(-self.line_step),
@@ -100,3 +216,91 @@ impl ScrollBindings {
         }
     }
 }
+
+fn help_desc(action: ScrollAction) -> &'static str {
+    match action {
+        ScrollAction::Up => "up",
+        ScrollAction::Down => "down",
+        ScrollAction::Left => "left",
+        ScrollAction::Right => "right",
+        ScrollAction::PageUp => "page up",
+        ScrollAction::PageDown => "page down",
+        ScrollAction::Top => "top",
+        ScrollAction::Bottom => "bottom",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keymap::ChordMatch;
+    use crate::keymap::ChordState;
+
+    #[test]
+    fn gg_scrolls_to_top() {
+        let bindings = ScrollBindings::default().keymap();
+        let mut chord = ChordState::new();
+        let g = keymap::key_char('g');
+        assert_eq!(bindings.feed(&mut chord, &g, None), ChordMatch::Pending);
+        assert_eq!(
+            bindings.feed(&mut chord, &g, None),
+            ChordMatch::Matched(ScrollAction::Top)
+        );
+        assert_eq!(
+            bindings.feed(&mut chord, &keymap::key_char('j'), None),
+            ChordMatch::Matched(ScrollAction::Down)
+        );
+    }
+
+    #[test]
+    fn chord_timeout_resolves_a_pending_prefix() {
+        let bindings = ScrollBindings {
+            chord_timeout: Some(Duration::from_millis(500)),
+            ..Default::default()
+        }
+        .keymap();
+        let mut chord = ChordState::new();
+        let g = keymap::key_char('g');
+        let t0 = Instant::now();
+        assert_eq!(bindings.feed(&mut chord, &g, Some(t0)), ChordMatch::Pending);
+        assert_eq!(
+            bindings.expire(&mut chord, t0 + Duration::from_millis(100)),
+            ChordMatch::Pending
+        );
+        assert_eq!(
+            bindings.expire(&mut chord, t0 + Duration::from_secs(1)),
+            ChordMatch::NoMatch
+        );
+        assert!(!chord.is_pending());
+    }
+
+    #[test]
+    fn help_bindings_reflect_configuration() {
+        let mut bindings = ScrollBindings::default();
+        bindings.left.clear();
+        let help = bindings.help_bindings();
+        assert!(help.iter().all(|b| b.help_desc != "left"));
+        let top: Vec<_> = help.iter().filter(|b| b.help_desc == "top").collect();
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].help_key, "home");
+        assert_eq!(top[0].keys, bindings.top);
+        assert_eq!(top[1].help_key, "g g");
+        assert_eq!(top[1].keys, bindings.chords[0].keys);
+    }
+
+    #[test]
+    fn g_alone_no_longer_scrolls_to_top() {
+        let bindings = ScrollBindings::default();
+        let g = keymap::key_char('g');
+        assert_eq!(bindings.action_for(&g), None);
+
+        let keymap = bindings.keymap();
+        let mut chord = ChordState::new();
+        let t0 = Instant::now();
+        assert_eq!(keymap.feed(&mut chord, &g, Some(t0)), ChordMatch::Pending);
+        assert_eq!(
+            keymap.expire(&mut chord, t0 + Duration::from_secs(2)),
+            ChordMatch::NoMatch
+        );
+    }
+}
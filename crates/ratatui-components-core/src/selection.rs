@@ -1,6 +1,17 @@
+use crate::component::ComponentAction;
 use crate::input::KeyCode;
 use crate::input::KeyEvent;
 use crate::keymap;
+use crate::keymap::Binding;
+use crate::keymap::ChordMatch;
+use crate::keymap::ChordState;
+use crate::keymap::Keymap;
+use crate::render;
+use crate::render::WordClass;
+use crate::scroll::ScrollAction;
+use crate::tabs::TabPolicy;
+use crate::viewport::ViewportState;
+use std::time::Instant;
 
 /// Actions produced by selection-capable widgets.
 ///
@@ -14,18 +25,39 @@ pub enum SelectionAction {
     CopyRequested(String),
 }
 
+impl ComponentAction for SelectionAction {
+    fn is_consumed(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+}
+
 /// Key bindings for selection interactions.
 ///
 /// The defaults are intentionally Vim-like:
 /// - `y` requests copying the current selection
 /// - `Esc` clears the selection
+/// - `v`, `V` and `Ctrl-v` start character, line and block visual mode (see [`VisualSelection`])
+/// - in visual mode, `w`/`b` and `0`/`$` move the caret by words and to the line ends, and the
+///   scroll keys move the caret instead of the viewport
 ///
 /// Widgets typically check these bindings in their `handle_event_action*` method and return a
 /// [`SelectionAction::CopyRequested`] so callers can plug in a clipboard implementation.
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct SelectionBindings {
     pub copy: Vec<KeyEvent>,
     pub clear: Vec<KeyEvent>,
+    pub visual_char: Vec<KeyEvent>,
+    pub visual_line: Vec<KeyEvent>,
+    pub visual_block: Vec<KeyEvent>,
+    pub word_forward: Vec<KeyEvent>,
+    pub word_back: Vec<KeyEvent>,
+    pub line_start: Vec<KeyEvent>,
+    pub line_end: Vec<KeyEvent>,
 }
 
 impl Default for SelectionBindings {
@@ -33,6 +65,13 @@ impl Default for SelectionBindings {
         Self {
             copy: vec![keymap::key_char('y')],
             clear: vec![KeyEvent::new(KeyCode::Esc)],
+            visual_char: vec![keymap::key_char('v')],
+            visual_line: vec![keymap::key_char('V')],
+            visual_block: vec![keymap::key_ctrl('v')],
+            word_forward: vec![keymap::key_char('w')],
+            word_back: vec![keymap::key_char('b')],
+            line_start: vec![keymap::key_char('0')],
+            line_end: vec![keymap::key_char('$')],
         }
     }
 }
@@ -47,4 +86,550 @@ impl SelectionBindings {
     pub fn is_clear(&self, key: &KeyEvent) -> bool {
         self.clear.iter().any(|p| keymap::key_event_matches(p, key))
     }
+
+    /// The visual mode `key` starts (or leaves, if it is the current one).
+    pub fn visual_mode_for(&self, key: &KeyEvent) -> Option<VisualMode> {
+        [
+            (&self.visual_char, VisualMode::Char),
+            (&self.visual_line, VisualMode::Line),
+            (&self.visual_block, VisualMode::Block),
+        ]
+        .into_iter()
+        .find(|(keys, _)| keys.iter().any(|p| keymap::key_event_matches(p, key)))
+        .map(|(_, mode)| mode)
+    }
+
+    /// The visual-mode caret motion bound to `key`, besides the scroll keys.
+    pub fn motion_for(&self, key: &KeyEvent) -> Option<CaretMotion> {
+        [
+            (&self.word_forward, CaretMotion::WordForward),
+            (&self.word_back, CaretMotion::WordBack),
+            (&self.line_start, CaretMotion::LineStart),
+            (&self.line_end, CaretMotion::LineEnd),
+        ]
+        .into_iter()
+        .find(|(keys, _)| keys.iter().any(|p| keymap::key_event_matches(p, key)))
+        .map(|(_, motion)| motion)
+    }
+
+    /// Describes the configured bindings for help screens.
+    pub fn help_bindings(&self) -> Vec<Binding> {
+        [
+            (&self.copy, "copy selection"),
+            (&self.clear, "clear selection"),
+            (&self.visual_char, "visual mode"),
+            (&self.visual_line, "visual line mode"),
+            (&self.visual_block, "visual block mode"),
+            (&self.word_forward, "next word (visual)"),
+            (&self.word_back, "previous word (visual)"),
+            (&self.line_start, "line start (visual)"),
+            (&self.line_end, "line end (visual)"),
+        ]
+        .into_iter()
+        .filter(|(keys, _)| !keys.is_empty())
+        .map(|(keys, desc)| Binding::from_keys(desc, keys.clone()))
+        .collect()
+    }
+}
+
+/// Returns the selection a multi-click should make at `(line, col)`, if any.
+///
+/// A double-click selects the word under the cursor (see [`render::word_cols_at`]) and a
+/// triple-click selects the whole line. `text` is only called for double-clicks and should return
+/// the plain text of `line`. Columns in the returned range are inclusive, matching how the
+/// selection-capable views store their selections.
+pub fn click_selection<L: Copy>(
+    count: u8,
+    line: L,
+    col: u32,
+    text: impl FnOnce() -> String,
+) -> Option<((L, u32), (L, u32))> {
+    match count {
+        2 => {
+            let (start, end) = render::word_cols_at(&text(), col)?;
+            Some(((line, start), (line, end)))
+        }
+        3 => Some(((line, 0), (line, u32::MAX))),
+        _ => None,
+    }
+}
+
+/// Returns the columns `[from, to)` that the inclusive `selection` covers on `line`, if any.
+///
+/// Stream selections cover the rest of their first line, whole lines in between and the start of
+/// their last line; `block` selections cover the same columns on every line.
+pub fn line_cols<L: Copy + Ord>(
+    selection: ((L, u32), (L, u32)),
+    block: bool,
+    line: L,
+) -> Option<(u32, u32)> {
+    let (a, b) = selection;
+    let (start, end) = if a <= b { (a, b) } else { (b, a) };
+    if line < start.0 || line > end.0 {
+        return None;
+    }
+    if block {
+        return Some((a.1.min(b.1), a.1.max(b.1).saturating_add(1)));
+    }
+    let from = if line == start.0 { start.1 } else { 0 };
+    let to = if line == end.0 {
+        end.1.saturating_add(1)
+    } else {
+        u32::MAX
+    };
+    Some((from, to))
+}
+
+/// Keyboard selection modes, named after Vim's.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VisualMode {
+    /// Characters from the anchor to the caret, in reading order.
+    Char,
+    /// Whole lines from the anchor's to the caret's.
+    Line,
+    /// The rectangle with the anchor and the caret at its corners.
+    Block,
+}
+
+/// Caret movements in visual mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CaretMotion {
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    WordForward,
+    WordBack,
+    LineStart,
+    LineEnd,
+}
+
+impl From<ScrollAction> for CaretMotion {
+    fn from(action: ScrollAction) -> Self {
+        match action {
+            ScrollAction::Up => Self::Up,
+            ScrollAction::Down => Self::Down,
+            ScrollAction::Left => Self::Left,
+            ScrollAction::Right => Self::Right,
+            ScrollAction::PageUp => Self::PageUp,
+            ScrollAction::PageDown => Self::PageDown,
+            ScrollAction::Top => Self::Top,
+            ScrollAction::Bottom => Self::Bottom,
+        }
+    }
+}
+
+/// What a key does in [`VisualSelection`], as resolved by [`VisualSelection::step`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VisualStep {
+    /// Not a visual-mode key; handle it as usual.
+    None,
+    /// A prefix of a scroll chord; wait for the next key.
+    Pending,
+    Toggle(VisualMode),
+    Move(CaretMotion),
+}
+
+/// A keyboard caret and visual mode for read-only views, in the view's `(line, column)` cells.
+///
+/// Views resolve keys with [`Self::step`], apply them with [`Self::apply`] and read the result
+/// back with [`Self::selection`], so keyboard and mouse selections render and copy the same way.
+/// While visual mode is active the scroll keys move the caret and the viewport follows it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VisualSelection {
+    mode: Option<VisualMode>,
+    anchor: (usize, u32),
+    caret: (usize, u32),
+    /// Column kept by vertical motions across shorter lines; `u32::MAX` sticks to line ends.
+    goal_col: u32,
+}
+
+impl VisualSelection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn mode(&self) -> Option<VisualMode> {
+        self.mode
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.mode.is_some()
+    }
+
+    /// Whether the selection is a [`VisualMode::Block`] rectangle (see [`line_cols`]).
+    pub fn is_block(&self) -> bool {
+        self.mode == Some(VisualMode::Block)
+    }
+
+    /// The caret position while visual mode is active.
+    pub fn caret(&self) -> Option<(usize, u32)> {
+        self.mode.map(|_| self.caret)
+    }
+
+    /// Leaves visual mode; the caret is kept for the next time it starts on screen.
+    pub fn stop(&mut self) {
+        self.mode = None;
+    }
+
+    /// The selected range with inclusive columns, like the mouse selections of the views.
+    ///
+    /// Line mode spans full lines; block mode returns the rectangle's corners.
+    pub fn selection(&self) -> Option<((usize, u32), (usize, u32))> {
+        let (a, c) = (self.anchor, self.caret);
+        match self.mode? {
+            VisualMode::Char => Some((a, c)),
+            VisualMode::Line => Some(((a.0.min(c.0), 0), (a.0.max(c.0), u32::MAX))),
+            VisualMode::Block => Some(((a.0.min(c.0), a.1.min(c.1)), (a.0.max(c.0), a.1.max(c.1)))),
+        }
+    }
+
+    /// Resolves `key`, received at `now`: a visual-mode toggle, or (while active) a caret
+    /// motion, with the scroll keys and chords of `scroll` moving the caret.
+    ///
+    /// `scroll` is usually built once per options value by
+    /// [`ScrollBindings::keymap`](crate::scroll::ScrollBindings::keymap).
+    pub fn step(
+        &self,
+        key: &KeyEvent,
+        bindings: &SelectionBindings,
+        scroll: &Keymap<ScrollAction>,
+        chord: &mut ChordState,
+        now: Option<Instant>,
+    ) -> VisualStep {
+        if let Some(mode) = bindings.visual_mode_for(key) {
+            return VisualStep::Toggle(mode);
+        }
+        if self.mode.is_none() {
+            return VisualStep::None;
+        }
+        if let Some(motion) = bindings.motion_for(key) {
+            return VisualStep::Move(motion);
+        }
+        match scroll.feed(chord, key, now) {
+            ChordMatch::Matched(action) => VisualStep::Move(action.into()),
+            ChordMatch::Pending => VisualStep::Pending,
+            ChordMatch::NoMatch => VisualStep::None,
+        }
+    }
+
+    /// Applies `step` over `lines` lines whose plain text (tabs expanded) `text` returns, and
+    /// scrolls `state` to keep the caret in view. Returns `None` if `step` did nothing.
+    ///
+    /// Visual mode starts at the previous caret if it is still on screen, else at the top-left
+    /// of the viewport.
+    pub fn apply(
+        &mut self,
+        step: VisualStep,
+        state: &mut ViewportState,
+        lines: usize,
+        mut text: impl FnMut(usize) -> String,
+    ) -> Option<SelectionAction> {
+        match step {
+            VisualStep::None => None,
+            VisualStep::Pending => Some(SelectionAction::Redraw),
+            VisualStep::Toggle(mode) => {
+                match self.mode {
+                    Some(current) if current == mode => self.mode = None,
+                    Some(_) => self.mode = Some(mode),
+                    None => {
+                        if lines == 0 {
+                            return None;
+                        }
+                        let top = state.y as usize;
+                        let on_screen = self.caret.0 >= top
+                            && self.caret.0 < top + state.viewport_h as usize
+                            && self.caret.0 < lines;
+                        let (line, col) = if on_screen {
+                            self.caret
+                        } else {
+                            (top.min(lines - 1), state.x)
+                        };
+                        let col = snap_col(&text(line), col);
+                        self.caret = (line, col);
+                        self.anchor = self.caret;
+                        self.goal_col = col;
+                        self.mode = Some(mode);
+                    }
+                }
+                Some(SelectionAction::Redraw)
+            }
+            VisualStep::Move(motion) => {
+                if self.mode.is_none() || lines == 0 {
+                    return None;
+                }
+                let page = state.viewport_h.saturating_sub(1).max(1) as usize;
+                self.move_caret(motion, page, lines, &mut text);
+                state.ensure_visible(self.caret.1, self.caret.0 as u32);
+                Some(SelectionAction::Redraw)
+            }
+        }
+    }
+
+    fn move_caret(
+        &mut self,
+        motion: CaretMotion,
+        page: usize,
+        lines: usize,
+        text: &mut impl FnMut(usize) -> String,
+    ) {
+        let last = lines - 1;
+        let line = self.caret.0.min(last);
+        let col = self.caret.1;
+        let vertical = |target: usize, goal: u32, text: &mut dyn FnMut(usize) -> String| {
+            (target, snap_col(&text(target), goal))
+        };
+        self.caret = match motion {
+            CaretMotion::Up => vertical(line.saturating_sub(1), self.goal_col, text),
+            CaretMotion::Down => vertical((line + 1).min(last), self.goal_col, text),
+            CaretMotion::PageUp => vertical(line.saturating_sub(page), self.goal_col, text),
+            CaretMotion::PageDown => vertical((line + page).min(last), self.goal_col, text),
+            CaretMotion::Top => vertical(0, self.goal_col, text),
+            CaretMotion::Bottom => vertical(last, self.goal_col, text),
+            CaretMotion::Left => (line, step_col(&text(line), col, false)),
+            CaretMotion::Right => (line, step_col(&text(line), col, true)),
+            CaretMotion::LineStart => (line, 0),
+            CaretMotion::LineEnd => (line, last_col(&text(line))),
+            CaretMotion::WordForward => word_forward(line, col, last, text),
+            CaretMotion::WordBack => word_back(line, col, text),
+        };
+        self.goal_col = match motion {
+            CaretMotion::Up
+            | CaretMotion::Down
+            | CaretMotion::PageUp
+            | CaretMotion::PageDown
+            | CaretMotion::Top
+            | CaretMotion::Bottom => self.goal_col,
+            CaretMotion::LineEnd => u32::MAX,
+            _ => self.caret.1,
+        };
+    }
+}
+
+/// The last column a caret can sit on in `text`: the first cell of its last grapheme.
+fn last_col(text: &str) -> u32 {
+    render::word_cells(text, TabPolicy::default())
+        .last()
+        .map_or(0, |&(first, _, _)| first)
+}
+
+/// Moves `col` onto the first cell of the grapheme covering it (or of the last grapheme), so
+/// the caret never sits on the continuation cell of a wide character.
+fn snap_col(text: &str, col: u32) -> u32 {
+    let cells = render::word_cells(text, TabPolicy::default());
+    cells
+        .iter()
+        .find(|c| c.1 >= col)
+        .or(cells.last())
+        .map_or(0, |c| c.0)
+}
+
+/// The first cell of the grapheme after (or before) the one at `col`, staying on the line.
+fn step_col(text: &str, col: u32, forward: bool) -> u32 {
+    let cells = render::word_cells(text, TabPolicy::default());
+    let Some(i) = cells.iter().position(|c| c.1 >= col) else {
+        return cells.last().map_or(0, |c| c.0);
+    };
+    let target = if forward {
+        (i + 1).min(cells.len() - 1)
+    } else {
+        i.saturating_sub(1)
+    };
+    cells[target].0
+}
+
+/// Vim's `w`: the start of the next word or punctuation run, continuing on the next line.
+fn word_forward(
+    line: usize,
+    col: u32,
+    last: usize,
+    text: &mut impl FnMut(usize) -> String,
+) -> (usize, u32) {
+    let cells = render::word_cells(&text(line), TabPolicy::default());
+    let mut i = cells.iter().position(|c| c.1 >= col).unwrap_or(cells.len());
+    if let Some(&(_, _, class)) = cells.get(i)
+        && class != WordClass::Space
+    {
+        while cells.get(i).is_some_and(|c| c.2 == class) {
+            i += 1;
+        }
+    }
+    while cells.get(i).is_some_and(|c| c.2 == WordClass::Space) {
+        i += 1;
+    }
+    if let Some(cell) = cells.get(i) {
+        return (line, cell.0);
+    }
+    if line == last {
+        return (line, last_col(&text(line)));
+    }
+    let next = render::word_cells(&text(line + 1), TabPolicy::default());
+    let start = next
+        .iter()
+        .find(|c| c.2 != WordClass::Space)
+        .map_or(0, |c| c.0);
+    (line + 1, start)
+}
+
+/// Vim's `b`: the start of the previous word or punctuation run, continuing on earlier lines.
+fn word_back(mut line: usize, col: u32, text: &mut impl FnMut(usize) -> String) -> (usize, u32) {
+    let mut cells = render::word_cells(&text(line), TabPolicy::default());
+    let mut i = cells.iter().position(|c| c.1 >= col).unwrap_or(cells.len());
+    loop {
+        if i == 0 {
+            if line == 0 {
+                return (0, 0);
+            }
+            line -= 1;
+            cells = render::word_cells(&text(line), TabPolicy::default());
+            if cells.is_empty() {
+                return (line, 0);
+            }
+            i = cells.len();
+        }
+        i -= 1;
+        if cells[i].2 != WordClass::Space {
+            break;
+        }
+    }
+    let class = cells[i].2;
+    while i > 0 && cells[i - 1].2 == class {
+        i -= 1;
+    }
+    (line, cells[i].0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scroll::ScrollBindings;
+
+    fn lines() -> Vec<String> {
+        ["fn main() {", "", "    let x = 1;", "}"]
+            .map(String::from)
+            .to_vec()
+    }
+
+    fn press(visual: &mut VisualSelection, state: &mut ViewportState, key: KeyEvent) {
+        press_in(&lines(), visual, state, key);
+    }
+
+    fn press_in(
+        lines: &[String],
+        visual: &mut VisualSelection,
+        state: &mut ViewportState,
+        key: KeyEvent,
+    ) {
+        let step = visual.step(
+            &key,
+            &SelectionBindings::default(),
+            &ScrollBindings::default().keymap(),
+            &mut ChordState::default(),
+            None,
+        );
+        visual.apply(step, state, lines.len(), |l| lines[l].clone());
+    }
+
+    fn viewport() -> ViewportState {
+        let mut state = ViewportState::default();
+        state.set_viewport(20, 2);
+        state.set_content(14, 4);
+        state
+    }
+
+    #[test]
+    fn word_motions_cross_lines() {
+        let mut visual = VisualSelection::new();
+        let mut state = viewport();
+        press(&mut visual, &mut state, keymap::key_char('v'));
+        let mut carets = Vec::new();
+        for _ in 0..5 {
+            press(&mut visual, &mut state, keymap::key_char('w'));
+            carets.push(visual.caret().unwrap());
+        }
+        assert_eq!(carets, [(0, 3), (0, 7), (0, 10), (1, 0), (2, 4)]);
+        assert_eq!(state.y, 1);
+
+        press(&mut visual, &mut state, keymap::key_char('b'));
+        assert_eq!(visual.caret(), Some((1, 0)));
+        press(&mut visual, &mut state, keymap::key_char('b'));
+        assert_eq!(visual.caret(), Some((0, 10)));
+        assert_eq!(state.y, 0);
+    }
+
+    #[test]
+    fn caret_steps_over_wide_graphemes() {
+        let lines = ["a界b", "界界"].map(String::from);
+        let mut visual = VisualSelection::new();
+        let mut state = viewport();
+        let mut press = |visual: &mut VisualSelection, c| {
+            press_in(&lines, visual, &mut state, keymap::key_char(c));
+            visual.caret().unwrap()
+        };
+        press(&mut visual, 'v');
+        let carets = ['l', 'l', 'l', 'h', 'j', '$', 'k'].map(|c| press(&mut visual, c));
+        assert_eq!(
+            carets,
+            [(0, 1), (0, 3), (0, 3), (0, 1), (1, 0), (1, 2), (0, 3)]
+        );
+    }
+
+    #[test]
+    fn vertical_motions_keep_the_goal_column() {
+        let mut visual = VisualSelection::new();
+        let mut state = viewport();
+        press(&mut visual, &mut state, keymap::key_char('v'));
+        press(&mut visual, &mut state, keymap::key_char('$'));
+        assert_eq!(visual.caret(), Some((0, 10)));
+        press(&mut visual, &mut state, keymap::key_char('j'));
+        assert_eq!(visual.caret(), Some((1, 0)));
+        press(&mut visual, &mut state, keymap::key_char('j'));
+        assert_eq!(visual.caret(), Some((2, 13)));
+        press(&mut visual, &mut state, KeyEvent::new(KeyCode::Up));
+        press(&mut visual, &mut state, KeyEvent::new(KeyCode::Up));
+        assert_eq!(visual.caret(), Some((0, 10)));
+        assert_eq!(visual.selection(), Some(((0, 0), (0, 10))));
+    }
+
+    #[test]
+    fn modes_switch_and_toggle_off() {
+        let mut visual = VisualSelection::new();
+        let mut state = viewport();
+        press(&mut visual, &mut state, keymap::key_char('V'));
+        press(&mut visual, &mut state, keymap::key_char('j'));
+        press(&mut visual, &mut state, keymap::key_char('l'));
+        assert_eq!(visual.selection(), Some(((0, 0), (1, u32::MAX))));
+
+        press(&mut visual, &mut state, keymap::key_ctrl('v'));
+        press(&mut visual, &mut state, keymap::key_char('j'));
+        press(&mut visual, &mut state, keymap::key_char('w'));
+        assert!(visual.is_block());
+        let sel = visual.selection().unwrap();
+        assert_eq!(sel, ((0, 0), (2, 4)));
+        assert_eq!(line_cols(sel, true, 1), Some((0, 5)));
+        assert_eq!(line_cols(sel, false, 1), Some((0, u32::MAX)));
+        assert_eq!(line_cols(sel, true, 3), None);
+
+        press(&mut visual, &mut state, keymap::key_ctrl('v'));
+        assert!(!visual.is_active());
+        assert_eq!(visual.selection(), None);
+    }
+
+    #[test]
+    fn motions_fall_through_outside_visual_mode() {
+        let visual = VisualSelection::new();
+        let step = visual.step(
+            &keymap::key_char('j'),
+            &SelectionBindings::default(),
+            &ScrollBindings::default().keymap(),
+            &mut ChordState::default(),
+            None,
+        );
+        assert_eq!(step, VisualStep::None);
+    }
 }
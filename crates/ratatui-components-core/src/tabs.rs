@@ -0,0 +1,225 @@
+//! Tab expansion shared by every view, [`crate::wrapping`] and the [`crate::render`] helpers.
+//!
+//! By default a tab advances to the next multiple of [`TabPolicy::width`] (a tab stop), so
+//! tab-indented Makefiles and Go code line up the way editors show them. Views expand tabs with
+//! their configured policy, so selection, hit-testing and copy all agree on the columns.
+use std::borrow::Cow;
+
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::grapheme;
+
+pub const DEFAULT_TAB_WIDTH: u16 = 4;
+
+/// The usual visible tab marker for [`TabPolicy::with_glyph`].
+pub const TAB_GLYPH: char = '→';
+
+/// How tabs are turned into cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct TabPolicy {
+    /// Distance between tab stops, or the fixed tab width without `align_to_stop`. `0` is
+    /// treated as `1`.
+    pub width: u16,
+    /// Advance to the next tab stop instead of always inserting `width` cells.
+    pub align_to_stop: bool,
+    /// Drawn in the first cell of each tab at render time (e.g. [`TAB_GLYPH`]); must be one cell
+    /// wide. Expanded text never contains it.
+    pub glyph: Option<char>,
+}
+
+impl Default for TabPolicy {
+    fn default() -> Self {
+        Self {
+            width: DEFAULT_TAB_WIDTH,
+            align_to_stop: true,
+            glyph: None,
+        }
+    }
+}
+
+impl TabPolicy {
+    pub fn new(width: u16) -> Self {
+        Self {
+            width,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_align_to_stop(mut self, align_to_stop: bool) -> Self {
+        self.align_to_stop = align_to_stop;
+        self
+    }
+
+    pub fn with_glyph(mut self, glyph: Option<char>) -> Self {
+        self.glyph = glyph;
+        self
+    }
+
+    /// Number of cells a tab starting at display column `col` occupies.
+    pub fn advance(&self, col: usize) -> usize {
+        let width = self.width.max(1) as usize;
+        if self.align_to_stop {
+            width - col % width
+        } else {
+            width
+        }
+    }
+
+    /// Display width of `grapheme` at column `col`: tabs use [`Self::advance`].
+    pub fn width_at(&self, grapheme: &str, col: usize) -> usize {
+        if grapheme == "\t" {
+            self.advance(col)
+        } else {
+            grapheme::width(grapheme)
+        }
+    }
+
+    /// Display width of `s` starting at column 0, with tabs expanded.
+    pub fn str_width(&self, s: &str) -> usize {
+        grapheme::graphemes(s).fold(0, |col, g| col + self.width_at(g, col))
+    }
+
+    /// Appends the blank cells of a tab at column `col` to `out`; returns how many were added.
+    pub fn push_tab(&self, out: &mut String, col: usize) -> usize {
+        let n = self.advance(col);
+        out.extend(std::iter::repeat_n(' ', n));
+        n
+    }
+
+    /// Expands the tabs of a line that starts at column 0.
+    ///
+    /// Tabs become spaces; [`Self::glyph`] is never written into the text, so copied text,
+    /// highlighter input and diffs stay free of it. Views draw the glyph at render time (see
+    /// [`Self::tab_cols`] and [`crate::render::render_tab_glyphs`]).
+    pub fn expand<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        if !s.contains('\t') {
+            return Cow::Borrowed(s);
+        }
+        let mut out = String::with_capacity(s.len() + 8);
+        self.expand_into(&mut out, s, 0);
+        Cow::Owned(out)
+    }
+
+    /// Expands the tabs of `s`, which starts at column `col`, onto `out`. Returns the end column.
+    pub fn expand_into(&self, out: &mut String, s: &str, col: usize) -> usize {
+        self.expand_into_with(out, s, col, None)
+    }
+
+    /// Expands tabs across the spans of `line`, keeping each span's style.
+    pub fn expand_line(&self, line: &mut Line<'_>) {
+        self.expand_line_with(line, None);
+    }
+
+    /// Like [`Self::expand_line`], but draws [`Self::glyph`] into the first cell of each tab.
+    ///
+    /// Only for text that goes straight to the screen (e.g. [`crate::code_render`] output).
+    pub fn expand_line_for_display(&self, line: &mut Line<'_>) {
+        self.expand_line_with(line, self.glyph);
+    }
+
+    /// Display columns where the tabs of a line (given as consecutive parts, e.g. spans) start.
+    pub fn tab_cols<'a>(&self, parts: impl IntoIterator<Item = &'a str>) -> Vec<u32> {
+        let mut cols = Vec::new();
+        let mut col = 0;
+        for part in parts {
+            for g in grapheme::graphemes(part) {
+                if g == "\t" {
+                    cols.push(col as u32);
+                }
+                col += self.width_at(g, col);
+            }
+        }
+        cols
+    }
+
+    fn expand_into_with(
+        &self,
+        out: &mut String,
+        s: &str,
+        mut col: usize,
+        glyph: Option<char>,
+    ) -> usize {
+        for g in grapheme::graphemes(s) {
+            if g == "\t" {
+                let n = self.advance(col);
+                match glyph {
+                    Some(glyph) => {
+                        out.push(glyph);
+                        out.extend(std::iter::repeat_n(' ', n - 1));
+                    }
+                    None => out.extend(std::iter::repeat_n(' ', n)),
+                }
+                col += n;
+            } else {
+                out.push_str(g);
+                col += grapheme::width(g);
+            }
+        }
+        col
+    }
+
+    fn expand_line_with(&self, line: &mut Line<'_>, glyph: Option<char>) {
+        if !line.spans.iter().any(|s| s.content.contains('\t')) {
+            return;
+        }
+        let mut col = 0;
+        for span in &mut line.spans {
+            if span.content.contains('\t') {
+                let mut out = String::new();
+                col = self.expand_into_with(&mut out, &span.content, col, glyph);
+                *span = Span::styled(out, span.style);
+            } else {
+                col += grapheme::graphemes(&span.content)
+                    .map(grapheme::width)
+                    .sum::<usize>();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Style;
+
+    #[test]
+    fn tabs_align_to_stops() {
+        let tabs = TabPolicy::default();
+        assert_eq!(tabs.expand("\tx"), "    x");
+        assert_eq!(tabs.expand("ab\tx"), "ab  x");
+        assert_eq!(tabs.expand("abcd\tx"), "abcd    x");
+        assert_eq!(tabs.expand("你\tx"), "你  x");
+        assert_eq!(TabPolicy::new(8).expand("all:\t$(CC)"), "all:    $(CC)");
+        assert_eq!(tabs.str_width("ab\tx"), 5);
+    }
+
+    #[test]
+    fn fixed_width_and_glyph() {
+        let fixed = TabPolicy::new(2).with_align_to_stop(false);
+        assert_eq!(fixed.expand("a\tb"), "a  b");
+        let visible = TabPolicy::default().with_glyph(Some(TAB_GLYPH));
+        assert_eq!(visible.expand("a\tb"), "a   b");
+        assert_eq!(visible.tab_cols(["a\tb", "\t"]), vec![1, 5]);
+        let mut line = Line::from("a\tb");
+        visible.expand_line_for_display(&mut line);
+        assert_eq!(line.spans[0].content, "a→  b");
+        assert_eq!(TabPolicy::new(0).expand("\t"), " ");
+    }
+
+    #[test]
+    fn expand_line_tracks_columns_across_spans() {
+        let mut line = Line::from(vec![
+            Span::raw("ab"),
+            Span::styled("\tc", Style::new().bold()),
+        ]);
+        TabPolicy::default().expand_line(&mut line);
+        assert_eq!(line.spans[1].content, "  c");
+        assert_eq!(line.spans[1].style, Style::new().bold());
+    }
+}
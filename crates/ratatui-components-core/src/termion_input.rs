@@ -0,0 +1,163 @@
+use crate::input::InputEvent;
+use crate::input::KeyCode;
+use crate::input::KeyEvent;
+use crate::input::KeyModifiers;
+use crate::input::MouseButton;
+use crate::input::MouseEvent;
+use crate::input::MouseEventKind;
+
+/// Converts a termion event.
+///
+/// termion does not report paste, resize or focus events, and only reports presses (no key
+/// repeat/release).
+pub fn input_event_from_termion(ev: termion::event::Event) -> Option<InputEvent> {
+    match ev {
+        termion::event::Event::Key(key) => Some(InputEvent::Key(key_event_from_termion(key)?)),
+        termion::event::Event::Mouse(m) => Some(InputEvent::Mouse(mouse_event_from_termion(m)?)),
+        termion::event::Event::Unsupported(_) => None,
+    }
+}
+
+pub fn key_event_from_termion(key: termion::event::Key) -> Option<KeyEvent> {
+    let shift = KeyModifiers {
+        shift: true,
+        ..KeyModifiers::none()
+    };
+    let ctrl = KeyModifiers {
+        ctrl: true,
+        ..KeyModifiers::none()
+    };
+    let alt = KeyModifiers {
+        alt: true,
+        ..KeyModifiers::none()
+    };
+    let none = KeyModifiers::none();
+
+    let (code, modifiers) = match key {
+        termion::event::Key::Backspace => (KeyCode::Backspace, none),
+        termion::event::Key::Left => (KeyCode::Left, none),
+        termion::event::Key::ShiftLeft => (KeyCode::Left, shift),
+        termion::event::Key::AltLeft => (KeyCode::Left, alt),
+        termion::event::Key::CtrlLeft => (KeyCode::Left, ctrl),
+        termion::event::Key::Right => (KeyCode::Right, none),
+        termion::event::Key::ShiftRight => (KeyCode::Right, shift),
+        termion::event::Key::AltRight => (KeyCode::Right, alt),
+        termion::event::Key::CtrlRight => (KeyCode::Right, ctrl),
+        termion::event::Key::Up => (KeyCode::Up, none),
+        termion::event::Key::ShiftUp => (KeyCode::Up, shift),
+        termion::event::Key::AltUp => (KeyCode::Up, alt),
+        termion::event::Key::CtrlUp => (KeyCode::Up, ctrl),
+        termion::event::Key::Down => (KeyCode::Down, none),
+        termion::event::Key::ShiftDown => (KeyCode::Down, shift),
+        termion::event::Key::AltDown => (KeyCode::Down, alt),
+        termion::event::Key::CtrlDown => (KeyCode::Down, ctrl),
+        termion::event::Key::Home => (KeyCode::Home, none),
+        termion::event::Key::CtrlHome => (KeyCode::Home, ctrl),
+        termion::event::Key::End => (KeyCode::End, none),
+        termion::event::Key::CtrlEnd => (KeyCode::End, ctrl),
+        termion::event::Key::PageUp => (KeyCode::PageUp, none),
+        termion::event::Key::PageDown => (KeyCode::PageDown, none),
+        termion::event::Key::BackTab => (KeyCode::BackTab, none),
+        termion::event::Key::Delete => (KeyCode::Delete, none),
+        termion::event::Key::Insert => (KeyCode::Insert, none),
+        termion::event::Key::F(n) => (KeyCode::F(n), none),
+        // termion reports Enter and Tab as plain characters.
+        termion::event::Key::Char('\n') => (KeyCode::Enter, none),
+        termion::event::Key::Char('\t') => (KeyCode::Tab, none),
+        termion::event::Key::Char(c) => (KeyCode::Char(c), none),
+        termion::event::Key::Alt(c) => (KeyCode::Char(c), alt),
+        termion::event::Key::Ctrl(c) => (KeyCode::Char(c), ctrl),
+        termion::event::Key::Esc => (KeyCode::Esc, none),
+        _ => return None,
+    };
+
+    Some(KeyEvent::new(code).with_modifiers(modifiers))
+}
+
+/// Converts a termion mouse event.
+///
+/// termion coordinates are 1-based and are converted to 0-based cells. termion does not report
+/// which button was released or dragged, so `Hold`/`Release` are mapped to the left button,
+/// which is what selection-capable widgets react to.
+pub fn mouse_event_from_termion(m: termion::event::MouseEvent) -> Option<MouseEvent> {
+    let (kind, x, y) = match m {
+        termion::event::MouseEvent::Press(b, x, y) => {
+            let kind = match b {
+                termion::event::MouseButton::Left => MouseEventKind::Down(MouseButton::Left),
+                termion::event::MouseButton::Right => MouseEventKind::Down(MouseButton::Right),
+                termion::event::MouseButton::Middle => MouseEventKind::Down(MouseButton::Middle),
+                termion::event::MouseButton::WheelUp => MouseEventKind::ScrollUp,
+                termion::event::MouseButton::WheelDown => MouseEventKind::ScrollDown,
+                termion::event::MouseButton::WheelLeft => MouseEventKind::ScrollLeft,
+                termion::event::MouseButton::WheelRight => MouseEventKind::ScrollRight,
+            };
+            (kind, x, y)
+        }
+        termion::event::MouseEvent::Hold(x, y) => (MouseEventKind::Drag(MouseButton::Left), x, y),
+        termion::event::MouseEvent::Release(x, y) => (MouseEventKind::Up(MouseButton::Left), x, y),
+    };
+
+    Some(MouseEvent {
+        x: x.saturating_sub(1),
+        y: y.saturating_sub(1),
+        kind,
+        modifiers: KeyModifiers::none(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keymap;
+
+    #[test]
+    fn maps_keys_and_modifiers() {
+        let cases = [
+            (termion::event::Key::Char('q'), keymap::key_char('q')),
+            (termion::event::Key::Ctrl('d'), keymap::key_ctrl('d')),
+            (
+                termion::event::Key::Char('\n'),
+                KeyEvent::new(KeyCode::Enter),
+            ),
+            (termion::event::Key::F(5), KeyEvent::new(KeyCode::F(5))),
+            (
+                termion::event::Key::BackTab,
+                KeyEvent::new(KeyCode::BackTab),
+            ),
+            (
+                termion::event::Key::ShiftUp,
+                keymap::parse_key("shift+up").unwrap(),
+            ),
+        ];
+        for (key, expected) in cases {
+            assert_eq!(
+                input_event_from_termion(termion::event::Event::Key(key)),
+                Some(InputEvent::Key(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn maps_mouse_to_zero_based_cells() {
+        let ev = termion::event::Event::Mouse(termion::event::MouseEvent::Press(
+            termion::event::MouseButton::Left,
+            1,
+            3,
+        ));
+        let Some(InputEvent::Mouse(m)) = input_event_from_termion(ev) else {
+            panic!("expected a mouse event");
+        };
+        assert_eq!((m.x, m.y), (0, 2));
+        assert_eq!(m.kind, MouseEventKind::Down(MouseButton::Left));
+
+        let m = mouse_event_from_termion(termion::event::MouseEvent::Release(5, 5)).unwrap();
+        assert_eq!(m.kind, MouseEventKind::Up(MouseButton::Left));
+        let m = mouse_event_from_termion(termion::event::MouseEvent::Press(
+            termion::event::MouseButton::WheelDown,
+            1,
+            1,
+        ))
+        .unwrap();
+        assert_eq!(m.kind, MouseEventKind::ScrollDown);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::input::InputEvent;
+use crate::input::KeyCode;
+use crate::input::KeyEvent;
+use crate::input::KeyModifiers;
+use crate::input::MediaKeyCode;
+use crate::input::MouseButton;
+use crate::input::MouseEvent;
+use crate::input::MouseEventKind;
+
+/// Offset of the first cell in termwiz mouse coordinates.
+///
+/// On Unix termwiz decodes SGR mouse reports, which are 1-based; the Windows console input
+/// records it reads instead are already 0-based.
+#[cfg(not(windows))]
+const MOUSE_ORIGIN: u16 = 1;
+#[cfg(windows)]
+const MOUSE_ORIGIN: u16 = 0;
+
+/// Converts termwiz input events.
+///
+/// termwiz reports mouse input as the set of buttons currently held rather than as
+/// press/drag/release transitions, so conversion needs to remember the previously held button.
+/// Keep one `TermwizInput` per terminal and feed it every event.
+#[derive(Clone, Debug, Default)]
+pub struct TermwizInput {
+    pressed: Option<MouseButton>,
+}
+
+impl TermwizInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn input_event(&mut self, ev: termwiz::input::InputEvent) -> Option<InputEvent> {
+        match ev {
+            termwiz::input::InputEvent::Key(key) => {
+                Some(InputEvent::Key(key_event_from_termwiz(key)?))
+            }
+            termwiz::input::InputEvent::Mouse(m) => Some(InputEvent::Mouse(self.mouse_event(m)?)),
+            termwiz::input::InputEvent::Resized { cols, rows } => Some(InputEvent::Resize {
+                width: cols.min(u16::MAX as usize) as u16,
+                height: rows.min(u16::MAX as usize) as u16,
+            }),
+            termwiz::input::InputEvent::Paste(s) => Some(InputEvent::Paste(s)),
+            termwiz::input::InputEvent::PixelMouse(_) | termwiz::input::InputEvent::Wake => None,
+        }
+    }
+
+    /// Converts a mouse event, synthesizing down/drag/up from the held-button state.
+    ///
+    /// termwiz coordinates are converted to 0-based cells: SGR mouse reports on Unix are
+    /// 1-based, Windows console events already start at 0.
+    pub fn mouse_event(&mut self, m: termwiz::input::MouseEvent) -> Option<MouseEvent> {
+        let buttons = &m.mouse_buttons;
+        let kind = if buttons.contains(termwiz::input::MouseButtons::VERT_WHEEL) {
+            if buttons.contains(termwiz::input::MouseButtons::WHEEL_POSITIVE) {
+                MouseEventKind::ScrollUp
+            } else {
+                MouseEventKind::ScrollDown
+            }
+        } else if buttons.contains(termwiz::input::MouseButtons::HORZ_WHEEL) {
+            if buttons.contains(termwiz::input::MouseButtons::WHEEL_POSITIVE) {
+                MouseEventKind::ScrollLeft
+            } else {
+                MouseEventKind::ScrollRight
+            }
+        } else {
+            let held = if buttons.contains(termwiz::input::MouseButtons::LEFT) {
+                Some(MouseButton::Left)
+            } else if buttons.contains(termwiz::input::MouseButtons::RIGHT) {
+                Some(MouseButton::Right)
+            } else if buttons.contains(termwiz::input::MouseButtons::MIDDLE) {
+                Some(MouseButton::Middle)
+            } else {
+                None
+            };
+            match (self.pressed, held) {
+                (None, Some(b)) => {
+                    self.pressed = Some(b);
+                    MouseEventKind::Down(b)
+                }
+                (Some(p), Some(_)) => MouseEventKind::Drag(p),
+                (Some(p), None) => {
+                    self.pressed = None;
+                    MouseEventKind::Up(p)
+                }
+                (None, None) => MouseEventKind::Moved,
+            }
+        };
+
+        Some(MouseEvent {
+            x: m.x.saturating_sub(MOUSE_ORIGIN),
+            y: m.y.saturating_sub(MOUSE_ORIGIN),
+            kind,
+            modifiers: modifiers_from_termwiz(m.modifiers),
+        })
+    }
+}
+
+pub fn key_event_from_termwiz(key: termwiz::input::KeyEvent) -> Option<KeyEvent> {
+    let mut modifiers = modifiers_from_termwiz(key.modifiers);
+    let code = match key.key {
+        termwiz::input::KeyCode::Char(c) => KeyCode::Char(c),
+        termwiz::input::KeyCode::Enter => KeyCode::Enter,
+        termwiz::input::KeyCode::Backspace => KeyCode::Backspace,
+        termwiz::input::KeyCode::Delete => KeyCode::Delete,
+        termwiz::input::KeyCode::Tab if modifiers.shift => {
+            modifiers.shift = false;
+            KeyCode::BackTab
+        }
+        termwiz::input::KeyCode::Tab => KeyCode::Tab,
+        termwiz::input::KeyCode::Escape => KeyCode::Esc,
+        termwiz::input::KeyCode::LeftArrow | termwiz::input::KeyCode::ApplicationLeftArrow => {
+            KeyCode::Left
+        }
+        termwiz::input::KeyCode::RightArrow | termwiz::input::KeyCode::ApplicationRightArrow => {
+            KeyCode::Right
+        }
+        termwiz::input::KeyCode::UpArrow | termwiz::input::KeyCode::ApplicationUpArrow => {
+            KeyCode::Up
+        }
+        termwiz::input::KeyCode::DownArrow | termwiz::input::KeyCode::ApplicationDownArrow => {
+            KeyCode::Down
+        }
+        termwiz::input::KeyCode::Home | termwiz::input::KeyCode::KeyPadHome => KeyCode::Home,
+        termwiz::input::KeyCode::End | termwiz::input::KeyCode::KeyPadEnd => KeyCode::End,
+        termwiz::input::KeyCode::PageUp | termwiz::input::KeyCode::KeyPadPageUp => KeyCode::PageUp,
+        termwiz::input::KeyCode::PageDown | termwiz::input::KeyCode::KeyPadPageDown => {
+            KeyCode::PageDown
+        }
+        termwiz::input::KeyCode::Insert => KeyCode::Insert,
+        termwiz::input::KeyCode::Function(n) => KeyCode::F(n),
+        termwiz::input::KeyCode::Menu | termwiz::input::KeyCode::Applications => KeyCode::Menu,
+        termwiz::input::KeyCode::MediaPlayPause => KeyCode::Media(MediaKeyCode::PlayPause),
+        termwiz::input::KeyCode::MediaStop => KeyCode::Media(MediaKeyCode::Stop),
+        termwiz::input::KeyCode::MediaNextTrack => KeyCode::Media(MediaKeyCode::TrackNext),
+        termwiz::input::KeyCode::MediaPrevTrack => KeyCode::Media(MediaKeyCode::TrackPrevious),
+        termwiz::input::KeyCode::VolumeDown => KeyCode::Media(MediaKeyCode::LowerVolume),
+        termwiz::input::KeyCode::VolumeUp => KeyCode::Media(MediaKeyCode::RaiseVolume),
+        termwiz::input::KeyCode::VolumeMute => KeyCode::Media(MediaKeyCode::MuteVolume),
+        _ => return None,
+    };
+
+    Some(KeyEvent::new(code).with_modifiers(modifiers))
+}
+
+fn modifiers_from_termwiz(m: termwiz::input::Modifiers) -> KeyModifiers {
+    use termwiz::input::Modifiers as M;
+    KeyModifiers {
+        shift: m.intersects(M::SHIFT | M::LEFT_SHIFT | M::RIGHT_SHIFT),
+        ctrl: m.intersects(M::CTRL | M::LEFT_CTRL | M::RIGHT_CTRL),
+        alt: m.intersects(M::ALT | M::LEFT_ALT | M::RIGHT_ALT),
+        super_: m.contains(M::SUPER),
+        ..KeyModifiers::none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keymap;
+
+    fn tw_key(key: termwiz::input::KeyCode, modifiers: termwiz::input::Modifiers) -> InputEvent {
+        TermwizInput::new()
+            .input_event(termwiz::input::InputEvent::Key(termwiz::input::KeyEvent {
+                key,
+                modifiers,
+            }))
+            .expect("key event")
+    }
+
+    #[test]
+    fn maps_keys_and_modifiers() {
+        use termwiz::input::KeyCode as K;
+        use termwiz::input::Modifiers as M;
+        assert_eq!(
+            tw_key(K::Char('d'), M::CTRL),
+            InputEvent::Key(keymap::key_ctrl('d'))
+        );
+        assert_eq!(
+            tw_key(K::Function(12), M::SUPER),
+            InputEvent::Key(keymap::parse_key("super+f12").unwrap())
+        );
+        assert_eq!(
+            tw_key(K::Tab, M::SHIFT),
+            InputEvent::Key(KeyEvent::new(KeyCode::BackTab))
+        );
+        assert_eq!(
+            tw_key(K::UpArrow, M::LEFT_ALT),
+            InputEvent::Key(keymap::parse_key("alt+up").unwrap())
+        );
+    }
+
+    #[test]
+    fn synthesizes_mouse_transitions() {
+        use termwiz::input::MouseButtons as B;
+        let mut input = TermwizInput::new();
+        // Cells are given 0-based; the origin offset is covered by `mouse_origin_per_platform`.
+        let mut mouse = |x: u16, y: u16, mouse_buttons| {
+            let ev = termwiz::input::InputEvent::Mouse(termwiz::input::MouseEvent {
+                x: x + MOUSE_ORIGIN,
+                y: y + MOUSE_ORIGIN,
+                mouse_buttons,
+                modifiers: termwiz::input::Modifiers::NONE,
+            });
+            match input.input_event(ev) {
+                Some(InputEvent::Mouse(m)) => Some((m.x, m.y, m.kind)),
+                _ => None,
+            }
+        };
+        assert_eq!(
+            mouse(0, 0, B::LEFT),
+            Some((0, 0, MouseEventKind::Down(MouseButton::Left)))
+        );
+        assert_eq!(
+            mouse(3, 0, B::LEFT),
+            Some((3, 0, MouseEventKind::Drag(MouseButton::Left)))
+        );
+        assert_eq!(
+            mouse(3, 0, B::NONE),
+            Some((3, 0, MouseEventKind::Up(MouseButton::Left)))
+        );
+        assert_eq!(mouse(4, 0, B::NONE), Some((4, 0, MouseEventKind::Moved)));
+        assert_eq!(
+            mouse(1, 1, B::VERT_WHEEL | B::WHEEL_POSITIVE),
+            Some((1, 1, MouseEventKind::ScrollUp))
+        );
+        assert_eq!(
+            mouse(1, 1, B::HORZ_WHEEL),
+            Some((1, 1, MouseEventKind::ScrollRight))
+        );
+    }
+
+    #[test]
+    fn mouse_origin_per_platform() {
+        let mut input = TermwizInput::new();
+        let mut moved = |x, y| {
+            let m = input.mouse_event(termwiz::input::MouseEvent {
+                x,
+                y,
+                mouse_buttons: termwiz::input::MouseButtons::NONE,
+                modifiers: termwiz::input::Modifiers::NONE,
+            });
+            m.map(|m| (m.x, m.y))
+        };
+        // Unix: 1-based SGR reports.
+        #[cfg(not(windows))]
+        {
+            assert_eq!(moved(1, 1), Some((0, 0)));
+            assert_eq!(moved(10, 3), Some((9, 2)));
+        }
+        // Windows: console records are already 0-based.
+        #[cfg(windows)]
+        {
+            assert_eq!(moved(0, 0), Some((0, 0)));
+            assert_eq!(moved(10, 3), Some((10, 3)));
+        }
+    }
+
+    #[test]
+    fn maps_resize_and_paste() {
+        let mut input = TermwizInput::new();
+        assert_eq!(
+            input.input_event(termwiz::input::InputEvent::Resized { cols: 80, rows: 24 }),
+            Some(InputEvent::Resize {
+                width: 80,
+                height: 24
+            })
+        );
+        assert_eq!(
+            input.input_event(termwiz::input::InputEvent::Paste("hi".to_string())),
+            Some(InputEvent::Paste("hi".to_string()))
+        );
+    }
+}
@@ -1,13 +1,18 @@
+use crate::component::Component;
+use crate::component::ComponentAction;
+use crate::grapheme;
 use crate::input::InputEvent;
 use crate::input::KeyCode;
 use crate::input::KeyEvent;
 use crate::render;
+use crate::tabs::TabPolicy;
+use crate::theme::Theme;
 use crate::viewport::ViewportState;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Style;
-use unicode_width::UnicodeWidthChar;
-use unicode_width::UnicodeWidthStr;
+use std::time::Duration;
+use std::time::Instant;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum EnterBehavior {
@@ -28,6 +33,10 @@ pub struct TextAreaOptions {
     pub show_scrollbar: bool,
     pub style: Style,
     pub submit_rule: SubmitRule,
+    /// Blink interval for the cursor, driven by [`InputEvent::Tick`]. `None` disables blinking.
+    pub cursor_blink: Option<Duration>,
+    /// How tabs are displayed; the cursor moves over a tab as one unit.
+    pub tabs: TabPolicy,
 }
 
 impl Default for TextAreaOptions {
@@ -36,6 +45,8 @@ impl Default for TextAreaOptions {
             show_scrollbar: true,
             style: Style::default(),
             submit_rule: SubmitRule::default(),
+            cursor_blink: None,
+            tabs: TabPolicy::default(),
         }
     }
 }
@@ -43,7 +54,7 @@ impl Default for TextAreaOptions {
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct Cursor {
     pub row: usize,
-    pub col: usize, // char index within line
+    pub col: usize, // grapheme cluster index within line
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -53,6 +64,12 @@ pub enum TextAreaAction {
     Submitted(String),
 }
 
+impl ComponentAction for TextAreaAction {
+    fn is_consumed(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TextArea {
     lines: Vec<String>,
@@ -60,6 +77,9 @@ pub struct TextArea {
     preferred_x: Option<usize>, // display columns
     pub state: ViewportState,
     options: TextAreaOptions,
+    focused: bool,
+    cursor_visible: bool,
+    last_blink: Option<Instant>,
 }
 
 impl Default for TextArea {
@@ -76,6 +96,9 @@ impl TextArea {
             preferred_x: None,
             state: ViewportState::default(),
             options: TextAreaOptions::default(),
+            focused: true,
+            cursor_visible: true,
+            last_blink: None,
         }
     }
 
@@ -110,6 +133,16 @@ impl TextArea {
         self.cursor
     }
 
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Sets focus. The cursor is hidden (see [`Self::cursor_pos`]) while unfocused.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        self.reset_blink();
+    }
+
     pub fn set_viewport(&mut self, area: Rect) {
         let content_area = if self.options.show_scrollbar && area.width >= 2 {
             Rect::new(area.x, area.y, area.width - 1, area.height)
@@ -122,8 +155,10 @@ impl TextArea {
         self.ensure_cursor_visible();
     }
 
+    /// Returns the screen position of the cursor, or `None` if it is scrolled out of view,
+    /// the text area is unfocused, or the cursor is in the "off" phase of a blink.
     pub fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
-        if area.width == 0 || area.height == 0 {
+        if area.width == 0 || area.height == 0 || !self.focused || !self.cursor_visible {
             return None;
         }
         let content_area = if self.options.show_scrollbar && area.width >= 2 {
@@ -152,12 +187,73 @@ impl TextArea {
                 self.ensure_cursor_visible();
                 TextAreaAction::Changed
             }
-            InputEvent::Key(key) => self.handle_key(key),
+            InputEvent::Key(key) => {
+                if !key.is_release() {
+                    self.reset_blink();
+                }
+                self.handle_key(key)
+            }
             InputEvent::Mouse(_) => TextAreaAction::None,
+            // The viewport (and cursor visibility) is re-synced on the next render.
+            InputEvent::Resize { .. } => TextAreaAction::None,
+            InputEvent::FocusGained => {
+                self.set_focused(true);
+                TextAreaAction::None
+            }
+            InputEvent::FocusLost => {
+                self.set_focused(false);
+                TextAreaAction::None
+            }
+            InputEvent::Tick(now) => {
+                self.tick(now);
+                TextAreaAction::None
+            }
         }
     }
 
+    fn tick(&mut self, now: Instant) {
+        let Some(interval) = self.options.cursor_blink else {
+            return;
+        };
+        match self.last_blink {
+            Some(last) if now.saturating_duration_since(last) >= interval => {
+                self.cursor_visible = !self.cursor_visible;
+                self.last_blink = Some(now);
+            }
+            Some(_) => {}
+            None => self.last_blink = Some(now),
+        }
+    }
+
+    /// Shows the cursor and restarts the blink cycle (e.g. after typing).
+    fn reset_blink(&mut self) {
+        self.cursor_visible = true;
+        self.last_blink = None;
+    }
+
     pub fn render_ref(&mut self, area: Rect, buf: &mut Buffer) {
+        let style = self.options.style;
+        self.render_styled(area, buf, style, style);
+    }
+
+    /// Renders using `theme` for anything [`TextAreaOptions::style`] leaves unset.
+    pub fn render_with_theme(&mut self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        let style = if self.options.style == Style::default() {
+            theme.text_primary
+        } else {
+            self.options.style
+        };
+        self.render_styled(area, buf, style, theme.chrome.scrollbar);
+        theme.color_profile.apply_to_buffer(buf, area);
+    }
+
+    fn render_styled(
+        &mut self,
+        area: Rect,
+        buf: &mut Buffer,
+        style: Style,
+        scrollbar_style: Style,
+    ) {
         if area.width == 0 || area.height == 0 {
             return;
         }
@@ -175,19 +271,17 @@ impl TextArea {
         for row in 0..content_area.height {
             let y = content_area.y + row;
             let idx = (self.state.y as usize).saturating_add(row as usize);
-            buf.set_style(
-                Rect::new(content_area.x, y, content_area.width, 1),
-                self.options.style,
-            );
+            buf.set_style(Rect::new(content_area.x, y, content_area.width, 1), style);
             if let Some(line) = self.lines.get(idx) {
-                render::render_str_clipped(
+                render::render_str_clipped_with_tabs(
                     content_area.x,
                     y,
                     self.state.x,
                     content_area.width,
                     buf,
                     line,
-                    self.options.style,
+                    style,
+                    self.options.tabs,
                 );
             }
         }
@@ -197,15 +291,18 @@ impl TextArea {
                 Rect::new(sb_x, area.y, 1, area.height),
                 buf,
                 &self.state,
-                self.options.style,
+                scrollbar_style,
             );
         }
     }
 
     fn handle_key(&mut self, key: KeyEvent) -> TextAreaAction {
+        if key.is_release() {
+            return TextAreaAction::None;
+        }
         match key.code {
             KeyCode::Char(c) => {
-                if key.modifiers.ctrl || key.modifiers.alt {
+                if key.modifiers.has_command_modifier() {
                     return TextAreaAction::None;
                 }
                 self.insert_char(c);
@@ -275,7 +372,7 @@ impl TextArea {
                 TextAreaAction::None
             }
             KeyCode::End => {
-                self.cursor.col = self.current_line_char_len();
+                self.cursor.col = self.current_line_len();
                 self.preferred_x = Some(self.cursor_display_x());
                 self.ensure_cursor_visible();
                 TextAreaAction::None
@@ -288,7 +385,13 @@ impl TextArea {
                 self.state.page_up();
                 TextAreaAction::None
             }
-            KeyCode::Tab | KeyCode::Esc => TextAreaAction::None,
+            KeyCode::Tab
+            | KeyCode::BackTab
+            | KeyCode::Esc
+            | KeyCode::Insert
+            | KeyCode::Menu
+            | KeyCode::F(_)
+            | KeyCode::Media(_) => TextAreaAction::None,
         }
     }
 
@@ -317,7 +420,7 @@ impl TextArea {
         let content_w = self
             .lines
             .iter()
-            .map(|l| UnicodeWidthStr::width(l.as_str()) as u32)
+            .map(|l| self.options.tabs.str_width(l) as u32)
             .max()
             .unwrap_or(0);
         self.state.set_content(content_w, content_h);
@@ -349,11 +452,8 @@ impl TextArea {
     fn cursor_display_x(&self) -> usize {
         let line = self.current_line();
         let mut cols = 0usize;
-        for (i, ch) in line.chars().enumerate() {
-            if i >= self.cursor.col {
-                break;
-            }
-            cols += UnicodeWidthChar::width(ch).unwrap_or(0);
+        for g in grapheme::graphemes(line).take(self.cursor.col) {
+            cols += self.options.tabs.width_at(g, cols);
         }
         cols
     }
@@ -365,8 +465,8 @@ impl TextArea {
             .unwrap_or("")
     }
 
-    fn current_line_char_len(&self) -> usize {
-        self.current_line().chars().count()
+    fn current_line_len(&self) -> usize {
+        grapheme::count(self.current_line())
     }
 
     fn move_left(&mut self) {
@@ -374,13 +474,13 @@ impl TextArea {
             self.cursor.col -= 1;
         } else if self.cursor.row > 0 {
             self.cursor.row -= 1;
-            self.cursor.col = self.current_line_char_len();
+            self.cursor.col = self.current_line_len();
         }
         self.preferred_x = Some(self.cursor_display_x());
     }
 
     fn move_right(&mut self) {
-        let len = self.current_line_char_len();
+        let len = self.current_line_len();
         if self.cursor.col < len {
             self.cursor.col += 1;
         } else if self.cursor.row + 1 < self.lines.len() {
@@ -396,7 +496,7 @@ impl TextArea {
         }
         let target_x = self.preferred_x.unwrap_or_else(|| self.cursor_display_x());
         self.cursor.row -= 1;
-        self.cursor.col = col_from_display_x(self.current_line(), target_x);
+        self.cursor.col = col_from_display_x(self.current_line(), target_x, self.options.tabs);
         self.preferred_x = Some(target_x);
     }
 
@@ -406,23 +506,24 @@ impl TextArea {
         }
         let target_x = self.preferred_x.unwrap_or_else(|| self.cursor_display_x());
         self.cursor.row += 1;
-        self.cursor.col = col_from_display_x(self.current_line(), target_x);
+        self.cursor.col = col_from_display_x(self.current_line(), target_x, self.options.tabs);
         self.preferred_x = Some(target_x);
     }
 
     fn insert_char(&mut self, ch: char) {
         let row = self.cursor.row.min(self.lines.len() - 1);
         let line = &mut self.lines[row];
-        let byte_idx = byte_index_from_char_index(line, self.cursor.col);
+        let byte_idx = grapheme::byte_index(line, self.cursor.col);
         line.insert(byte_idx, ch);
+        // A combining mark or ZWJ joins the preceding cluster instead of starting a new one.
         self.cursor.row = row;
-        self.cursor.col += 1;
+        self.cursor.col = grapheme::count(&line[..byte_idx + ch.len_utf8()]);
     }
 
     fn insert_newline(&mut self) {
         let row = self.cursor.row.min(self.lines.len() - 1);
         let line = &mut self.lines[row];
-        let byte_idx = byte_index_from_char_index(line, self.cursor.col);
+        let byte_idx = grapheme::byte_index(line, self.cursor.col);
         let tail = line[byte_idx..].to_string();
         line.truncate(byte_idx);
         self.lines.insert(row + 1, tail);
@@ -444,7 +545,7 @@ impl TextArea {
         }
 
         let row = self.cursor.row.min(self.lines.len() - 1);
-        let byte_idx = byte_index_from_char_index(&self.lines[row], self.cursor.col);
+        let byte_idx = grapheme::byte_index(&self.lines[row], self.cursor.col);
         let tail = self.lines[row][byte_idx..].to_string();
         self.lines[row].truncate(byte_idx);
         self.lines[row].push_str(parts[0]);
@@ -459,7 +560,7 @@ impl TextArea {
         self.lines.insert(insert_at, format!("{last}{tail}"));
 
         self.cursor.row = insert_at;
-        self.cursor.col = last.chars().count();
+        self.cursor.col = grapheme::count(last);
     }
 
     fn backspace(&mut self) -> bool {
@@ -469,8 +570,8 @@ impl TextArea {
         if self.cursor.col > 0 {
             let row = self.cursor.row.min(self.lines.len() - 1);
             let line = &mut self.lines[row];
-            let start = byte_index_from_char_index(line, self.cursor.col - 1);
-            let end = byte_index_from_char_index(line, self.cursor.col);
+            let start = grapheme::byte_index(line, self.cursor.col - 1);
+            let end = grapheme::byte_index(line, self.cursor.col);
             line.replace_range(start..end, "");
             self.cursor.row = row;
             self.cursor.col -= 1;
@@ -481,7 +582,7 @@ impl TextArea {
             let cur = self.lines.remove(row);
             self.cursor.row -= 1;
             let prev = &mut self.lines[self.cursor.row];
-            let prev_len = prev.chars().count();
+            let prev_len = grapheme::count(prev);
             prev.push_str(&cur);
             self.cursor.col = prev_len;
             if self.lines.is_empty() {
@@ -498,11 +599,11 @@ impl TextArea {
             return false;
         }
         let row = self.cursor.row.min(self.lines.len() - 1);
-        let line_len = self.lines[row].chars().count();
+        let line_len = grapheme::count(&self.lines[row]);
         if self.cursor.col < line_len {
             let line = &mut self.lines[row];
-            let start = byte_index_from_char_index(line, self.cursor.col);
-            let end = byte_index_from_char_index(line, self.cursor.col + 1);
+            let start = grapheme::byte_index(line, self.cursor.col);
+            let end = grapheme::byte_index(line, self.cursor.col + 1);
             line.replace_range(start..end, "");
             return true;
         }
@@ -515,11 +616,32 @@ impl TextArea {
     }
 }
 
-fn col_from_display_x(line: &str, target_x: usize) -> usize {
+impl Component for TextArea {
+    type Action = TextAreaAction;
+
+    fn handle_event(&mut self, area: Rect, event: InputEvent) -> TextAreaAction {
+        self.set_viewport(area);
+        self.input(event)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        self.render_with_theme(area, buf, theme);
+    }
+
+    fn cursor_position(&self, area: Rect) -> Option<(u16, u16)> {
+        self.cursor_pos(area)
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        TextArea::set_focused(self, focused);
+    }
+}
+
+fn col_from_display_x(line: &str, target_x: usize, tabs: TabPolicy) -> usize {
     let mut cols = 0usize;
     let mut col = 0usize;
-    for ch in line.chars() {
-        let w = UnicodeWidthChar::width(ch).unwrap_or(0);
+    for g in grapheme::graphemes(line) {
+        let w = tabs.width_at(g, cols);
         if cols + w > target_x {
             break;
         }
@@ -529,16 +651,6 @@ fn col_from_display_x(line: &str, target_x: usize) -> usize {
     col
 }
 
-fn byte_index_from_char_index(s: &str, char_idx: usize) -> usize {
-    if char_idx == 0 {
-        return 0;
-    }
-    match s.char_indices().nth(char_idx) {
-        Some((i, _)) => i,
-        None => s.len(),
-    }
-}
-
 fn normalize_newlines(s: &str) -> String {
     s.replace("\r\n", "\n").replace('\r', "\n")
 }
@@ -568,6 +680,29 @@ mod tests {
     use crate::input::KeyEvent;
     use crate::input::KeyModifiers;
 
+    #[test]
+    fn cursor_hidden_when_unfocused_and_blinks_on_tick() {
+        let area = Rect::new(0, 0, 10, 3);
+        let mut ta = TextArea::with_options(TextAreaOptions {
+            cursor_blink: Some(Duration::from_millis(500)),
+            ..Default::default()
+        });
+        ta.set_viewport(area);
+        assert_eq!(ta.cursor_pos(area), Some((0, 0)));
+
+        ta.input(InputEvent::FocusLost);
+        assert_eq!(ta.cursor_pos(area), None);
+        ta.input(InputEvent::FocusGained);
+        assert_eq!(ta.cursor_pos(area), Some((0, 0)));
+
+        let t0 = Instant::now();
+        ta.input(InputEvent::Tick(t0));
+        ta.input(InputEvent::Tick(t0 + Duration::from_millis(600)));
+        assert_eq!(ta.cursor_pos(area), None);
+        ta.input(InputEvent::Key(KeyEvent::new(KeyCode::Char('a'))));
+        assert_eq!(ta.cursor_pos(area), Some((1, 0)));
+    }
+
     #[test]
     fn inserts_and_moves_cursor() {
         let mut ta = TextArea::new();
@@ -583,6 +718,34 @@ mod tests {
         assert_eq!(ta.text(), "ba");
     }
 
+    #[test]
+    fn modified_chars_are_not_inserted() {
+        let mut ta = TextArea::new();
+        for modifiers in [
+            KeyModifiers {
+                super_: true,
+                ..KeyModifiers::none()
+            },
+            KeyModifiers {
+                hyper: true,
+                ..KeyModifiers::none()
+            },
+            KeyModifiers {
+                meta: true,
+                ..KeyModifiers::none()
+            },
+        ] {
+            let key = KeyEvent::new(KeyCode::Char('v')).with_modifiers(modifiers);
+            assert_eq!(ta.input(InputEvent::Key(key)), TextAreaAction::None);
+        }
+        let shifted = KeyEvent::new(KeyCode::Char('V')).with_modifiers(KeyModifiers {
+            shift: true,
+            ..KeyModifiers::none()
+        });
+        assert_eq!(ta.input(InputEvent::Key(shifted)), TextAreaAction::Changed);
+        assert_eq!(ta.text(), "V");
+    }
+
     #[test]
     fn enter_submits_by_default() {
         let mut ta = TextArea::new();
@@ -598,8 +761,7 @@ mod tests {
         ta.input(InputEvent::Key(KeyEvent::new(KeyCode::Char('x'))));
         let key = KeyEvent::new(KeyCode::Enter).with_modifiers(KeyModifiers {
             shift: true,
-            ctrl: false,
-            alt: false,
+            ..KeyModifiers::none()
         });
         let act = ta.input(InputEvent::Key(key));
         assert_eq!(act, TextAreaAction::Changed);
@@ -626,4 +788,63 @@ mod tests {
         assert_eq!(ta.text(), "a\nb\nc");
         assert_eq!(ta.cursor.row, 2);
     }
+
+    #[test]
+    fn cursor_moves_over_whole_grapheme_clusters() {
+        let key = |code| InputEvent::Key(KeyEvent::new(code));
+        let mut ta = TextArea::new();
+        ta.set_text("e\u{301}👨\u{200d}👩\u{200d}👧🇯🇵x");
+        ta.set_viewport(Rect::new(0, 0, 20, 1));
+
+        let mut xs = Vec::new();
+        for _ in 0..5 {
+            xs.push(ta.cursor_display_x());
+            ta.input(key(KeyCode::Right));
+        }
+        assert_eq!(xs, vec![0, 1, 3, 5, 6]);
+        assert_eq!(ta.cursor().col, 4);
+
+        ta.input(key(KeyCode::Backspace));
+        ta.input(key(KeyCode::Backspace));
+        assert_eq!(ta.text(), "e\u{301}👨\u{200d}👩\u{200d}👧");
+
+        ta.input(key(KeyCode::Home));
+        ta.input(key(KeyCode::Delete));
+        assert_eq!(ta.text(), "👨\u{200d}👩\u{200d}👧");
+
+        // A combining mark typed after a letter extends that letter's cluster.
+        ta.input(key(KeyCode::Char('a')));
+        ta.input(key(KeyCode::Char('\u{308}')));
+        assert_eq!(ta.cursor().col, 1);
+        assert_eq!(ta.cursor_display_x(), 1);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn editing_keeps_the_cursor_on_cluster_boundaries(
+            text in crate::grapheme::fixtures::text(),
+            moves in proptest::collection::vec(0u8..6, 0..32),
+        ) {
+            let mut ta = TextArea::new();
+            ta.set_text(text);
+            for m in moves {
+                let code = match m {
+                    0 => KeyCode::Left,
+                    1 | 2 => KeyCode::Right,
+                    3 => KeyCode::Backspace,
+                    4 => KeyCode::Delete,
+                    _ => KeyCode::End,
+                };
+                ta.input(InputEvent::Key(KeyEvent::new(code)));
+
+                let line = ta.current_line();
+                proptest::prop_assert!(ta.cursor().col <= grapheme::count(line));
+                for g in grapheme::graphemes(line) {
+                    proptest::prop_assert!(crate::grapheme::fixtures::CLUSTERS.contains(&g));
+                }
+                let before: String = grapheme::graphemes(line).take(ta.cursor().col).collect();
+                proptest::prop_assert_eq!(ta.cursor_display_x(), TabPolicy::default().str_width(&before));
+            }
+        }
+    }
 }
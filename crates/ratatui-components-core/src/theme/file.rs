@@ -0,0 +1,619 @@
+//! Theme files (feature: `serde`).
+//!
+//! [`ThemeFile`] is a format-agnostic serde model: deserialize it with any serde format (TOML,
+//! JSON, ...) and resolve it with [`Theme::from_file`]. A file starts from a preset (see
+//! [`Theme::PRESETS`], default `"default"`), overrides palette colors, then overrides individual
+//! tokens:
+//!
+//! ```toml
+//! preset = "dark"
+//!
+//! [palette]
+//! accent = "#7aa2f7"
+//!
+//! [markdown]
+//! link = "accent underlined"
+//! heading1 = { fg = "black", bg = "accent", modifiers = ["bold"] }
+//!
+//! [chrome]
+//! selection = "on #33467c"
+//! ```
+//!
+//! Colors are palette names (`fg`, `bg`, `muted`, `accent`, `danger`, `success`, `warning`,
+//! `info`), ratatui color names (`"light-red"`), 256-color indices (`"236"`) or `"#rrggbb"`.
+//! A style is either a table with `fg`, `bg` and `modifiers`, or a shorthand string of
+//! space-separated words: modifiers (`bold`, `italic`, ...), `on <color>` for the background, and
+//! at most one foreground color.
+//!
+//! Token groups mirror [`Theme`]: `text` (`primary`, `muted`, `accent`, `danger`), `markdown`
+//! (`heading1`..`heading6` and the [`MarkdownTheme`](super::MarkdownTheme) fields), `diff`,
+//! `code`, `transcript` and `chrome`. Unknown keys and invalid values are reported as a
+//! [`ThemeError`] naming the dotted key, e.g. `markdown.link`.
+use super::Palette;
+use super::Theme;
+use crate::color::ColorProfile;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// The on-disk representation of a [`Theme`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    /// Overrides the preset's color profile (e.g. `"no-color"` for monochrome output).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color_profile: Option<ColorProfile>,
+    pub palette: BTreeMap<String, String>,
+    pub text: BTreeMap<String, StyleSpec>,
+    pub markdown: BTreeMap<String, StyleSpec>,
+    pub diff: BTreeMap<String, StyleSpec>,
+    pub code: BTreeMap<String, StyleSpec>,
+    pub transcript: BTreeMap<String, StyleSpec>,
+    pub chrome: BTreeMap<String, StyleSpec>,
+}
+
+/// A style in a [`ThemeFile`]: a shorthand string or a table.
+///
+/// Deserializing never fails on a malformed style; it yields [`StyleSpec::Invalid`] instead, so
+/// [`Theme::from_file`] can report the problem under the token's key.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(untagged)]
+pub enum StyleSpec {
+    Short(String),
+    Table(StyleTable),
+    /// A value that is not a valid style, e.g. a table with a misspelled field.
+    #[serde(skip_serializing)]
+    Invalid(ThemeErrorKind),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StyleTable {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fg: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<String>,
+}
+
+/// An invalid entry in a [`ThemeFile`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThemeError {
+    /// Dotted path of the offending key, e.g. `palette.accent` or `markdown.link`.
+    pub key: String,
+    pub kind: ThemeErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ThemeErrorKind {
+    UnknownPreset(String),
+    UnknownKey,
+    InvalidColor(String),
+    UnknownModifier(String),
+    /// A shorthand style with more than one foreground color or a dangling `on`.
+    InvalidStyle(String),
+    /// A style table field other than `fg`, `bg` and `modifiers`.
+    UnknownField(String),
+    /// A style that is neither a string nor a table, or a table field of the wrong type.
+    InvalidValue(String),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: ", self.key)?;
+        match &self.kind {
+            ThemeErrorKind::UnknownPreset(p) => write!(f, "unknown preset `{p}`"),
+            ThemeErrorKind::UnknownKey => write!(f, "unknown key"),
+            ThemeErrorKind::InvalidColor(c) => write!(f, "invalid color `{c}`"),
+            ThemeErrorKind::UnknownModifier(m) => write!(f, "unknown modifier `{m}`"),
+            ThemeErrorKind::InvalidStyle(s) => write!(f, "invalid style `{s}`"),
+            ThemeErrorKind::UnknownField(field) => write!(f, "unknown field `{field}`"),
+            ThemeErrorKind::InvalidValue(expected) => write!(f, "expected {expected}"),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+impl Theme {
+    /// Resolves a [`ThemeFile`] into a theme, reporting the first invalid key.
+    pub fn from_file(file: &ThemeFile) -> Result<Self, ThemeError> {
+        let preset = file.preset.as_deref().unwrap_or("default");
+        let mut palette = Palette::preset(preset)
+            .ok_or_else(|| error("preset", ThemeErrorKind::UnknownPreset(preset.to_string())))?;
+
+        for (name, value) in &file.palette {
+            let key = format!("palette.{name}");
+            let slot = palette_slot(&mut palette, name)
+                .ok_or_else(|| error(&key, ThemeErrorKind::UnknownKey))?;
+            *slot = Color::from_str(value)
+                .map_err(|_| error(&key, ThemeErrorKind::InvalidColor(value.clone())))?;
+        }
+
+        let mut theme = Theme::from_palette(palette);
+        theme.apply_preset_tokens(preset);
+        if let Some(profile) = file.color_profile {
+            theme.color_profile = profile;
+        }
+
+        let mut overrides = BTreeMap::new();
+        for (group, specs) in file.groups() {
+            for (name, spec) in specs {
+                overrides.insert(format!("{group}.{name}"), spec);
+            }
+        }
+        let known = token_keys();
+        if let Some(key) = overrides.keys().find(|k| !known.contains(k.as_str())) {
+            return Err(error(key, ThemeErrorKind::UnknownKey));
+        }
+
+        let palette = theme.palette;
+        let mut result = Ok(());
+        for_each_token(&mut theme, |key, style| {
+            if result.is_err() {
+                return;
+            }
+            if let Some(spec) = overrides.get(key) {
+                match spec.resolve(&palette) {
+                    Ok(resolved) => *style = resolved,
+                    Err(kind) => result = Err(error(key, kind)),
+                }
+            }
+        });
+        result.map(|()| theme)
+    }
+}
+
+impl ThemeFile {
+    /// Describes every palette color and token of `theme`, so the result resolves back to it.
+    pub fn from_theme(theme: &Theme) -> Self {
+        let mut file = ThemeFile {
+            color_profile: Some(theme.color_profile).filter(|p| *p != ColorProfile::default()),
+            ..ThemeFile::default()
+        };
+        let mut palette = theme.palette;
+        for name in PALETTE_KEYS {
+            let color = *palette_slot(&mut palette, name).expect("palette key");
+            file.palette.insert(name.to_string(), color.to_string());
+        }
+        let mut theme = theme.clone();
+        for_each_token(&mut theme, |key, style| {
+            let (group, name) = key.split_once('.').expect("dotted token key");
+            let spec = StyleSpec::Table(StyleTable {
+                fg: style.fg.map(|c| c.to_string()),
+                bg: style.bg.map(|c| c.to_string()),
+                modifiers: MODIFIERS
+                    .iter()
+                    .filter(|(_, m)| style.add_modifier.contains(*m))
+                    .map(|(n, _)| n.to_string())
+                    .collect(),
+            });
+            if let Some(specs) = file.group_mut(group) {
+                specs.insert(name.to_string(), spec);
+            }
+        });
+        file
+    }
+
+    fn groups(&self) -> [(&'static str, &BTreeMap<String, StyleSpec>); 6] {
+        [
+            ("text", &self.text),
+            ("markdown", &self.markdown),
+            ("diff", &self.diff),
+            ("code", &self.code),
+            ("transcript", &self.transcript),
+            ("chrome", &self.chrome),
+        ]
+    }
+
+    fn group_mut(&mut self, group: &str) -> Option<&mut BTreeMap<String, StyleSpec>> {
+        match group {
+            "text" => Some(&mut self.text),
+            "markdown" => Some(&mut self.markdown),
+            "diff" => Some(&mut self.diff),
+            "code" => Some(&mut self.code),
+            "transcript" => Some(&mut self.transcript),
+            "chrome" => Some(&mut self.chrome),
+            _ => None,
+        }
+    }
+}
+
+impl StyleSpec {
+    fn from_raw(raw: RawValue) -> Self {
+        let entries = match raw {
+            RawValue::Str(s) => return StyleSpec::Short(s),
+            RawValue::Map(entries) => entries,
+            RawValue::Seq(_) | RawValue::Other => {
+                return StyleSpec::Invalid(ThemeErrorKind::InvalidValue(
+                    "a style string or table".to_string(),
+                ));
+            }
+        };
+        let mut table = StyleTable::default();
+        for (field, value) in entries {
+            let invalid = |expected: &str| {
+                StyleSpec::Invalid(ThemeErrorKind::InvalidValue(format!(
+                    "`{field}` to be {expected}"
+                )))
+            };
+            match (field.as_str(), value) {
+                ("fg", RawValue::Str(color)) => table.fg = Some(color),
+                ("bg", RawValue::Str(color)) => table.bg = Some(color),
+                ("fg" | "bg", _) => return invalid("a color string"),
+                ("modifiers", RawValue::Seq(items)) => {
+                    for item in items {
+                        let RawValue::Str(name) = item else {
+                            return invalid("a list of modifier names");
+                        };
+                        table.modifiers.push(name);
+                    }
+                }
+                ("modifiers", _) => return invalid("a list of modifier names"),
+                _ => return StyleSpec::Invalid(ThemeErrorKind::UnknownField(field)),
+            }
+        }
+        StyleSpec::Table(table)
+    }
+
+    fn resolve(&self, palette: &Palette) -> Result<Style, ThemeErrorKind> {
+        match self {
+            StyleSpec::Invalid(kind) => Err(kind.clone()),
+            StyleSpec::Table(t) => {
+                let mut style = Style::default();
+                if let Some(fg) = &t.fg {
+                    style = style.fg(parse_color(fg, palette)?);
+                }
+                if let Some(bg) = &t.bg {
+                    style = style.bg(parse_color(bg, palette)?);
+                }
+                for m in &t.modifiers {
+                    style = style.add_modifier(parse_modifier(m)?);
+                }
+                Ok(style)
+            }
+            StyleSpec::Short(s) => {
+                let mut style = Style::default();
+                let mut fg_set = false;
+                let mut words = s.split_whitespace();
+                while let Some(word) = words.next() {
+                    if word == "on" {
+                        let bg = words
+                            .next()
+                            .ok_or_else(|| ThemeErrorKind::InvalidStyle(s.clone()))?;
+                        style = style.bg(parse_color(bg, palette)?);
+                    } else if let Ok(m) = parse_modifier(word) {
+                        style = style.add_modifier(m);
+                    } else if fg_set {
+                        return Err(ThemeErrorKind::InvalidStyle(s.clone()));
+                    } else {
+                        style = style.fg(parse_color(word, palette)?);
+                        fg_set = true;
+                    }
+                }
+                Ok(style)
+            }
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for StyleSpec {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RawValue::deserialize(deserializer).map(StyleSpec::from_raw)
+    }
+}
+
+/// A loosely typed token value, validated by [`StyleSpec::from_raw`].
+enum RawValue {
+    Str(String),
+    Seq(Vec<RawValue>),
+    Map(Vec<(String, RawValue)>),
+    Other,
+}
+
+impl<'de> serde::Deserialize<'de> for RawValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(RawValueVisitor)
+    }
+}
+
+struct RawValueVisitor;
+
+impl<'de> serde::de::Visitor<'de> for RawValueVisitor {
+    type Value = RawValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a style string or table")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<RawValue, E> {
+        Ok(RawValue::Str(v.to_string()))
+    }
+
+    fn visit_string<E: serde::de::Error>(self, v: String) -> Result<RawValue, E> {
+        Ok(RawValue::Str(v))
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, _: bool) -> Result<RawValue, E> {
+        Ok(RawValue::Other)
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, _: i64) -> Result<RawValue, E> {
+        Ok(RawValue::Other)
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, _: u64) -> Result<RawValue, E> {
+        Ok(RawValue::Other)
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, _: f64) -> Result<RawValue, E> {
+        Ok(RawValue::Other)
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<RawValue, E> {
+        Ok(RawValue::Other)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<RawValue, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(RawValue::Seq(items))
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<RawValue, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(RawValue::Map(entries))
+    }
+}
+
+const PALETTE_KEYS: [&str; 8] = [
+    "fg", "bg", "muted", "accent", "danger", "success", "warning", "info",
+];
+
+const MODIFIERS: [(&str, Modifier); 9] = [
+    ("bold", Modifier::BOLD),
+    ("dim", Modifier::DIM),
+    ("italic", Modifier::ITALIC),
+    ("underlined", Modifier::UNDERLINED),
+    ("slow-blink", Modifier::SLOW_BLINK),
+    ("rapid-blink", Modifier::RAPID_BLINK),
+    ("reversed", Modifier::REVERSED),
+    ("hidden", Modifier::HIDDEN),
+    ("crossed-out", Modifier::CROSSED_OUT),
+];
+
+fn error(key: &str, kind: ThemeErrorKind) -> ThemeError {
+    ThemeError {
+        key: key.to_string(),
+        kind,
+    }
+}
+
+fn palette_slot<'a>(palette: &'a mut Palette, name: &str) -> Option<&'a mut Color> {
+    match name {
+        "fg" => Some(&mut palette.fg),
+        "bg" => Some(&mut palette.bg),
+        "muted" => Some(&mut palette.muted),
+        "accent" => Some(&mut palette.accent),
+        "danger" => Some(&mut palette.danger),
+        "success" => Some(&mut palette.success),
+        "warning" => Some(&mut palette.warning),
+        "info" => Some(&mut palette.info),
+        _ => None,
+    }
+}
+
+fn parse_color(value: &str, palette: &Palette) -> Result<Color, ThemeErrorKind> {
+    let mut palette = *palette;
+    if let Some(color) = palette_slot(&mut palette, value) {
+        return Ok(*color);
+    }
+    Color::from_str(value).map_err(|_| ThemeErrorKind::InvalidColor(value.to_string()))
+}
+
+fn parse_modifier(name: &str) -> Result<Modifier, ThemeErrorKind> {
+    let normalized = name.to_ascii_lowercase().replace('_', "-");
+    let normalized = match normalized.as_str() {
+        "underline" => "underlined",
+        "reverse" => "reversed",
+        "strikethrough" | "crossedout" => "crossed-out",
+        other => other,
+    };
+    MODIFIERS
+        .iter()
+        .find(|(n, _)| *n == normalized)
+        .map(|(_, m)| *m)
+        .ok_or_else(|| ThemeErrorKind::UnknownModifier(name.to_string()))
+}
+
+fn token_keys() -> BTreeSet<&'static str> {
+    let mut keys = BTreeSet::new();
+    for_each_token(&mut Theme::default(), |key, _| {
+        keys.insert(key);
+    });
+    keys
+}
+
+/// Visits every token of `theme` with its dotted file key.
+fn for_each_token(theme: &mut Theme, mut f: impl FnMut(&'static str, &mut Style)) {
+    f("text.primary", &mut theme.text_primary);
+    f("text.muted", &mut theme.text_muted);
+    f("text.accent", &mut theme.accent);
+    f("text.danger", &mut theme.danger);
+
+    let md = &mut theme.markdown;
+    const HEADINGS: [&str; 6] = [
+        "markdown.heading1",
+        "markdown.heading2",
+        "markdown.heading3",
+        "markdown.heading4",
+        "markdown.heading5",
+        "markdown.heading6",
+    ];
+    for (key, style) in HEADINGS.into_iter().zip(md.headings.iter_mut()) {
+        f(key, style);
+    }
+    f("markdown.link", &mut md.link);
+    f("markdown.emphasis", &mut md.emphasis);
+    f("markdown.strong", &mut md.strong);
+    f("markdown.strikethrough", &mut md.strikethrough);
+    f("markdown.blockquote", &mut md.blockquote);
+    f("markdown.list_marker", &mut md.list_marker);
+    f("markdown.code_block", &mut md.code_block);
+    f("markdown.table_border", &mut md.table_border);
+    f("markdown.table_header", &mut md.table_header);
+    f("markdown.rule", &mut md.rule);
+
+    let diff = &mut theme.diff;
+    f("diff.add", &mut diff.add);
+    f("diff.del", &mut diff.del);
+    f("diff.context", &mut diff.context);
+    f("diff.hunk_header", &mut diff.hunk_header);
+    f("diff.file_header", &mut diff.file_header);
+    f("diff.meta", &mut diff.meta);
+    f("diff.gutter", &mut diff.gutter);
+    f("diff.intraline", &mut diff.intraline);
+
+    let code = &mut theme.code;
+    f("code.text", &mut code.text);
+    f("code.inline", &mut code.inline);
+    f("code.line_number", &mut code.line_number);
+
+    let tr = &mut theme.transcript;
+    f("transcript.user", &mut tr.user);
+    f("transcript.assistant", &mut tr.assistant);
+    f("transcript.tool", &mut tr.tool);
+    f("transcript.system", &mut tr.system);
+    f("transcript.separator", &mut tr.separator);
+
+    let chrome = &mut theme.chrome;
+    f("chrome.border", &mut chrome.border);
+    f("chrome.border_focused", &mut chrome.border_focused);
+    f("chrome.scrollbar", &mut chrome.scrollbar);
+    f("chrome.marker_search", &mut chrome.marker_search);
+    f("chrome.marker_hunk", &mut chrome.marker_hunk);
+    f("chrome.marker_diagnostic", &mut chrome.marker_diagnostic);
+    f("chrome.marker_selection", &mut chrome.marker_selection);
+    f("chrome.selection", &mut chrome.selection);
+    f("chrome.cursor", &mut chrome.cursor);
+    f("chrome.cursor_unfocused", &mut chrome.cursor_unfocused);
+    f("chrome.selected", &mut chrome.selected);
+    f("chrome.hover", &mut chrome.hover);
+    f("chrome.header", &mut chrome.header);
+    f("chrome.grid_line", &mut chrome.grid_line);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_toml(src: &str) -> Result<Theme, ThemeError> {
+        let file: ThemeFile = toml::from_str(src).expect("valid toml");
+        Theme::from_file(&file)
+    }
+
+    #[test]
+    fn toml_file_overrides_palette_and_tokens() {
+        let theme = load_toml(
+            r##"
+            preset = "dark"
+
+            [palette]
+            accent = "#7aa2f7"
+
+            [markdown]
+            link = "accent underlined"
+            heading1 = { fg = "black", bg = "accent", modifiers = ["bold"] }
+
+            [chrome]
+            selection = "on 236 italic"
+            "##,
+        )
+        .unwrap();
+
+        let accent = Color::Rgb(0x7a, 0xa2, 0xf7);
+        assert_eq!(theme.palette.accent, accent);
+        assert_eq!(theme.accent.fg, Some(accent));
+        assert_eq!(
+            theme.markdown.link,
+            Style::default()
+                .fg(accent)
+                .add_modifier(Modifier::UNDERLINED)
+        );
+        assert_eq!(
+            theme.markdown.heading(1),
+            Style::default()
+                .fg(Color::Black)
+                .bg(accent)
+                .add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(
+            theme.chrome.selection,
+            Style::default()
+                .bg(Color::Indexed(236))
+                .add_modifier(Modifier::ITALIC)
+        );
+        assert_eq!(theme.text_primary.fg, Some(Color::Gray));
+    }
+
+    #[test]
+    fn json_file_round_trips_every_token() {
+        for name in ["glow-light", "monochrome"] {
+            let theme = Theme::preset(name).unwrap();
+            let json = serde_json::to_string(&ThemeFile::from_theme(&theme)).unwrap();
+            let file: ThemeFile = serde_json::from_str(&json).unwrap();
+            assert_eq!(Theme::from_file(&file), Ok(theme));
+        }
+    }
+
+    #[test]
+    fn errors_name_the_offending_key() {
+        let err = |src| load_toml(src).unwrap_err().to_string();
+        assert_eq!(err(r#"preset = "neon""#), "`preset`: unknown preset `neon`");
+        assert_eq!(
+            err("[palette]\naccent = \"blu\""),
+            "`palette.accent`: invalid color `blu`"
+        );
+        assert_eq!(
+            err("[palette]\nprimary = \"red\""),
+            "`palette.primary`: unknown key"
+        );
+        assert_eq!(
+            err("[markdown]\nlnk = \"red\""),
+            "`markdown.lnk`: unknown key"
+        );
+        assert_eq!(
+            err("[diff]\nadd = { fg = \"green\", modifiers = [\"blinky\"] }"),
+            "`diff.add`: unknown modifier `blinky`"
+        );
+        assert_eq!(
+            err("[chrome]\nborder = \"red blue\""),
+            "`chrome.border`: invalid style `red blue`"
+        );
+        assert_eq!(
+            err("[markdown]\nlink = { fgg = \"red\" }"),
+            "`markdown.link`: unknown field `fgg`"
+        );
+        assert_eq!(
+            err("[markdown]\nlink = { fg = 1 }"),
+            "`markdown.link`: expected `fg` to be a color string"
+        );
+        assert_eq!(
+            err("[markdown]\nlink = 3"),
+            "`markdown.link`: expected a style string or table"
+        );
+        assert!(toml::from_str::<ThemeFile>("[colors]\nx = 1").is_err());
+    }
+}
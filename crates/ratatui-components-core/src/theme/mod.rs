@@ -0,0 +1,585 @@
+//! Semantic styling tokens shared by every view (see ADR 0002).
+//!
+//! A [`Theme`] is built from a small [`Palette`] of base colors plus groups of per-component
+//! tokens ([`MarkdownTheme`], [`DiffTheme`], [`CodeTheme`], [`TranscriptTheme`],
+//! [`ChromeTheme`]). [`Theme::from_palette`] derives every token from the palette, so swapping
+//! the palette restyles the whole app; individual tokens can still be overridden afterwards.
+//!
+//! Built-in presets are available via [`Theme::preset`]; with the `serde` feature, themes can also
+//! be loaded from TOML/JSON files (see `ThemeFile`).
+use crate::color::ColorProfile;
+use crate::render::MarkerKind;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+
+#[cfg(feature = "serde")]
+mod file;
+#[cfg(feature = "serde")]
+pub use file::StyleSpec;
+#[cfg(feature = "serde")]
+pub use file::StyleTable;
+#[cfg(feature = "serde")]
+pub use file::ThemeError;
+#[cfg(feature = "serde")]
+pub use file::ThemeErrorKind;
+#[cfg(feature = "serde")]
+pub use file::ThemeFile;
+
+/// Base colors the default tokens are derived from.
+///
+/// `Color::Reset` means "the terminal's default" and produces an unstyled token.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette {
+    pub fg: Color,
+    pub bg: Color,
+    pub muted: Color,
+    pub accent: Color,
+    pub danger: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub info: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            fg: Color::Reset,
+            bg: Color::Reset,
+            muted: Color::DarkGray,
+            accent: Color::Cyan,
+            danger: Color::Red,
+            success: Color::Green,
+            warning: Color::Yellow,
+            info: Color::Blue,
+        }
+    }
+}
+
+/// Tokens for [Markdown](https://commonmark.org) rendering.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarkdownTheme {
+    /// Heading styles for levels 1 through 6.
+    pub headings: [Style; 6],
+    pub link: Style,
+    /// Patched onto `*emphasis*`.
+    pub emphasis: Style,
+    /// Patched onto `**strong**` text.
+    pub strong: Style,
+    /// Patched onto `~~strikethrough~~` text.
+    pub strikethrough: Style,
+    pub blockquote: Style,
+    /// List markers and list item prefixes.
+    pub list_marker: Style,
+    /// Fenced/indented code blocks without syntax highlighting.
+    pub code_block: Style,
+    pub table_border: Style,
+    /// Patched onto table header cells.
+    pub table_header: Style,
+    /// Horizontal rules.
+    pub rule: Style,
+}
+
+impl MarkdownTheme {
+    /// Returns the style for a heading of `level` (clamped to 1..=6).
+    pub fn heading(&self, level: u8) -> Style {
+        self.headings[(level.clamp(1, 6) - 1) as usize]
+    }
+}
+
+/// Tokens for unified diffs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffTheme {
+    pub add: Style,
+    pub del: Style,
+    pub context: Style,
+    pub hunk_header: Style,
+    pub file_header: Style,
+    pub meta: Style,
+    /// The `+`/`-`/` ` marker column for context lines.
+    pub gutter: Style,
+    /// Patched onto the changed parts of a line when intraline highlighting is enabled.
+    pub intraline: Style,
+}
+
+/// Tokens for source code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeTheme {
+    /// Code text when no highlighter is set.
+    pub text: Style,
+    /// Inline code spans (e.g. Markdown backticks).
+    pub inline: Style,
+    pub line_number: Style,
+}
+
+/// Tokens for chat transcripts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TranscriptTheme {
+    pub user: Style,
+    pub assistant: Style,
+    pub tool: Style,
+    pub system: Style,
+    /// The `│` separator between the role gutter and the content.
+    pub separator: Style,
+}
+
+/// Tokens for UI chrome shared by all views: borders, scrollbars, selection and cursors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChromeTheme {
+    /// Pane borders around an unfocused view.
+    pub border: Style,
+    /// Pane borders around the focused view.
+    pub border_focused: Style,
+    pub scrollbar: Style,
+    /// Scrollbar markers for search hits (see [`crate::render::ScrollbarMarker`]).
+    pub marker_search: Style,
+    /// Scrollbar markers for diff hunk boundaries.
+    pub marker_hunk: Style,
+    /// Scrollbar markers for diagnostics.
+    pub marker_diagnostic: Style,
+    /// Scrollbar markers for the selected range.
+    pub marker_selection: Style,
+    /// Patched onto selected text.
+    pub selection: Style,
+    /// The cursor row/cell of list-like views while focused.
+    pub cursor: Style,
+    /// The cursor row/cell while the view is unfocused.
+    pub cursor_unfocused: Style,
+    /// Selected (but not cursor) rows/cells of list-like views.
+    pub selected: Style,
+    /// Patched onto the row under the mouse pointer.
+    pub hover: Style,
+    /// Column headers of grids.
+    pub header: Style,
+    pub grid_line: Style,
+}
+
+impl ChromeTheme {
+    /// Returns [`Self::border_focused`] or [`Self::border`].
+    pub fn border_style(&self, focused: bool) -> Style {
+        if focused {
+            self.border_focused
+        } else {
+            self.border
+        }
+    }
+
+    /// Returns [`Self::cursor`] or [`Self::cursor_unfocused`].
+    pub fn cursor_style(&self, focused: bool) -> Style {
+        if focused {
+            self.cursor
+        } else {
+            self.cursor_unfocused
+        }
+    }
+
+    /// The `marker_*` token for `kind`.
+    pub fn marker_style(&self, kind: MarkerKind) -> Style {
+        match kind {
+            MarkerKind::Selection => self.marker_selection,
+            MarkerKind::Hunk => self.marker_hunk,
+            MarkerKind::Search => self.marker_search,
+            MarkerKind::Diagnostic => self.marker_diagnostic,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Theme {
+    pub palette: Palette,
+    pub text_primary: Style,
+    pub text_muted: Style,
+    pub accent: Style,
+    pub danger: Style,
+    pub markdown: MarkdownTheme,
+    pub diff: DiffTheme,
+    pub code: CodeTheme,
+    pub transcript: TranscriptTheme,
+    pub chrome: ChromeTheme,
+    /// Color capabilities of the target terminal. Views downsample everything they render to this
+    /// profile (see [`crate::color`]); the default, `TrueColor`, leaves colors untouched.
+    pub color_profile: ColorProfile,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::from_palette(Palette::default())
+    }
+}
+
+impl Theme {
+    /// Derives every token from `palette`.
+    pub fn from_palette(palette: Palette) -> Self {
+        let text_primary = if palette.bg == Color::Reset {
+            fg(palette.fg)
+        } else {
+            fg(palette.fg).bg(palette.bg)
+        };
+        let text_muted = fg(palette.muted);
+        let accent = fg(palette.accent);
+        let danger = fg(palette.danger);
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let reversed = Style::default().add_modifier(Modifier::REVERSED);
+
+        Self {
+            palette,
+            text_primary,
+            text_muted,
+            accent,
+            danger,
+            markdown: MarkdownTheme {
+                headings: [
+                    text_primary.add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                    text_primary.add_modifier(Modifier::BOLD),
+                    text_primary.add_modifier(Modifier::BOLD),
+                    text_primary.add_modifier(Modifier::BOLD),
+                    text_primary.add_modifier(Modifier::BOLD),
+                    text_primary.add_modifier(Modifier::BOLD),
+                ],
+                link: accent.add_modifier(Modifier::UNDERLINED),
+                emphasis: Style::default().add_modifier(Modifier::ITALIC),
+                strong: bold,
+                strikethrough: Style::default().add_modifier(Modifier::CROSSED_OUT),
+                blockquote: text_muted,
+                list_marker: text_muted,
+                code_block: accent,
+                table_border: text_muted,
+                table_header: bold,
+                rule: text_muted,
+            },
+            diff: DiffTheme {
+                add: fg(palette.success),
+                del: danger,
+                context: text_primary,
+                hunk_header: accent.add_modifier(Modifier::BOLD),
+                file_header: text_muted,
+                meta: text_primary,
+                gutter: text_muted,
+                intraline: reversed,
+            },
+            code: CodeTheme {
+                text: text_primary,
+                inline: accent,
+                line_number: text_muted,
+            },
+            transcript: TranscriptTheme {
+                user: accent.add_modifier(Modifier::BOLD),
+                assistant: text_primary.add_modifier(Modifier::BOLD),
+                tool: text_muted.add_modifier(Modifier::BOLD),
+                system: text_muted,
+                separator: text_muted,
+            },
+            chrome: ChromeTheme {
+                border: text_muted,
+                border_focused: accent,
+                scrollbar: text_muted,
+                marker_search: fg(palette.warning),
+                marker_hunk: fg(palette.info),
+                marker_diagnostic: danger,
+                marker_selection: accent,
+                selection: reversed,
+                cursor: accent.add_modifier(Modifier::REVERSED),
+                cursor_unfocused: text_muted.add_modifier(Modifier::REVERSED),
+                selected: accent.add_modifier(Modifier::BOLD),
+                hover: Style::default().bg(palette.muted),
+                header: accent.add_modifier(Modifier::BOLD),
+                grid_line: text_muted,
+            },
+            color_profile: ColorProfile::default(),
+        }
+    }
+
+    pub fn with_color_profile(mut self, profile: ColorProfile) -> Self {
+        self.color_profile = profile;
+        self
+    }
+}
+
+impl Palette {
+    /// Returns the palette behind the built-in preset `name` (see [`Theme::PRESETS`]).
+    pub fn preset(name: &str) -> Option<Self> {
+        let palette = match name {
+            "default" => Self::default(),
+            "dark" => Self {
+                fg: Color::Gray,
+                bg: Color::Reset,
+                muted: Color::DarkGray,
+                accent: Color::LightCyan,
+                danger: Color::LightRed,
+                success: Color::LightGreen,
+                warning: Color::LightYellow,
+                info: Color::LightBlue,
+            },
+            "light" => Self {
+                fg: Color::Black,
+                bg: Color::Reset,
+                muted: Color::DarkGray,
+                accent: Color::Blue,
+                danger: Color::Red,
+                success: Color::Green,
+                warning: Color::Indexed(130),
+                info: Color::Cyan,
+            },
+            "high-contrast" => Self {
+                fg: Color::White,
+                bg: Color::Black,
+                muted: Color::Gray,
+                accent: Color::LightYellow,
+                danger: Color::LightRed,
+                success: Color::LightGreen,
+                warning: Color::Yellow,
+                info: Color::LightCyan,
+            },
+            // Colors follow Glamour's `dark` / `light` styles used by Glow.
+            "glow-dark" => Self {
+                fg: Color::Indexed(252),
+                bg: Color::Reset,
+                muted: Color::Indexed(240),
+                accent: Color::Indexed(39),
+                danger: Color::Indexed(203),
+                success: Color::Indexed(35),
+                warning: Color::Indexed(228),
+                info: Color::Indexed(30),
+            },
+            "glow-light" => Self {
+                fg: Color::Indexed(234),
+                bg: Color::Reset,
+                muted: Color::Indexed(243),
+                accent: Color::Indexed(27),
+                danger: Color::Indexed(203),
+                success: Color::Indexed(35),
+                warning: Color::Indexed(228),
+                info: Color::Indexed(36),
+            },
+            "monochrome" => Self {
+                fg: Color::Reset,
+                bg: Color::Reset,
+                muted: Color::Reset,
+                accent: Color::Reset,
+                danger: Color::Reset,
+                success: Color::Reset,
+                warning: Color::Reset,
+                info: Color::Reset,
+            },
+            _ => return None,
+        };
+        Some(palette)
+    }
+}
+
+impl Theme {
+    /// Names accepted by [`Self::preset`].
+    pub const PRESETS: &'static [&'static str] = &[
+        "default",
+        "dark",
+        "light",
+        "high-contrast",
+        "glow-dark",
+        "glow-light",
+        "monochrome",
+    ];
+
+    /// The `monochrome` preset: no colors, structure is kept through modifiers only.
+    pub fn monochrome() -> Self {
+        Self::preset("monochrome").expect("built-in preset")
+    }
+
+    /// Whether views should render without any color (the [`ColorProfile::NoColor`] profile, e.g.
+    /// from `NO_COLOR` or the `monochrome` preset).
+    ///
+    /// In this mode views skip syntax highlighting entirely and mark selections with reverse video
+    /// (see [`Self::selection_style`]); diff lines keep their `+` / `-` markers.
+    pub fn is_monochrome(&self) -> bool {
+        self.color_profile == ColorProfile::NoColor
+    }
+
+    /// The style for selected text: [`ChromeTheme::selection`], or reverse video when
+    /// [`Self::is_monochrome`] (a background-only selection would be invisible).
+    pub fn selection_style(&self) -> Style {
+        if self.is_monochrome() {
+            ColorProfile::NoColor
+                .adapt_style(self.chrome.selection)
+                .add_modifier(Modifier::REVERSED)
+        } else {
+            self.chrome.selection
+        }
+    }
+
+    /// Returns the built-in theme `name`, or `None` if it is not one of [`Self::PRESETS`].
+    pub fn preset(name: &str) -> Option<Self> {
+        let mut theme = Self::from_palette(Palette::preset(name)?);
+        theme.apply_preset_tokens(name);
+        Some(theme)
+    }
+
+    /// Applies the token tweaks a preset makes on top of [`Self::from_palette`].
+    fn apply_preset_tokens(&mut self, name: &str) {
+        match name {
+            "high-contrast" => {
+                self.chrome.selection = self.chrome.selection.add_modifier(Modifier::BOLD);
+                self.chrome.border = self.chrome.border.add_modifier(Modifier::BOLD);
+                self.chrome.border_focused =
+                    self.chrome.border_focused.add_modifier(Modifier::BOLD);
+                self.markdown.link = self.markdown.link.add_modifier(Modifier::BOLD);
+            }
+            "glow-dark" | "glow-light" => {
+                let dark = name == "glow-dark";
+                let heading = self.accent.add_modifier(Modifier::BOLD);
+                self.markdown.headings = [heading; 6];
+                self.markdown.headings[0] = Style::default()
+                    .fg(Color::Indexed(228))
+                    .bg(Color::Indexed(63))
+                    .add_modifier(Modifier::BOLD);
+                self.code.inline = Style::default()
+                    .fg(Color::Indexed(203))
+                    .bg(Color::Indexed(if dark { 236 } else { 254 }));
+                self.markdown.code_block = fg(Color::Indexed(if dark { 244 } else { 242 }));
+                self.markdown.link = fg(self.palette.info).add_modifier(Modifier::UNDERLINED);
+                self.markdown.rule = fg(Color::Indexed(if dark { 240 } else { 249 }));
+            }
+            "monochrome" => {
+                let m = |modifier: Modifier| Style::default().add_modifier(modifier);
+                let bold = m(Modifier::BOLD);
+                let dim = m(Modifier::DIM);
+                let underlined = m(Modifier::UNDERLINED);
+                let reversed = m(Modifier::REVERSED);
+
+                self.text_muted = dim;
+                self.accent = bold;
+                self.danger = bold.add_modifier(Modifier::UNDERLINED);
+
+                let md = &mut self.markdown;
+                md.headings = [bold; 6];
+                md.headings[0] = bold.add_modifier(Modifier::UNDERLINED);
+                md.link = underlined;
+                md.emphasis = m(Modifier::ITALIC);
+                md.strong = bold;
+                md.strikethrough = m(Modifier::CROSSED_OUT);
+                md.blockquote = m(Modifier::ITALIC);
+                md.list_marker = bold;
+                md.code_block = Style::default();
+                md.table_border = dim;
+                md.table_header = bold;
+                md.rule = dim;
+
+                self.diff = DiffTheme {
+                    add: bold,
+                    del: dim,
+                    context: Style::default(),
+                    hunk_header: bold,
+                    file_header: bold.add_modifier(Modifier::UNDERLINED),
+                    meta: dim,
+                    gutter: dim,
+                    intraline: underlined,
+                };
+                self.code = CodeTheme {
+                    text: Style::default(),
+                    inline: bold,
+                    line_number: dim,
+                };
+                self.transcript = TranscriptTheme {
+                    user: bold,
+                    assistant: bold.add_modifier(Modifier::ITALIC),
+                    tool: m(Modifier::ITALIC),
+                    system: dim,
+                    separator: dim,
+                };
+                self.chrome = ChromeTheme {
+                    border: dim,
+                    border_focused: bold,
+                    scrollbar: dim,
+                    marker_search: bold,
+                    marker_hunk: dim,
+                    marker_diagnostic: bold,
+                    marker_selection: bold,
+                    selection: reversed,
+                    cursor: reversed,
+                    cursor_unfocused: underlined,
+                    selected: reversed.add_modifier(Modifier::BOLD),
+                    hover: bold,
+                    header: bold.add_modifier(Modifier::UNDERLINED),
+                    grid_line: dim,
+                };
+                self.color_profile = ColorProfile::NoColor;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn fg(color: Color) -> Style {
+    if color == Color::Reset {
+        Style::default()
+    } else {
+        Style::default().fg(color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_palette_leaves_primary_text_unstyled() {
+        let theme = Theme::default();
+        assert_eq!(theme.text_primary, Style::default());
+        assert_eq!(theme.code.text, Style::default());
+        assert_eq!(theme.diff.add, Style::default().fg(Color::Green));
+        assert_eq!(
+            theme.chrome.cursor_style(false),
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::REVERSED)
+        );
+    }
+
+    #[test]
+    fn tokens_follow_the_palette() {
+        let theme = Theme::from_palette(Palette {
+            accent: Color::Magenta,
+            bg: Color::Black,
+            ..Palette::default()
+        });
+        assert_eq!(theme.text_primary.bg, Some(Color::Black));
+        assert_eq!(theme.markdown.link.fg, Some(Color::Magenta));
+        assert_eq!(theme.chrome.border_style(true).fg, Some(Color::Magenta));
+        assert_eq!(theme.transcript.user.fg, Some(Color::Magenta));
+        assert_eq!(theme.markdown.heading(0), theme.markdown.headings[0]);
+        assert_eq!(theme.markdown.heading(9), theme.markdown.headings[5]);
+    }
+
+    #[test]
+    fn monochrome_uses_modifiers_and_reverse_selection() {
+        let theme = Theme::monochrome();
+        assert!(theme.is_monochrome());
+        assert_eq!(theme.palette, Palette::preset("monochrome").unwrap());
+        assert_eq!(theme.diff.add.fg, None);
+        assert!(theme.diff.add.add_modifier.contains(Modifier::BOLD));
+
+        let glow = Theme::preset("glow-dark")
+            .unwrap()
+            .with_color_profile(ColorProfile::NoColor);
+        assert!(glow.is_monochrome());
+        assert_eq!(
+            glow.selection_style(),
+            Style::default().add_modifier(Modifier::REVERSED)
+        );
+        assert_eq!(
+            Theme::default().selection_style(),
+            Theme::default().chrome.selection
+        );
+    }
+
+    #[test]
+    fn every_listed_preset_exists() {
+        for name in Theme::PRESETS {
+            assert!(Theme::preset(name).is_some(), "{name}");
+        }
+        assert_eq!(Theme::preset("default"), Some(Theme::default()));
+        assert_eq!(Theme::preset("solarized"), None);
+
+        let glow = Theme::preset("glow-dark").unwrap();
+        assert_eq!(glow.markdown.heading(1).bg, Some(Color::Indexed(63)));
+        assert_eq!(glow.markdown.heading(2).fg, Some(Color::Indexed(39)));
+    }
+}
@@ -1,7 +1,11 @@
+use crate::grapheme;
 use crate::render;
+use crate::tabs::TabPolicy;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Style;
+use std::time::Duration;
+use std::time::Instant;
 
 #[derive(Clone, Copy, Debug, Default)]
 pub struct ViewportState {
@@ -67,6 +71,28 @@ impl ViewportState {
         self.x = self.max_x();
     }
 
+    /// Scrolls as little as possible to bring content cell `(x, y)` into view.
+    pub fn ensure_visible(&mut self, x: u32, y: u32) {
+        let h = self.viewport_h.max(1) as u32;
+        let w = self.viewport_w.max(1) as u32;
+        if y < self.y {
+            self.y = y;
+        } else if y >= self.y.saturating_add(h) {
+            self.y = y + 1 - h;
+        }
+        if x < self.x {
+            self.x = x;
+        } else if x >= self.x.saturating_add(w) {
+            self.x = x + 1 - w;
+        }
+        self.clamp();
+    }
+
+    /// Whether the content overflows the viewport and the view is scrolled all the way down.
+    pub fn is_scrolled_to_bottom(&self) -> bool {
+        self.content_h > self.viewport_h as u32 && self.y >= self.max_y()
+    }
+
     pub fn percent_y(&self) -> Option<u8> {
         if self.content_h == 0 || self.viewport_h == 0 || self.content_h <= self.viewport_h as u32 {
             return None;
@@ -85,11 +111,251 @@ impl ViewportState {
     }
 }
 
+/// A scroll position recorded in source terms, so it survives a re-layout.
+///
+/// Views that wrap content record the source line (or block, or entry) shown in the top row plus
+/// how far into it that row starts, re-layout at the new width or for new content, then look the
+/// anchor up again. `offset` is in whatever unit the recording layout uses: byte offsets for
+/// [`WrapCache`](crate::wrapping::WrapCache), visible characters (see [`Self::text_offset`]) for
+/// views that only keep rendered lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScrollAnchor {
+    pub src: usize,
+    pub offset: usize,
+    /// The view was scrolled to the bottom; restoring keeps it there.
+    pub at_bottom: bool,
+}
+
+impl ScrollAnchor {
+    pub fn new(src: usize, offset: usize, state: &ViewportState) -> Self {
+        Self {
+            src,
+            offset,
+            at_bottom: state.is_scrolled_to_bottom(),
+        }
+    }
+
+    /// Scrolls `state` to `row`, the anchor's top row in the new layout (`None` keeps `y`), or to
+    /// the bottom when the anchor was recorded there.
+    pub fn restore(&self, state: &mut ViewportState, row: Option<u32>) {
+        if self.at_bottom {
+            state.to_bottom();
+            return;
+        }
+        if let Some(row) = row {
+            state.y = row;
+        }
+        state.clamp();
+    }
+
+    /// Number of visible (non-whitespace) graphemes in `rows`.
+    ///
+    /// Counting only visible text makes the offset independent of where a layout broke lines,
+    /// which spaces it dropped and which indents or prefixes it added.
+    pub fn text_offset<I>(rows: I) -> usize
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        rows.into_iter().map(|row| visible_len(row.as_ref())).sum()
+    }
+
+    /// Index of the row in `rows` that holds the visible grapheme at `offset` (the inverse of
+    /// [`Self::text_offset`]); past the end this is the last row.
+    pub fn row_for_offset<I>(rows: I, offset: usize) -> usize
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut seen = 0;
+        let mut last = 0;
+        for (i, row) in rows.into_iter().enumerate() {
+            let n = visible_len(row.as_ref());
+            if seen >= offset || seen + n > offset {
+                return i;
+            }
+            seen += n;
+            last = i;
+        }
+        last
+    }
+}
+
+fn visible_len(s: &str) -> usize {
+    grapheme::graphemes(s)
+        .filter(|g| !grapheme::is_whitespace(g))
+        .count()
+}
+
+/// Wheel acceleration and eased scrolling, opted into per view through
+/// [`ScrollBindings::smooth`](crate::scroll::ScrollBindings::smooth).
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct SmoothScroll {
+    /// How long an eased scroll takes to reach its target; `0` jumps straight there.
+    pub duration_ms: u64,
+    /// Wheel events in the same direction at most this far apart continue a gesture.
+    pub accel_window_ms: u64,
+    /// Added to the wheel multiplier for each event that continues a gesture.
+    pub accel_step: f32,
+    /// Upper bound of the wheel multiplier; `1.0` disables acceleration.
+    pub max_accel: f32,
+}
+
+impl Default for SmoothScroll {
+    fn default() -> Self {
+        Self {
+            duration_ms: 120,
+            accel_window_ms: 50,
+            accel_step: 0.5,
+            max_accel: 4.0,
+        }
+    }
+}
+
+/// Drives [`SmoothScroll`] for one [`ViewportState`].
+///
+/// [`Self::wheel`] and [`Self::scroll_to`] set a target offset, and [`Self::tick`] (called on
+/// [`InputEvent::Tick`](crate::input::InputEvent::Tick)) eases `state.y` toward it, so the app
+/// must keep sending ticks while [`Self::is_animating`]. Any other change to `state.y` (a key
+/// binding, a scroll anchor) cancels the animation.
+#[derive(Clone, Debug, Default)]
+pub struct ScrollAnimator {
+    animation: Option<Animation>,
+    /// Time and direction (`true` = down) of the last wheel event.
+    last_wheel: Option<(Instant, bool)>,
+    accel: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Animation {
+    from: f64,
+    to: u32,
+    start: Instant,
+    /// The `y` this animation last wrote; anything else means someone else scrolled.
+    shown: u32,
+}
+
+impl ScrollAnimator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.animation.is_some()
+    }
+
+    /// Where the view is headed: the animation target, or `state.y` when idle.
+    pub fn target(&self, state: &ViewportState) -> u32 {
+        match self.animation {
+            Some(a) if a.shown == state.y => a.to,
+            _ => state.y,
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.animation = None;
+    }
+
+    /// Scrolls `lines` rows (negative = up) for a wheel event received at `now`.
+    ///
+    /// Events that follow each other within [`SmoothScroll::accel_window_ms`] in the same
+    /// direction multiply `lines`, up to [`SmoothScroll::max_accel`].
+    pub fn wheel(
+        &mut self,
+        state: &mut ViewportState,
+        smooth: &SmoothScroll,
+        lines: i32,
+        now: Instant,
+    ) {
+        if lines == 0 {
+            return;
+        }
+        let down = lines > 0;
+        let window = Duration::from_millis(smooth.accel_window_ms);
+        let continues = self
+            .last_wheel
+            .is_some_and(|(t, d)| d == down && now.saturating_duration_since(t) <= window);
+        self.accel = if continues {
+            (self.accel + smooth.accel_step).min(smooth.max_accel.max(1.0))
+        } else {
+            1.0
+        };
+        self.last_wheel = Some((now, down));
+
+        let delta = (lines as f32 * self.accel).round() as i64;
+        let to = (self.target(state) as i64 + delta).clamp(0, state.max_y() as i64);
+        self.scroll_to(state, smooth, to as u32, now);
+    }
+
+    /// Eases `state.y` toward `y` (clamped to the content) starting at `now`.
+    pub fn scroll_to(
+        &mut self,
+        state: &mut ViewportState,
+        smooth: &SmoothScroll,
+        y: u32,
+        now: Instant,
+    ) {
+        let y = y.min(state.max_y());
+        if smooth.duration_ms == 0 {
+            self.animation = None;
+            state.y = y;
+            return;
+        }
+        let from = match self.animation {
+            Some(a) if a.shown == state.y => a.position(now, smooth.duration_ms).0,
+            _ => state.y as f64,
+        };
+        self.animation = Some(Animation {
+            from,
+            to: y,
+            start: now,
+            shown: state.y,
+        });
+    }
+
+    /// Advances the animation to `now`; returns whether `state.y` changed.
+    pub fn tick(&mut self, state: &mut ViewportState, smooth: &SmoothScroll, now: Instant) -> bool {
+        let Some(anim) = self.animation else {
+            return false;
+        };
+        if anim.shown != state.y {
+            self.animation = None;
+            return false;
+        }
+        let (pos, done) = anim.position(now, smooth.duration_ms);
+        let y = (pos.round() as u32).min(state.max_y());
+        self.animation = (!done).then_some(Animation { shown: y, ..anim });
+        let changed = y != state.y;
+        state.y = y;
+        changed
+    }
+}
+
+impl Animation {
+    /// Eased (cubic ease-out) offset at `now`, and whether the target was reached.
+    fn position(&self, now: Instant, duration_ms: u64) -> (f64, bool) {
+        let elapsed = now.saturating_duration_since(self.start).as_secs_f64();
+        let t = if duration_ms == 0 {
+            1.0
+        } else {
+            (elapsed * 1000.0 / duration_ms as f64).min(1.0)
+        };
+        let eased = 1.0 - (1.0 - t).powi(3);
+        (self.from + (self.to as f64 - self.from) * eased, t >= 1.0)
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 pub struct ViewportOptions {
     pub style: Style,
     pub show_scrollbar: bool,
     pub scrollbar_style: Style,
+    pub tabs: TabPolicy,
 }
 
 pub fn render_lines(area: Rect, buf: &mut Buffer, lines: &[String], state: &ViewportState) {
@@ -121,7 +387,7 @@ pub fn render_lines_with_options(
         let idx = (state.y as usize).saturating_add(row as usize);
         buf.set_style(Rect::new(text_area.x, y, text_area.width, 1), options.style);
         if let Some(line) = lines.get(idx) {
-            render::render_str_clipped(
+            render::render_str_clipped_with_tabs(
                 text_area.x,
                 y,
                 state.x,
@@ -129,6 +395,7 @@ pub fn render_lines_with_options(
                 buf,
                 line,
                 options.style,
+                options.tabs,
             );
         }
     }
@@ -159,6 +426,88 @@ mod tests {
         assert_eq!(s.y, 1);
     }
 
+    #[test]
+    fn anchor_offsets_ignore_where_lines_were_broken() {
+        let wide = ["one two three", "four five"];
+        let narrow = ["one two", "three four", "five"];
+        let offset = ScrollAnchor::text_offset(&wide[..1]);
+        assert_eq!(offset, 11);
+        assert_eq!(ScrollAnchor::row_for_offset(narrow, offset), 1);
+        assert_eq!(ScrollAnchor::row_for_offset(narrow, 0), 0);
+        assert_eq!(ScrollAnchor::row_for_offset(narrow, 99), 2);
+
+        let mut s = ViewportState::default();
+        s.set_viewport(10, 2);
+        s.set_content(10, 6);
+        s.to_bottom();
+        let anchor = ScrollAnchor::new(0, 0, &s);
+        assert!(anchor.at_bottom);
+        s.set_content(10, 9);
+        anchor.restore(&mut s, Some(0));
+        assert_eq!(s.y, 7);
+    }
+
+    #[test]
+    fn animator_eases_toward_the_target() {
+        let smooth = SmoothScroll {
+            duration_ms: 100,
+            ..Default::default()
+        };
+        let mut s = ViewportState::default();
+        s.set_viewport(10, 10);
+        s.set_content(10, 100);
+        let mut anim = ScrollAnimator::new();
+        let t0 = Instant::now();
+        anim.scroll_to(&mut s, &smooth, 40, t0);
+        assert_eq!(s.y, 0);
+        assert!(anim.tick(&mut s, &smooth, t0 + Duration::from_millis(50)));
+        assert_eq!(s.y, 35);
+        assert!(anim.tick(&mut s, &smooth, t0 + Duration::from_millis(100)));
+        assert_eq!(s.y, 40);
+        assert!(!anim.is_animating());
+
+        // Scrolling by other means cancels the animation.
+        anim.scroll_to(&mut s, &smooth, 80, t0);
+        s.y = 10;
+        assert!(!anim.tick(&mut s, &smooth, t0 + Duration::from_millis(50)));
+        assert_eq!(s.y, 10);
+    }
+
+    #[test]
+    fn quick_wheel_events_accelerate() {
+        let smooth = SmoothScroll {
+            duration_ms: 0,
+            ..Default::default()
+        };
+        let mut s = ViewportState::default();
+        s.set_viewport(10, 10);
+        s.set_content(10, 1000);
+        let mut anim = ScrollAnimator::new();
+        let t0 = Instant::now();
+        let steps: Vec<u32> = (0..6)
+            .map(|i| {
+                let before = s.y;
+                anim.wheel(&mut s, &smooth, 3, t0 + Duration::from_millis(20 * i));
+                s.y - before
+            })
+            .collect();
+        assert_eq!(steps, [3, 5, 6, 8, 9, 11]);
+        let cap = (3.0 * smooth.max_accel) as u32;
+        anim.wheel(&mut s, &smooth, 3, t0 + Duration::from_millis(120));
+        anim.wheel(&mut s, &smooth, 3, t0 + Duration::from_millis(140));
+        let before = s.y;
+        anim.wheel(&mut s, &smooth, 3, t0 + Duration::from_millis(160));
+        assert_eq!(s.y - before, cap);
+
+        // A pause or a direction change starts a new gesture.
+        let before = s.y;
+        anim.wheel(&mut s, &smooth, -3, t0 + Duration::from_millis(170));
+        assert_eq!(before - s.y, 3);
+        let before = s.y;
+        anim.wheel(&mut s, &smooth, -3, t0 + Duration::from_secs(1));
+        assert_eq!(before - s.y, 3);
+    }
+
     #[test]
     fn slice_by_cols_handles_tabs_and_limits_width() {
         assert_eq!(render::slice_by_cols("\t1", 0, 4), "    ");
@@ -1,3 +1,5 @@
+use crate::component::Component;
+use crate::component::ComponentAction;
 use crate::input::InputEvent;
 use crate::input::KeyCode;
 use crate::input::KeyEvent;
@@ -6,7 +8,6 @@ use crate::theme::Theme;
 use crate::viewport::ViewportState;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Modifier;
 use ratatui::style::Style;
 use std::collections::BTreeSet;
 use std::sync::Arc;
@@ -26,6 +27,12 @@ pub enum VirtualListAction {
     SelectionChanged,
 }
 
+impl ComponentAction for VirtualListAction {
+    fn is_consumed(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+}
+
 /// Options for [`VirtualListView`].
 ///
 /// This widget virtualizes items along the vertical axis using the `virtualizer` crate. You
@@ -41,8 +48,11 @@ pub struct VirtualListViewOptions {
     pub scroll_padding_top: u32,
     pub scroll_padding_bottom: u32,
     pub style: Style,
+    /// Defaults to [`crate::theme::ChromeTheme::scrollbar`] when left as `Style::default()`.
     pub scrollbar_style: Style,
+    /// Patched over [`crate::theme::ChromeTheme::cursor`] (or `cursor_unfocused`).
     pub cursor_style: Style,
+    /// Patched over [`crate::theme::ChromeTheme::selected`].
     pub selected_style: Style,
     pub selection_follows_cursor: bool,
     pub multi_select: bool,
@@ -60,8 +70,8 @@ impl Default for VirtualListViewOptions {
             scroll_padding_bottom: 0,
             style: Style::default(),
             scrollbar_style: Style::default(),
-            cursor_style: Style::default().add_modifier(Modifier::REVERSED),
-            selected_style: Style::default().add_modifier(Modifier::BOLD),
+            cursor_style: Style::default(),
+            selected_style: Style::default(),
             selection_follows_cursor: true,
             multi_select: false,
         }
@@ -100,6 +110,7 @@ pub struct VirtualListView {
     cursor: Option<usize>,
     selection: BTreeSet<usize>,
     selection_anchor: Option<usize>,
+    focused: bool,
 
     content_w: Option<u32>,
     cached_width: Option<u16>,
@@ -121,6 +132,7 @@ impl Default for VirtualListView {
             cursor: None,
             selection: BTreeSet::new(),
             selection_anchor: None,
+            focused: true,
             content_w: None,
             cached_width: None,
             width_cell,
@@ -146,6 +158,16 @@ impl VirtualListView {
         &self.options
     }
 
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Sets focus. While unfocused the cursor is drawn with
+    /// [`crate::theme::ChromeTheme::cursor_unfocused`].
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
     pub fn set_options(&mut self, options: VirtualListViewOptions) {
         self.options = options;
         self.virtualizer.set_overscan(self.options.overscan);
@@ -245,6 +267,11 @@ impl VirtualListView {
     pub fn handle_event(&mut self, event: InputEvent, count: usize) -> VirtualListAction {
         match event {
             InputEvent::Paste(_) => VirtualListAction::None,
+            // Terminal focus is not pane focus; containers call `set_focused` instead.
+            InputEvent::Resize { .. }
+            | InputEvent::Tick(_)
+            | InputEvent::FocusGained
+            | InputEvent::FocusLost => VirtualListAction::None,
             InputEvent::Key(key) => self.handle_key(key, count),
             InputEvent::Mouse(_) => VirtualListAction::None,
         }
@@ -307,8 +334,16 @@ impl VirtualListView {
         };
         buf.set_style(content_area, base_style);
 
-        let cursor_style = self.options.cursor_style.patch(theme.accent);
-        let selected_style = self.options.selected_style.patch(theme.accent);
+        let cursor_style = theme
+            .chrome
+            .cursor_style(self.focused)
+            .patch(self.options.cursor_style);
+        let selected_style = theme.chrome.selected.patch(self.options.selected_style);
+        let scrollbar_style = if self.options.scrollbar_style == Style::default() {
+            theme.chrome.scrollbar
+        } else {
+            self.options.scrollbar_style
+        };
 
         self.virtualizer
             .collect_virtual_items(&mut self.scratch_items);
@@ -378,12 +413,16 @@ impl VirtualListView {
                 Rect::new(sb_x, area.y, 1, area.height),
                 buf,
                 &self.viewport,
-                self.options.scrollbar_style,
+                scrollbar_style,
             );
         }
+        theme.color_profile.apply_to_buffer(buf, area);
     }
 
     fn handle_key(&mut self, key: KeyEvent, count: usize) -> VirtualListAction {
+        if key.is_release() {
+            return VirtualListAction::None;
+        }
         if count == 0 {
             self.cursor = None;
             self.clear_selection();
@@ -593,6 +632,51 @@ impl VirtualListView {
     }
 }
 
+/// A [`VirtualListView`] bundled with its item count and item renderer, usable as a
+/// [`Component`].
+///
+/// The list view itself does not own its items; this adapter supplies the `count` and
+/// `render_item` arguments that [`VirtualListView::handle_event`] and
+/// [`VirtualListView::render`] otherwise take on every call.
+pub struct VirtualListComponent<F> {
+    pub view: VirtualListView,
+    pub count: usize,
+    render_item: F,
+}
+
+impl<F> VirtualListComponent<F>
+where
+    F: FnMut(Rect, VirtualListItemContext, &mut Buffer, &Theme) -> Option<u32>,
+{
+    pub fn new(view: VirtualListView, count: usize, render_item: F) -> Self {
+        Self {
+            view,
+            count,
+            render_item,
+        }
+    }
+}
+
+impl<F> Component for VirtualListComponent<F>
+where
+    F: FnMut(Rect, VirtualListItemContext, &mut Buffer, &Theme) -> Option<u32>,
+{
+    type Action = VirtualListAction;
+
+    fn handle_event(&mut self, _area: Rect, event: InputEvent) -> VirtualListAction {
+        self.view.handle_event(event, self.count)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        self.view
+            .render(area, buf, theme, self.count, &mut self.render_item);
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.view.set_focused(focused);
+    }
+}
+
 fn clamp_cursor(cursor: Option<usize>, count: usize) -> Option<usize> {
     match cursor {
         None => None,
@@ -613,8 +697,7 @@ mod tests {
     fn key_shift(code: KeyCode) -> KeyEvent {
         KeyEvent::new(code).with_modifiers(KeyModifiers {
             shift: true,
-            ctrl: false,
-            alt: false,
+            ..KeyModifiers::none()
         })
     }
 
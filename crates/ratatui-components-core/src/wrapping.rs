@@ -1,22 +1,84 @@
-use unicode_width::UnicodeWidthChar;
+use std::collections::VecDeque;
+use std::ops::Range;
+
 use unicode_width::UnicodeWidthStr;
 
+use crate::grapheme;
+use crate::tabs::TabPolicy;
+use crate::viewport::ScrollAnchor;
+use crate::viewport::ViewportState;
+
+/// The usual continuation glyph for [`WrapCache::set_continuation_marker`].
+pub const CONTINUATION_MARKER: &str = "↪";
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum WrapMode {
     #[default]
     None,
+    /// Break at whitespace and after punctuation; words wider than the line are split.
     Word,
+    /// Break at any grapheme cluster boundary, keeping whitespace (logs, code).
+    Char,
+}
+
+/// How continuation lines of a wrapped source line are indented.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum HangingIndent {
+    #[default]
+    None,
+    /// A fixed number of columns.
+    Fixed(u16),
+    /// Align under the text after leading whitespace and a list marker (`- `, `* `, `+ `, `1. `,
+    /// `1) `).
+    Auto,
+    /// Align under the text following the first occurrence of a delimiter, e.g. `"] "` for
+    /// `[INFO] message` log lines. Lines without the delimiter are not indented.
+    After(String),
+}
+
+/// Where a wrapped line comes from, for hit-testing.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WrappedLine {
+    /// Index of the source line (see [`WrapCache::first_source_index`]).
+    pub src: usize,
+    /// Byte range of the source line shown on this line. A tab split across two wrapped lines
+    /// belongs to both.
+    pub bytes: Range<usize>,
+    /// Display column range of the source line (tabs expanded) shown on this line.
+    pub cols: Range<usize>,
+    /// Width of the indent/continuation-marker prefix in front of the text (0 on first lines).
+    pub prefix_cols: u16,
+    /// Columns of this wrapped line (prefix included) where a tab starts.
+    ///
+    /// The wrapped text holds spaces for tabs; draw [`TabPolicy::glyph`] at these columns with
+    /// [`crate::render::render_tab_glyphs`].
+    pub tab_cols: Vec<u32>,
 }
 
+/// Wraps source lines to a width and maps the wrapped rows back to the source.
+///
+/// Source lines are numbered from the first line added since the last [`Self::set_content`];
+/// trimming the front (see [`Self::set_max_lines`]) does not renumber the lines that are kept, so
+/// `raw_lines()[i]` is source line `first_source_index() + i`.
 #[derive(Clone, Debug, Default)]
 pub struct WrapCache {
-    raw: Vec<String>,
-    wrapped: Vec<String>,
-    wrapped_src_idx: Vec<usize>,
+    raw: VecDeque<String>,
+    /// Source index of `raw[0]`; grows as the front is trimmed.
+    base: usize,
+    wrapped: VecDeque<String>,
+    wrapped_src_idx: VecDeque<usize>,
+    line_map: VecDeque<WrappedLine>,
+    /// `(source index, widest wrapped row)` with strictly decreasing widths: the front is the
+    /// widest line still kept (a sliding-window maximum).
+    widest: VecDeque<(usize, u32)>,
     wrap_cols: Option<u16>,
     mode: WrapMode,
-    content_w: u16,
-    content_h: u16,
+    hanging_indent: HangingIndent,
+    continuation_marker: Option<String>,
+    max_lines: Option<usize>,
+    tabs: TabPolicy,
+    content_w: u32,
+    content_h: u32,
 }
 
 impl WrapCache {
@@ -25,7 +87,43 @@ impl WrapCache {
     }
 
     pub fn set_content(&mut self, lines: Vec<String>) {
-        self.raw = lines;
+        self.raw = lines.into();
+        self.base = 0;
+        self.invalidate();
+        self.trim_front();
+    }
+
+    /// Appends source lines, wrapping only the new ones if the cache is already built.
+    ///
+    /// With [`Self::set_max_lines`], the oldest lines are then dropped.
+    pub fn append_lines(&mut self, lines: impl IntoIterator<Item = String>) {
+        let first_new = self.end_source_index();
+        self.raw.extend(lines);
+        if self.wrap_cols.is_some() {
+            let width = self.wrap_width();
+            for src in first_new..self.end_source_index() {
+                self.wrap_line(src, width);
+            }
+            self.update_metrics();
+        }
+        self.trim_front();
+    }
+
+    /// Keeps at most `max` source lines, dropping the oldest ones (a ring buffer for log tails).
+    pub fn set_max_lines(&mut self, max: Option<usize>) {
+        self.max_lines = max;
+        self.trim_front();
+    }
+
+    pub fn max_lines(&self) -> Option<usize> {
+        self.max_lines
+    }
+
+    pub fn set_tab_policy(&mut self, tabs: TabPolicy) {
+        if self.tabs == tabs {
+            return;
+        }
+        self.tabs = tabs;
         self.invalidate();
     }
 
@@ -37,6 +135,25 @@ impl WrapCache {
         self.invalidate();
     }
 
+    pub fn set_hanging_indent(&mut self, indent: HangingIndent) {
+        if self.hanging_indent == indent {
+            return;
+        }
+        self.hanging_indent = indent;
+        self.invalidate();
+    }
+
+    /// Sets a glyph (e.g. [`CONTINUATION_MARKER`]) drawn in the gutter of continuation lines.
+    ///
+    /// The marker and a space sit inside the hanging indent; the indent grows if they do not fit.
+    pub fn set_continuation_marker(&mut self, marker: Option<String>) {
+        if self.continuation_marker == marker {
+            return;
+        }
+        self.continuation_marker = marker;
+        self.invalidate();
+    }
+
     pub fn set_width(&mut self, width: u16) {
         if self.wrap_cols == Some(width) {
             return;
@@ -45,145 +162,400 @@ impl WrapCache {
         self.rebuild();
     }
 
-    pub fn raw_lines(&self) -> &[String] {
+    pub fn raw_lines(&self) -> &VecDeque<String> {
         &self.raw
     }
 
-    pub fn wrapped_lines(&self) -> &[String] {
+    /// Source index of the oldest kept line, `raw_lines()[0]`.
+    pub fn first_source_index(&self) -> usize {
+        self.base
+    }
+
+    /// The source line with index `src`, if it has not been trimmed.
+    pub fn raw_line(&self, src: usize) -> Option<&str> {
+        self.raw
+            .get(src.checked_sub(self.base)?)
+            .map(String::as_str)
+    }
+
+    pub fn wrapped_lines(&self) -> &VecDeque<String> {
         &self.wrapped
     }
 
-    pub fn wrapped_src_indices(&self) -> &[usize] {
+    pub fn wrapped_src_indices(&self) -> &VecDeque<usize> {
         &self.wrapped_src_idx
     }
 
-    pub fn content_width(&self) -> u16 {
+    /// Source mapping for each entry of [`Self::wrapped_lines`].
+    pub fn line_map(&self) -> &VecDeque<WrappedLine> {
+        &self.line_map
+    }
+
+    /// Maps a cell of the wrapped output to `(source line, byte offset)`.
+    ///
+    /// Cells inside a continuation prefix map to the start of the line's text; cells past the
+    /// end map to the end of the text shown on that line.
+    pub fn source_position(&self, row: usize, col: u16) -> Option<(usize, usize)> {
+        let info = self.line_map.get(row)?;
+        let raw = self.raw_line(info.src)?;
+        let target = info.cols.start + col.saturating_sub(info.prefix_cols) as usize;
+        let hit = units(raw, self.tabs)
+            .into_iter()
+            .filter(|u| !u.hard_break && info.bytes.contains(&u.byte) && u.col >= info.cols.start)
+            .find(|u| target < u.col + u.width.max(1))
+            .map_or(info.bytes.end, |u| u.byte);
+        Some((info.src, hit))
+    }
+
+    /// Records the top row of `state` as a source line and byte offset.
+    ///
+    /// Capture it before changing the width or the options, and pass it to [`Self::anchor_row`]
+    /// once the lines are rewrapped. Anchors survive front trimming (see [`Self::set_max_lines`])
+    /// unless their own line is dropped.
+    pub fn scroll_anchor(&self, state: &ViewportState) -> Option<ScrollAnchor> {
+        let info = self.line_map.get(state.y as usize)?;
+        Some(ScrollAnchor::new(info.src, info.bytes.start, state))
+    }
+
+    /// The wrapped row showing `anchor`: the last row of its source line starting at or before
+    /// the anchor's byte offset.
+    pub fn anchor_row(&self, anchor: &ScrollAnchor) -> Option<u32> {
+        let start = self
+            .wrapped_src_idx
+            .partition_point(|&src| src < anchor.src);
+        let end = self
+            .wrapped_src_idx
+            .partition_point(|&src| src <= anchor.src);
+        if start == end {
+            return None;
+        }
+        let n = self
+            .line_map
+            .range(start..end)
+            .take_while(|info| info.bytes.start <= anchor.offset)
+            .count()
+            .max(1);
+        Some((start + n - 1) as u32)
+    }
+
+    pub fn content_width(&self) -> u32 {
         self.content_w
     }
 
-    pub fn content_height(&self) -> u16 {
+    pub fn content_height(&self) -> u32 {
         self.content_h
     }
 
     fn invalidate(&mut self) {
         self.wrapped.clear();
         self.wrapped_src_idx.clear();
+        self.line_map.clear();
+        self.widest.clear();
         self.content_w = 0;
         self.content_h = 0;
         self.wrap_cols = None;
     }
 
     fn rebuild(&mut self) {
-        let Some(width) = self.wrap_cols else {
+        if self.wrap_cols.is_none() {
             return;
-        };
-        match self.mode {
-            WrapMode::None => self.no_wrap(),
-            WrapMode::Word => self.word_wrap(width),
         }
-        self.content_h = self.wrapped.len() as u16;
-        self.content_w = self
-            .wrapped
-            .iter()
-            .map(|l| UnicodeWidthStr::width(l.as_str()) as u16)
-            .max()
-            .unwrap_or(0);
+        self.wrapped.clear();
+        self.wrapped_src_idx.clear();
+        self.line_map.clear();
+        self.widest.clear();
+        let width = self.wrap_width();
+        for src in self.base..self.end_source_index() {
+            self.wrap_line(src, width);
+        }
+        self.update_metrics();
     }
 
-    fn no_wrap(&mut self) {
-        self.wrapped = self
-            .raw
-            .iter()
-            .map(|l| normalize_tabs(l))
-            .collect::<Vec<_>>();
-        self.wrapped_src_idx = (0..self.raw.len()).collect();
+    /// One past the newest source index.
+    fn end_source_index(&self) -> usize {
+        self.base + self.raw.len()
     }
 
-    fn word_wrap(&mut self, width: u16) {
-        if width == 0 {
-            self.no_wrap();
-            return;
-        }
-
-        let max_cols = width as usize;
-        let mut out: Vec<String> = Vec::new();
-        let mut out_idx: Vec<usize> = Vec::new();
+    fn update_metrics(&mut self) {
+        self.content_h = self.wrapped.len() as u32;
+        self.content_w = self.widest.front().map_or(0, |&(_, w)| w);
+    }
 
-        for (raw_idx, raw) in self.raw.iter().enumerate() {
-            let raw = normalize_tabs(raw);
-            if raw.is_empty() {
-                out.push(String::new());
-                out_idx.push(raw_idx);
-                continue;
-            }
+    /// The column limit lines are wrapped at, or `None` when they are not wrapped.
+    fn wrap_width(&self) -> Option<usize> {
+        match (self.mode, self.wrap_cols) {
+            (WrapMode::Word | WrapMode::Char, Some(width)) if width > 0 => Some(width as usize),
+            _ => None,
+        }
+    }
 
-            let mut line = String::new();
-            let mut line_cols = 0usize;
-            let mut last_soft_idx: Option<usize> = None;
-
-            for ch in raw.chars() {
-                if ch == '\n' {
-                    out.push(std::mem::take(&mut line));
-                    out_idx.push(raw_idx);
-                    line_cols = 0;
-                    last_soft_idx = None;
-                    continue;
-                }
+    fn trim_front(&mut self) {
+        let Some(max) = self.max_lines else {
+            return;
+        };
+        let drop = self.raw.len().saturating_sub(max);
+        if drop == 0 {
+            return;
+        }
+        self.raw.drain(..drop);
+        self.base += drop;
+        let dropped = self.wrapped_src_idx.partition_point(|&src| src < self.base);
+        self.wrapped.drain(..dropped);
+        self.wrapped_src_idx.drain(..dropped);
+        self.line_map.drain(..dropped);
+        while self.widest.front().is_some_and(|&(src, _)| src < self.base) {
+            self.widest.pop_front();
+        }
+        self.update_metrics();
+    }
 
-                let w = UnicodeWidthChar::width(ch).unwrap_or(0);
-                if line_cols.saturating_add(w) > max_cols {
-                    if let Some(split) = last_soft_idx {
-                        let (prefix, rest) = line.split_at(split);
-                        out.push(prefix.trim_end().to_string());
-                        out_idx.push(raw_idx);
-                        line = rest.trim_start().to_string();
-                        last_soft_idx = None;
-                    } else if !line.is_empty() {
-                        out.push(std::mem::take(&mut line));
-                        out_idx.push(raw_idx);
-                    }
+    fn wrap_line(&mut self, src: usize, width: Option<usize>) {
+        let raw = &self.raw[src - self.base];
+        let units = units(raw, self.tabs);
+        let mut wrapped = Vec::new();
+        // Embedded newlines are hard breaks: each piece starts a fresh, non-continuation line.
+        let mut seg_byte = 0;
+        for seg in units.split(|u| u.hard_break) {
+            let end = seg.last().map_or(seg_byte, |u| u.byte + u.len);
+            seg_byte = end + if raw[end..].starts_with("\r\n") { 2 } else { 1 };
+            let (prefix, ranges) = match width {
+                None => (String::new(), vec![(0, seg.len())]),
+                Some(width) => {
+                    let prefix = self.continuation_prefix(seg, width);
+                    let cont = width - UnicodeWidthStr::width(prefix.as_str());
+                    let ranges = break_segment(seg, width, cont, self.mode == WrapMode::Word);
+                    (prefix, ranges)
                 }
-
-                if line.is_empty() && ch.is_whitespace() {
-                    continue;
+            };
+            for (i, (a, b)) in ranges.into_iter().enumerate() {
+                let mut text = String::new();
+                let mut prefix_cols = 0;
+                if i > 0 {
+                    text.push_str(&prefix);
+                    prefix_cols = UnicodeWidthStr::width(prefix.as_str()) as u16;
                 }
-
-                if ch.is_whitespace()
-                    || matches!(
-                        ch,
-                        ',' | ';' | '.' | ':' | ')' | ']' | '}' | '|' | '/' | '?' | '!' | '-' | '_'
-                    )
-                {
-                    last_soft_idx = Some(line.len());
+                let mut tab_cols = Vec::new();
+                for u in &seg[a..b] {
+                    if u.tab_start {
+                        tab_cols.push(prefix_cols as u32 + (u.col - seg[a].col) as u32);
+                    }
+                    text.push_str(u.text);
                 }
-
-                line.push(ch);
-                line_cols = UnicodeWidthStr::width(line.as_str());
+                let info = match &seg[a..b] {
+                    [] => {
+                        let (byte, col) = seg.get(a).map_or((end, 0), |u| (u.byte, u.col));
+                        WrappedLine {
+                            src,
+                            bytes: byte..byte,
+                            cols: col..col,
+                            prefix_cols,
+                            tab_cols,
+                        }
+                    }
+                    [first, .., last] | [first @ last] => WrappedLine {
+                        src,
+                        bytes: first.byte..last.byte + last.len,
+                        cols: first.col..last.col + last.width,
+                        prefix_cols,
+                        tab_cols,
+                    },
+                };
+                wrapped.push((text, info));
             }
+        }
+        let line_w = wrapped
+            .iter()
+            .map(|(text, _)| UnicodeWidthStr::width(text.as_str()) as u32)
+            .max()
+            .unwrap_or(0);
+        while self.widest.back().is_some_and(|&(_, w)| w <= line_w) {
+            self.widest.pop_back();
+        }
+        self.widest.push_back((src, line_w));
+        for (text, info) in wrapped {
+            self.wrapped.push_back(text);
+            self.wrapped_src_idx.push_back(src);
+            self.line_map.push_back(info);
+        }
+    }
 
-            if !line.is_empty() {
-                out.push(line);
-                out_idx.push(raw_idx);
+    /// Indent plus marker for continuation lines of `seg`; empty when it would not fit.
+    fn continuation_prefix(&self, seg: &[Unit<'_>], width: usize) -> String {
+        let indent = match &self.hanging_indent {
+            HangingIndent::None => 0,
+            HangingIndent::Fixed(n) => *n as usize,
+            HangingIndent::Auto => auto_indent(seg),
+            HangingIndent::After(delim) => {
+                let text: String = seg.iter().map(|u| u.text).collect();
+                text.find(delim.as_str())
+                    .map_or(0, |i| UnicodeWidthStr::width(&text[..i + delim.len()]))
             }
+        };
+        let mut prefix = String::new();
+        if let Some(marker) = &self.continuation_marker {
+            prefix.push_str(marker);
+            prefix.push(' ');
         }
+        let pad = indent.saturating_sub(UnicodeWidthStr::width(prefix.as_str()));
+        prefix.extend(std::iter::repeat_n(' ', pad));
+        // Keep room for at least one wide cluster after the prefix.
+        if UnicodeWidthStr::width(prefix.as_str()) + 2 > width {
+            prefix.clear();
+        }
+        prefix
+    }
+}
 
-        self.wrapped = out;
-        self.wrapped_src_idx = out_idx;
+/// One display unit of a source line: a grapheme cluster, or one cell of an expanded tab.
+struct Unit<'a> {
+    text: &'a str,
+    /// The first cell of an expanded tab.
+    tab_start: bool,
+    byte: usize,
+    len: usize,
+    col: usize,
+    width: usize,
+    hard_break: bool,
+}
+
+fn units(raw: &str, tabs: TabPolicy) -> Vec<Unit<'_>> {
+    let mut col = 0;
+    let mut out = Vec::new();
+    for (byte, g) in grapheme::grapheme_indices(raw) {
+        let hard_break = g == "\n" || g == "\r\n";
+        if hard_break {
+            col = 0;
+        }
+        if g == "\t" {
+            for i in 0..tabs.advance(col) {
+                out.push(Unit {
+                    text: " ",
+                    tab_start: i == 0,
+                    byte,
+                    len: 1,
+                    col,
+                    width: 1,
+                    hard_break,
+                });
+                col += 1;
+            }
+            continue;
+        }
+        let width = if hard_break { 0 } else { grapheme::width(g) };
+        out.push(Unit {
+            text: g,
+            tab_start: false,
+            byte,
+            len: g.len(),
+            col,
+            width,
+            hard_break,
+        });
+        col += width;
     }
+    out
 }
 
-fn normalize_tabs(s: &str) -> String {
-    if s.contains('\t') {
-        s.replace('\t', "    ")
+fn auto_indent(seg: &[Unit<'_>]) -> usize {
+    let lead = seg
+        .iter()
+        .take_while(|u| grapheme::is_whitespace(u.text))
+        .count();
+    let rest = &seg[lead..];
+    let digits = rest
+        .iter()
+        .take_while(|u| u.text.len() == 1 && u.text.as_bytes()[0].is_ascii_digit())
+        .count();
+    let marker = match rest.first().map(|u| u.text) {
+        Some("-" | "*" | "+") => 1,
+        _ if digits > 0 && matches!(rest.get(digits).map(|u| u.text), Some("." | ")")) => {
+            digits + 1
+        }
+        _ => 0,
+    };
+    let marker = if marker > 0 && rest.get(marker).is_some_and(|u| u.text == " ") {
+        marker + 1
     } else {
-        s.to_string()
+        0
+    };
+    seg[..lead + marker].iter().map(|u| u.width).sum()
+}
+
+fn is_soft_break_after(g: &str) -> bool {
+    matches!(
+        g,
+        "," | ";" | "." | ":" | ")" | "]" | "}" | "|" | "/" | "?" | "!" | "-" | "_"
+    )
+}
+
+/// Splits `seg` into unit ranges: the first line gets `first` columns, the rest `cont`.
+fn break_segment(seg: &[Unit<'_>], first: usize, cont: usize, word: bool) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    loop {
+        let avail = if out.is_empty() { first } else { cont };
+        if word && !out.is_empty() {
+            while start < seg.len() && grapheme::is_whitespace(seg[start].text) {
+                start += 1;
+            }
+        }
+        if start == seg.len() && !out.is_empty() {
+            break;
+        }
+
+        let mut end = start;
+        let mut cols = 0;
+        let mut soft = None;
+        let mut seen_text = false;
+        while end < seg.len() {
+            let u = &seg[end];
+            if cols + u.width > avail && end > start {
+                break;
+            }
+            let ws = grapheme::is_whitespace(u.text);
+            if word && ws && seen_text {
+                soft = Some(end);
+            }
+            seen_text |= !ws;
+            cols += u.width;
+            end += 1;
+            if word && is_soft_break_after(u.text) {
+                soft = Some(end);
+            }
+        }
+        if end < seg.len()
+            && let Some(soft) = soft.filter(|&s| s > start)
+        {
+            end = soft;
+        }
+
+        let mut line_end = end;
+        if word && end < seg.len() {
+            while line_end > start && grapheme::is_whitespace(seg[line_end - 1].text) {
+                line_end -= 1;
+            }
+        }
+        out.push((start, line_end));
+        if end == seg.len() {
+            break;
+        }
+        start = end;
     }
+    out
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn wrap(lines: &[&str], mode: WrapMode, width: u16) -> WrapCache {
+        let mut cache = WrapCache::new();
+        cache.set_content(lines.iter().map(|l| l.to_string()).collect());
+        cache.set_mode(mode);
+        cache.set_width(width);
+        cache
+    }
+
     #[test]
     fn word_wrap_splits_and_maps_source_indices() {
         let mut cache = WrapCache::new();
@@ -206,7 +578,15 @@ mod tests {
         cache.set_content(vec!["a\tb".to_string()]);
         cache.set_mode(WrapMode::None);
         cache.set_width(80);
-        assert_eq!(cache.wrapped_lines(), &["a    b".to_string()]);
+        assert_eq!(cache.wrapped_lines(), &["a   b".to_string()]);
+
+        assert_eq!(cache.line_map()[0].tab_cols, vec![1]);
+
+        // The glyph is left to the renderer; the stored text keeps spaces.
+        cache.set_tab_policy(TabPolicy::new(2).with_glyph(Some('→')));
+        cache.set_width(80);
+        assert_eq!(cache.wrapped_lines(), &["a b".to_string()]);
+        assert_eq!(cache.line_map()[0].tab_cols, vec![1]);
     }
 
     #[test]
@@ -218,4 +598,268 @@ mod tests {
         cache.set_width(10);
         assert_eq!(cache.content_height(), 1);
     }
+
+    #[test]
+    fn word_wrap_keeps_grapheme_clusters_intact() {
+        let mut cache = WrapCache::new();
+        cache.set_content(vec![
+            "👨\u{200d}👩\u{200d}👧🇯🇵e\u{301}\u{1100}\u{1161}".to_string(),
+        ]);
+        cache.set_mode(WrapMode::Word);
+        cache.set_width(3);
+        assert_eq!(
+            cache.wrapped_lines(),
+            &[
+                "👨\u{200d}👩\u{200d}👧".to_string(),
+                "🇯🇵e\u{301}".to_string(),
+                "\u{1100}\u{1161}".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn word_wrap_breaks_after_punctuation() {
+        let cache = wrap(&["see foo/bar-baz"], WrapMode::Word, 12);
+        assert_eq!(cache.wrapped_lines(), &["see foo/bar-", "baz"]);
+    }
+
+    #[test]
+    fn char_wrap_keeps_whitespace() {
+        let cache = wrap(&["ab  cd ef"], WrapMode::Char, 4);
+        assert_eq!(cache.wrapped_lines(), &["ab  ", "cd e", "f"]);
+        assert_eq!(
+            cache.line_map()[1],
+            WrappedLine {
+                src: 0,
+                bytes: 4..8,
+                cols: 4..8,
+                prefix_cols: 0,
+                tab_cols: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn embedded_newlines_are_hard_breaks() {
+        let cache = wrap(&["ab\r\n\ncd"], WrapMode::Word, 10);
+        assert_eq!(cache.wrapped_lines(), &["ab", "", "cd"]);
+        let bytes: Vec<_> = cache.line_map().iter().map(|l| l.bytes.clone()).collect();
+        assert_eq!(bytes, vec![0..2, 4..4, 5..7]);
+    }
+
+    #[test]
+    fn hanging_indent_aligns_continuations() {
+        let mut cache = WrapCache::new();
+        cache.set_content(vec![
+            "  - alpha beta gamma".to_string(),
+            "[WARN] disk almost full".to_string(),
+        ]);
+        cache.set_mode(WrapMode::Word);
+        cache.set_hanging_indent(HangingIndent::Auto);
+        cache.set_width(12);
+        assert_eq!(
+            cache.wrapped_lines(),
+            &[
+                "  - alpha",
+                "    beta",
+                "    gamma",
+                "[WARN] disk",
+                "almost full"
+            ]
+        );
+
+        cache.set_hanging_indent(HangingIndent::After("] ".to_string()));
+        cache.set_width(14);
+        assert!(cache.wrapped_lines().range(2..).eq([
+            "[WARN] disk",
+            "       almost",
+            "       full"
+        ]));
+        assert_eq!(cache.line_map()[3].prefix_cols, 7);
+        assert_eq!(cache.line_map()[3].bytes, 12..18);
+    }
+
+    #[test]
+    fn continuation_marker_sits_in_the_indent() {
+        let mut cache = WrapCache::new();
+        cache.set_content(vec!["abcdefgh".to_string()]);
+        cache.set_mode(WrapMode::Char);
+        cache.set_continuation_marker(Some(CONTINUATION_MARKER.to_string()));
+        cache.set_hanging_indent(HangingIndent::Fixed(3));
+        cache.set_width(5);
+        assert_eq!(cache.wrapped_lines(), &["abcde", "↪  fg", "↪  h"]);
+        assert_eq!(cache.content_width(), 5);
+    }
+
+    #[test]
+    fn source_position_maps_wrapped_cells_back() {
+        let mut cache = wrap(&["a\t\tb", "x\u{301}yz"], WrapMode::Char, 4);
+        cache.set_continuation_marker(Some(CONTINUATION_MARKER.to_string()));
+        cache.set_width(4);
+        assert_eq!(
+            cache.wrapped_lines(),
+            &["a   ", "↪   ", "↪   ", "↪ b", "x\u{301}yz"]
+        );
+        // A tab's glyph column is only on the line where it starts, after the prefix.
+        let tab_cols: Vec<_> = cache
+            .line_map()
+            .iter()
+            .map(|l| l.tab_cols.clone())
+            .collect();
+        assert_eq!(tab_cols, vec![vec![1], vec![2], vec![], vec![], vec![]]);
+        // Cells of an expanded tab map to the tab, on every line it spans.
+        assert_eq!(cache.source_position(0, 3), Some((0, 1)));
+        assert_eq!(cache.source_position(1, 2), Some((0, 2)));
+        assert_eq!(cache.source_position(2, 3), Some((0, 2)));
+        assert_eq!(cache.source_position(2, 0), Some((0, 2)));
+        assert_eq!(cache.source_position(3, 2), Some((0, 3)));
+        assert_eq!(cache.source_position(4, 1), Some((1, 3)));
+        assert_eq!(cache.source_position(4, 9), Some((1, 5)));
+        assert_eq!(cache.source_position(9, 0), None);
+    }
+
+    #[test]
+    fn append_lines_wraps_only_new_lines() {
+        let mut cache = wrap(&["one two"], WrapMode::Word, 4);
+        cache.append_lines(["three four".to_string(), "x".to_string()]);
+        assert_eq!(
+            cache.wrapped_lines(),
+            &["one", "two", "thre", "e", "four", "x"]
+        );
+        assert_eq!(cache.wrapped_src_indices(), &[0, 0, 1, 1, 1, 2]);
+        assert_eq!(cache.content_height(), 6);
+        assert_eq!(cache.content_width(), 4);
+
+        let rebuilt = wrap(&["one two", "three four", "x"], WrapMode::Word, 4);
+        assert_eq!(cache.wrapped_lines(), rebuilt.wrapped_lines());
+        assert_eq!(cache.line_map(), rebuilt.line_map());
+    }
+
+    #[test]
+    fn max_lines_trims_the_front() {
+        let mut cache = wrap(&["a very long line", "b"], WrapMode::Word, 6);
+        cache.set_max_lines(Some(2));
+        cache.append_lines(["c".to_string()]);
+        assert_eq!(cache.raw_lines(), &["b", "c"]);
+        assert_eq!(cache.first_source_index(), 1);
+        assert_eq!(cache.raw_line(2), Some("c"));
+        assert_eq!(cache.raw_line(0), None);
+        assert_eq!(cache.wrapped_lines(), &["b", "c"]);
+        assert_eq!(cache.wrapped_src_indices(), &[1, 2]);
+        assert_eq!(cache.line_map()[1].src, 2);
+        assert_eq!(cache.content_width(), 1);
+        assert_eq!(cache.content_height(), 2);
+    }
+
+    #[test]
+    fn trimming_keeps_source_indices_and_the_widest_kept_line() {
+        let mut cache = wrap(&["wide line", "mid", "x"], WrapMode::None, 80);
+        cache.set_max_lines(Some(3));
+        let mut state = ViewportState::default();
+        state.set_viewport(10, 1);
+        state.set_content(10, cache.content_height());
+        state.y = 2;
+        let anchor = cache.scroll_anchor(&state).expect("row exists");
+        assert_eq!(cache.content_width(), 9);
+
+        cache.append_lines(["y".to_string()]);
+        assert_eq!(cache.content_width(), 3);
+        cache.append_lines(["z".to_string()]);
+        assert_eq!(cache.content_width(), 1);
+        assert_eq!(cache.anchor_row(&anchor), Some(0));
+        assert_eq!(cache.wrapped_lines(), &["x", "y", "z"]);
+
+        cache.append_lines(["abcd".to_string()]);
+        assert_eq!(cache.content_width(), 4);
+        assert_eq!(cache.anchor_row(&anchor), None);
+    }
+
+    #[test]
+    fn scroll_anchor_survives_rewrapping() {
+        let mut cache = wrap(
+            &["intro", "alpha beta gamma delta", "end"],
+            WrapMode::Word,
+            6,
+        );
+        let mut state = ViewportState::default();
+        state.set_viewport(6, 2);
+        state.set_content(6, cache.content_height());
+        state.y = 3;
+        assert_eq!(cache.wrapped_lines()[3], "gamma");
+
+        let anchor = cache.scroll_anchor(&state).expect("row exists");
+        assert_eq!((anchor.src, anchor.offset), (1, 11));
+        cache.set_width(12);
+        let row = cache.anchor_row(&anchor).expect("source line exists");
+        assert_eq!(cache.wrapped_lines()[row as usize], "gamma delta");
+        assert_eq!(cache.anchor_row(&ScrollAnchor::new(7, 0, &state)), None);
+    }
+
+    #[test]
+    fn metrics_do_not_overflow_u16() {
+        let mut cache = WrapCache::new();
+        cache.set_mode(WrapMode::Char);
+        cache.set_width(1);
+        cache.append_lines(std::iter::repeat_n("ab".to_string(), 40_000));
+        assert_eq!(cache.content_height(), 80_000);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn word_wrap_fits_width_without_splitting_clusters(
+            text in crate::grapheme::fixtures::text(),
+            width in 2u16..16,
+        ) {
+            let mut cache = WrapCache::new();
+            cache.set_content(vec![text.clone()]);
+            cache.set_mode(WrapMode::Word);
+            cache.set_width(width);
+
+            let visible = |s: &str| -> Vec<String> {
+                grapheme::graphemes(s)
+                    .filter(|g| !grapheme::is_whitespace(g))
+                    .map(str::to_string)
+                    .collect()
+            };
+            let mut wrapped_visible = Vec::new();
+            for line in cache.wrapped_lines() {
+                proptest::prop_assert!(UnicodeWidthStr::width(line.as_str()) <= width as usize);
+                wrapped_visible.extend(visible(line));
+            }
+            proptest::prop_assert_eq!(wrapped_visible, visible(&text));
+        }
+
+        #[test]
+        fn wrapped_lines_map_back_to_source(
+            text in crate::grapheme::fixtures::text(),
+            width in 2u16..16,
+            char_mode in proptest::bool::ANY,
+            indent in 0u16..6,
+        ) {
+            let mut cache = WrapCache::new();
+            cache.set_content(vec![text.clone()]);
+            cache.set_mode(if char_mode { WrapMode::Char } else { WrapMode::Word });
+            cache.set_hanging_indent(HangingIndent::Fixed(indent));
+            cache.set_continuation_marker(Some(CONTINUATION_MARKER.to_string()));
+            cache.set_width(width);
+
+            let boundary = |b: usize| {
+                b == text.len() || grapheme::grapheme_indices(&text).any(|(i, _)| i == b)
+            };
+            let mut next_col = 0;
+            for (line, info) in cache.wrapped_lines().iter().zip(cache.line_map()) {
+                let line_w = UnicodeWidthStr::width(line.as_str());
+                proptest::prop_assert!(line_w <= width as usize);
+                proptest::prop_assert_eq!(info.cols.len(), line_w - info.prefix_cols as usize);
+                proptest::prop_assert!(boundary(info.bytes.start) && boundary(info.bytes.end));
+                if char_mode {
+                    proptest::prop_assert_eq!(info.cols.start, next_col);
+                }
+                next_col = info.cols.end;
+            }
+            if char_mode {
+                proptest::prop_assert_eq!(next_col, TabPolicy::default().str_width(&text));
+            }
+        }
+    }
 }
@@ -7,6 +7,7 @@ use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::text::Line;
 use ratatui_components_core::render;
+use ratatui_components_core::text::CodeHighlighter;
 use ratatui_components_core::theme::Theme;
 use ratatui_components_core::viewport::ViewportState;
 use std::hash::Hash;
@@ -106,8 +107,11 @@ pub struct MarkdownStreamView {
 
     render_options: MarkdownViewOptions,
     engine: MarkdownView,
+    highlighter: Option<Arc<dyn CodeHighlighter + Send + Sync>>,
 
     cached_width: Option<u16>,
+    /// Whether the cached lines were laid out for a monochrome theme (rendered unhighlighted).
+    cached_monochrome: bool,
 
     committed_rendered_blocks: usize,
     committed_rendered_lines: Vec<Line<'static>>,
@@ -146,7 +150,9 @@ impl MarkdownStreamView {
             state: DocumentState::new(),
             render_options,
             engine,
+            highlighter: None,
             cached_width: None,
+            cached_monochrome: false,
             committed_rendered_blocks: 0,
             committed_rendered_lines: Vec::new(),
             committed_max_w: 0,
@@ -163,11 +169,9 @@ impl MarkdownStreamView {
         self.pending_key = 0;
     }
 
-    pub fn set_highlighter(
-        &mut self,
-        highlighter: Option<Arc<dyn ratatui_components_core::text::CodeHighlighter + Send + Sync>>,
-    ) {
-        self.engine.set_highlighter(highlighter);
+    pub fn set_highlighter(&mut self, highlighter: Option<Arc<dyn CodeHighlighter + Send + Sync>>) {
+        self.highlighter = highlighter;
+        self.engine.set_highlighter(self.engine_highlighter());
         self.reset_layout_cache();
     }
 
@@ -179,6 +183,7 @@ impl MarkdownStreamView {
         engine_opts.padding_left = 0;
         engine_opts.padding_right = 0;
         self.engine = MarkdownView::with_options(engine_opts);
+        self.engine.set_highlighter(self.engine_highlighter());
 
         self.reset_layout_cache();
     }
@@ -257,9 +262,10 @@ impl MarkdownStreamView {
                 Rect::new(sb_x, area.y, 1, area.height),
                 buf,
                 &self.viewport,
-                theme.text_muted,
+                theme.chrome.scrollbar,
             );
         }
+        theme.color_profile.apply_to_buffer(buf, area);
     }
 
     pub fn total_lines_for_width(&mut self, width: u16, theme: &Theme) -> usize {
@@ -277,6 +283,12 @@ impl MarkdownStreamView {
     }
 
     fn ensure_layout(&mut self, width: u16, theme: &Theme) {
+        let monochrome = theme.is_monochrome();
+        if monochrome != self.cached_monochrome {
+            self.cached_monochrome = monochrome;
+            self.engine.set_highlighter(self.engine_highlighter());
+            self.reset_layout_cache();
+        }
         let width_changed = self.cached_width != Some(width);
         if width_changed {
             self.reset_layout_cache();
@@ -326,6 +338,11 @@ impl MarkdownStreamView {
         self.viewport.set_content(content_w, content_h);
     }
 
+    /// The highlighter for code blocks; monochrome themes skip syntax highlighting.
+    fn engine_highlighter(&self) -> Option<Arc<dyn CodeHighlighter + Send + Sync>> {
+        self.highlighter.clone().filter(|_| !self.cached_monochrome)
+    }
+
     fn render_md_block(&mut self, markdown: &str, width: u16, theme: &Theme) -> Vec<Line<'static>> {
         self.engine.set_markdown(markdown);
         self.engine.lines_for_width(width, theme)
@@ -464,3 +481,45 @@ fn is_fence_closing(line: &str, indent: &str, ch: char, len: usize) -> bool {
     }
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+    use ratatui::style::Style;
+    use ratatui::text::Span;
+
+    struct RgbHighlighter;
+
+    impl CodeHighlighter for RgbHighlighter {
+        fn highlight_lines(
+            &self,
+            _language: Option<&str>,
+            lines: &[&str],
+        ) -> Vec<Vec<Span<'static>>> {
+            let style = Style::default().fg(Color::Rgb(1, 2, 3));
+            lines
+                .iter()
+                .map(|l| vec![Span::styled((*l).to_string(), style)])
+                .collect()
+        }
+    }
+
+    fn highlighted(lines: &[Line<'static>]) -> bool {
+        lines
+            .iter()
+            .flat_map(|l| &l.spans)
+            .any(|s| s.style.fg == Some(Color::Rgb(1, 2, 3)))
+    }
+
+    #[test]
+    fn monochrome_themes_skip_highlighting() {
+        let mut view = MarkdownStreamView::default();
+        view.set_highlighter(Some(Arc::new(RgbHighlighter)));
+        view.append("```rs\nfn main() {}\n```\n\ndone\n");
+
+        assert!(highlighted(&view.snapshot_lines(40, &Theme::default())));
+        assert!(!highlighted(&view.snapshot_lines(40, &Theme::monochrome())));
+        assert!(highlighted(&view.snapshot_lines(40, &Theme::default())));
+    }
+}
@@ -9,28 +9,43 @@ use pulldown_cmark::Tag;
 use pulldown_cmark::TagEnd;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::text::Line;
 use ratatui::text::Span;
 use ratatui::text::Text;
+use ratatui_components_core::component::Component;
+use ratatui_components_core::grapheme;
+use ratatui_components_core::help;
+use ratatui_components_core::help::BindingGroup;
+use ratatui_components_core::input::ClickTracker;
 use ratatui_components_core::input::InputEvent;
+use ratatui_components_core::input::KeyEvent;
 use ratatui_components_core::input::MouseButton;
 use ratatui_components_core::input::MouseEvent;
 use ratatui_components_core::input::MouseEventKind;
+use ratatui_components_core::keymap::ChordMatch;
+use ratatui_components_core::keymap::ChordState;
+use ratatui_components_core::keymap::Keymap;
 use ratatui_components_core::render;
+use ratatui_components_core::scroll::ScrollAction;
 use ratatui_components_core::scroll::ScrollBindings;
+use ratatui_components_core::selection;
 use ratatui_components_core::selection::SelectionAction;
 use ratatui_components_core::selection::SelectionBindings;
+use ratatui_components_core::selection::VisualSelection;
+use ratatui_components_core::selection::VisualStep;
+use ratatui_components_core::tabs::TabPolicy;
 use ratatui_components_core::text::CodeHighlighter;
 use ratatui_components_core::theme::Theme;
+use ratatui_components_core::viewport::ScrollAnchor;
+use ratatui_components_core::viewport::ScrollAnimator;
 use ratatui_components_core::viewport::ViewportState;
 use std::collections::HashMap;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::sync::Arc;
-use unicode_width::UnicodeWidthChar;
+use std::time::Instant;
 use unicode_width::UnicodeWidthStr;
 use url::Url;
 
@@ -145,6 +160,9 @@ pub struct MarkdownViewOptions {
     pub max_sync_highlight_blocks_per_frame: usize,
     pub base_url: Option<String>,
     pub link_destination_style: LinkDestinationStyle,
+    /// How tabs in code blocks are expanded to spaces (the glyph is not drawn); applies to markdown
+    /// set after the change.
+    pub tabs: TabPolicy,
     pub scroll: ScrollBindings,
     pub enable_selection: bool,
     pub selection: SelectionBindings,
@@ -175,6 +193,7 @@ impl Default for MarkdownViewOptions {
             max_sync_highlight_blocks_per_frame: 1,
             base_url: None,
             link_destination_style: LinkDestinationStyle::Paren,
+            tabs: TabPolicy::default(),
             scroll: ScrollBindings::default(),
             enable_selection: true,
             selection: SelectionBindings::default(),
@@ -182,6 +201,16 @@ impl Default for MarkdownViewOptions {
     }
 }
 
+impl MarkdownViewOptions {
+    /// Describes the configured bindings for help screens (see [`help::HelpPanel`]).
+    pub fn help_groups(&self) -> Vec<BindingGroup> {
+        help::view_help_groups(
+            &self.scroll,
+            self.enable_selection.then_some(&self.selection),
+        )
+    }
+}
+
 pub mod document {
     //! A lightweight, reusable markdown rendering core.
     //!
@@ -271,6 +300,8 @@ pub mod document {
         pub max_highlight_lines: usize,
         pub base_url: Option<String>,
         pub link_destination_style: LinkDestinationStyle,
+        /// How tabs in code blocks are expanded (applied during parsing).
+        pub tabs: TabPolicy,
     }
 
     impl Default for MarkdownRenderOptions {
@@ -294,6 +325,7 @@ pub mod document {
                 max_highlight_lines: 200,
                 base_url: None,
                 link_destination_style: LinkDestinationStyle::Paren,
+                tabs: TabPolicy::default(),
             }
         }
     }
@@ -319,6 +351,7 @@ pub mod document {
                 max_highlight_lines: value.max_sync_highlight_lines,
                 base_url: value.base_url.clone(),
                 link_destination_style: value.link_destination_style,
+                tabs: value.tabs,
             }
         }
     }
@@ -353,6 +386,7 @@ pub mod document {
                     code_block_indent_in_blockquote: options.code_block_indent_in_blockquote,
                     footnote_hanging_indent: options.footnote_hanging_indent,
                     base_url: options.base_url.as_deref(),
+                    tabs: options.tabs,
                 },
             );
 
@@ -398,9 +432,10 @@ pub mod document {
                 theme,
                 options.show_code_line_numbers,
                 options.table_style,
+                &mut Vec::new(),
             );
 
-            if let Some(hi) = highlighter {
+            if let Some(hi) = highlighter.filter(|_| !theme.is_monochrome()) {
                 let mut highlight_cache: HashMap<u64, Arc<Vec<Vec<Span<'static>>>>> =
                     HashMap::new();
                 for block in &self.blocks {
@@ -442,7 +477,7 @@ pub mod document {
                     if content.is_empty() {
                         spans.extend(line.spans[code_ref.content_start..].iter().cloned());
                     } else {
-                        spans.extend(patch_spans_style(content, theme.code_inline));
+                        spans.extend(patch_spans_style(content, theme.markdown.code_block));
                     }
                     line.spans = spans;
                     line.code_ref = None;
@@ -460,12 +495,13 @@ pub mod document {
                 .max()
                 .unwrap_or(0);
 
-            let text = Text::from(
+            let mut text = Text::from(
                 rendered
                     .into_iter()
                     .map(|l| Line::from(l.spans))
                     .collect::<Vec<_>>(),
             );
+            theme.color_profile.adapt_text(&mut text);
 
             RenderedMarkdown {
                 text,
@@ -511,14 +547,27 @@ pub struct MarkdownView {
     source: String,
     blocks: Vec<Block>,
     rendered: Vec<RenderedLine>,
+    /// First rendered line of each block in `blocks`.
+    block_starts: Vec<usize>,
     cached_width: Option<u16>,
     pub state: ViewportState,
+    scroll_chord: ChordState,
+    /// Built from `options.scroll` on first use and dropped when the options change.
+    scroll_keymap: Option<Keymap<ScrollAction>>,
+    scroll_animator: ScrollAnimator,
+    clicks: ClickTracker,
     options: MarkdownViewOptions,
     highlighter: Option<Arc<dyn CodeHighlighter + Send + Sync>>,
     highlight_cache: HashMap<u64, Arc<Vec<Vec<Span<'static>>>>>,
     code_block_index: HashMap<u64, usize>,
     selection_anchor: Option<(usize, u32)>,
     selection: Option<((usize, u32), (usize, u32))>,
+    hover_row: Option<u16>,
+    visual: VisualSelection,
+    /// Inverted so the derived `Default` starts focused.
+    unfocused: bool,
+    /// Top line as `(block, visible characters into it)`, restored after the next re-layout.
+    scroll_anchor: Option<ScrollAnchor>,
 }
 
 #[derive(Clone, Debug)]
@@ -541,14 +590,23 @@ impl Clone for MarkdownView {
             source: self.source.clone(),
             blocks: self.blocks.clone(),
             rendered: self.rendered.clone(),
+            block_starts: self.block_starts.clone(),
             cached_width: self.cached_width,
             state: self.state,
+            scroll_chord: ChordState::default(),
+            scroll_keymap: self.scroll_keymap.clone(),
+            scroll_animator: ScrollAnimator::default(),
+            clicks: ClickTracker::default(),
             options: self.options.clone(),
             highlighter: self.highlighter.clone(),
             highlight_cache: self.highlight_cache.clone(),
             code_block_index: self.code_block_index.clone(),
             selection_anchor: None,
             selection: None,
+            hover_row: None,
+            visual: VisualSelection::default(),
+            unfocused: self.unfocused,
+            scroll_anchor: self.scroll_anchor,
         }
     }
 }
@@ -566,7 +624,11 @@ impl MarkdownView {
     }
 
     /// Sets markdown source and reparses blocks.
+    ///
+    /// The top line stays anchored to the same block, so appending to or editing below the
+    /// visible part does not move it; a view scrolled to the bottom stays there.
     pub fn set_markdown(&mut self, input: &str) {
+        self.capture_scroll_anchor();
         self.source = input.to_string();
         self.blocks = parse_markdown_blocks(
             input,
@@ -585,6 +647,7 @@ impl MarkdownView {
                 code_block_indent_in_blockquote: self.options.code_block_indent_in_blockquote,
                 footnote_hanging_indent: self.options.footnote_hanging_indent,
                 base_url: self.options.base_url.as_deref(),
+                tabs: self.options.tabs,
             },
         );
         self.code_block_index.clear();
@@ -631,37 +694,148 @@ impl MarkdownView {
     }
 
     /// Handles an event and returns a [`SelectionAction`] (redraw / copy-on-request).
+    ///
+    /// On [`InputEvent::Resize`] the scroll position is re-anchored after the next re-layout:
+    /// a view scrolled to the bottom stays there, otherwise the text at the top stays on top.
     pub fn handle_event_action(&mut self, event: InputEvent) -> SelectionAction {
         match event {
             InputEvent::Paste(_) => SelectionAction::None,
+            InputEvent::Resize { .. } => {
+                self.capture_scroll_anchor();
+                SelectionAction::Redraw
+            }
+            InputEvent::FocusGained => SelectionAction::None,
+            InputEvent::FocusLost => {
+                // Drop half-typed chords and any in-progress drag; the release may never arrive.
+                self.scroll_chord.clear();
+                self.selection_anchor = None;
+                SelectionAction::Redraw
+            }
+            InputEvent::Tick(now) => {
+                let mut redraw =
+                    self.options
+                        .scroll
+                        .tick(&mut self.scroll_animator, &mut self.state, now);
+                if let ChordMatch::Matched(action) = self
+                    .scroll_keymap
+                    .get_or_insert_with(|| self.options.scroll.keymap())
+                    .expire(&mut self.scroll_chord, now)
+                {
+                    self.apply_scroll_action(action);
+                    redraw = true;
+                }
+                if redraw {
+                    SelectionAction::Redraw
+                } else {
+                    SelectionAction::None
+                }
+            }
             InputEvent::Mouse(m) => match m.kind {
                 MouseEventKind::ScrollUp => {
-                    self.state.scroll_y_by(-3);
+                    self.options.scroll.wheel(
+                        &mut self.scroll_animator,
+                        &mut self.state,
+                        -3,
+                        Instant::now(),
+                    );
                     SelectionAction::Redraw
                 }
                 MouseEventKind::ScrollDown => {
-                    self.state.scroll_y_by(3);
+                    self.options.scroll.wheel(
+                        &mut self.scroll_animator,
+                        &mut self.state,
+                        3,
+                        Instant::now(),
+                    );
                     SelectionAction::Redraw
                 }
-                _ => SelectionAction::None,
-            },
-            InputEvent::Key(key) => {
-                if self.options.enable_selection && self.options.selection.is_clear(&key) {
-                    self.clear_selection();
-                    return SelectionAction::Redraw;
+                MouseEventKind::ScrollLeft => {
+                    self.state.scroll_x_by(-self.options.scroll.horiz_step);
+                    SelectionAction::Redraw
                 }
-                if self.options.enable_selection && self.options.selection.is_copy(&key) {
-                    return self
-                        .selected_text()
-                        .map(SelectionAction::CopyRequested)
-                        .unwrap_or(SelectionAction::None);
+                MouseEventKind::ScrollRight => {
+                    self.state.scroll_x_by(self.options.scroll.horiz_step);
+                    SelectionAction::Redraw
                 }
-                let Some(action) = self.options.scroll.action_for(&key) else {
-                    return SelectionAction::None;
-                };
-                self.options.scroll.apply(&mut self.state, action);
-                SelectionAction::Redraw
+                _ => SelectionAction::None,
+            },
+            InputEvent::Key(key) => self.handle_key_event_at(key, Instant::now()),
+        }
+    }
+
+    /// Handles a key received at `now`.
+    ///
+    /// The timestamp drives [`ScrollBindings::chord_timeout`]; a prefix left pending is
+    /// resolved by a later [`InputEvent::Tick`].
+    pub fn handle_key_event_at(&mut self, key: KeyEvent, now: Instant) -> SelectionAction {
+        let action = self.handle_key(key, now);
+        // A bound chord prefix fired because the key could not continue it; handle the key too.
+        match self.scroll_chord.take_replay() {
+            Some(key) => match self.handle_key_event_at(key, now) {
+                SelectionAction::None => action,
+                replayed => replayed,
+            },
+            None => action,
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, now: Instant) -> SelectionAction {
+        if self.options.enable_selection && self.options.selection.is_clear(&key) {
+            self.clear_selection();
+            return SelectionAction::Redraw;
+        }
+        if self.options.enable_selection && self.options.selection.is_copy(&key) {
+            let text = self.selected_text();
+            if self.visual.is_active() {
+                self.clear_selection();
             }
+            return text
+                .map(SelectionAction::CopyRequested)
+                .unwrap_or(SelectionAction::None);
+        }
+        if self.options.enable_selection {
+            let step = self.visual.step(
+                &key,
+                &self.options.selection,
+                self.scroll_keymap
+                    .get_or_insert_with(|| self.options.scroll.keymap()),
+                &mut self.scroll_chord,
+                Some(now),
+            );
+            if let Some(action) = self.apply_visual_step(step) {
+                return action;
+            }
+        }
+
+        let action = match self
+            .scroll_keymap
+            .get_or_insert_with(|| self.options.scroll.keymap())
+            .feed(&mut self.scroll_chord, &key, Some(now))
+        {
+            ChordMatch::Matched(action) => action,
+            ChordMatch::Pending => return SelectionAction::Redraw,
+            ChordMatch::NoMatch => return SelectionAction::None,
+        };
+        self.options.scroll.apply(&mut self.state, action);
+        SelectionAction::Redraw
+    }
+
+    fn apply_visual_step(&mut self, step: VisualStep) -> Option<SelectionAction> {
+        let (lines, state) = (&self.rendered, &mut self.state);
+        let action = self
+            .visual
+            .apply(step, state, lines.len(), |l| lines[l].plain.clone())?;
+        self.selection_anchor = None;
+        self.selection = self.visual.selection();
+        Some(action)
+    }
+
+    /// Applies a scroll action resolved outside a key press, moving the visual caret if active.
+    fn apply_scroll_action(&mut self, action: ScrollAction) {
+        if self.visual.is_active() {
+            self.apply_visual_step(VisualStep::Move(action.into()));
+        } else {
+            self.options.scroll.apply(&mut self.state, action);
         }
     }
 
@@ -673,7 +847,6 @@ impl MarkdownView {
     ) -> SelectionAction {
         match event {
             InputEvent::Paste(_) => SelectionAction::None,
-            InputEvent::Key(_) => self.handle_event_action(event),
             InputEvent::Mouse(m) => {
                 if self.handle_mouse_event(area, m) {
                     SelectionAction::Redraw
@@ -681,26 +854,65 @@ impl MarkdownView {
                     SelectionAction::None
                 }
             }
+            InputEvent::Key(_)
+            | InputEvent::Resize { .. }
+            | InputEvent::FocusGained
+            | InputEvent::FocusLost
+            | InputEvent::Tick(_) => self.handle_event_action(event),
         }
     }
 
     /// Handles mouse events for scrolling and drag-selection.
     pub fn handle_mouse_event(&mut self, area: Rect, event: MouseEvent) -> bool {
+        self.handle_mouse_event_at(area, event, Instant::now())
+    }
+
+    /// Like [`Self::handle_mouse_event`], but with the time the event was received.
+    ///
+    /// The timestamp drives double-click (select word) and triple-click (select line) detection.
+    pub fn handle_mouse_event_at(&mut self, area: Rect, event: MouseEvent, now: Instant) -> bool {
         if area.width == 0 || area.height == 0 {
             return false;
         }
 
         self.set_viewport(area);
 
+        let (content_area, _) = if self.options.show_scrollbar && area.width >= 2 {
+            (
+                Rect::new(area.x, area.y, area.width - 1, area.height),
+                Some(area.x + area.width - 1),
+            )
+        } else {
+            (area, None)
+        };
+
         match event.kind {
             MouseEventKind::ScrollUp => {
-                self.state.scroll_y_by(-3);
+                self.options
+                    .scroll
+                    .wheel(&mut self.scroll_animator, &mut self.state, -3, now);
                 return true;
             }
             MouseEventKind::ScrollDown => {
-                self.state.scroll_y_by(3);
+                self.options
+                    .scroll
+                    .wheel(&mut self.scroll_animator, &mut self.state, 3, now);
                 return true;
             }
+            MouseEventKind::ScrollLeft => {
+                self.state.scroll_x_by(-self.options.scroll.horiz_step);
+                return true;
+            }
+            MouseEventKind::ScrollRight => {
+                self.state.scroll_x_by(self.options.scroll.horiz_step);
+                return true;
+            }
+            MouseEventKind::Moved => {
+                let row = event.position_in(content_area).map(|(_, row)| row);
+                let changed = row != self.hover_row;
+                self.hover_row = row;
+                return changed;
+            }
             _ => {}
         }
 
@@ -708,15 +920,6 @@ impl MarkdownView {
             return false;
         }
 
-        let (content_area, _) = if self.options.show_scrollbar && area.width >= 2 {
-            (
-                Rect::new(area.x, area.y, area.width - 1, area.height),
-                Some(area.x + area.width - 1),
-            )
-        } else {
-            (area, None)
-        };
-
         let inner = inset_h(
             content_area,
             self.options.padding_left,
@@ -774,8 +977,21 @@ impl MarkdownView {
             .min(self.rendered.len().saturating_sub(1) as u32) as usize;
         let col = self.state.x.saturating_add(rel_x);
 
+        let click = self.clicks.on_mouse(&event, now);
         match event.kind {
             MouseEventKind::Down(MouseButton::Left) => {
+                self.visual.stop();
+                let count = click.map_or(1, |c| c.count);
+                if let Some(sel) = selection::click_selection(count, line, col, || {
+                    self.rendered
+                        .get(line)
+                        .map(|l| render::slice_spans_by_bytes(&l.spans, 0, usize::MAX))
+                        .unwrap_or_default()
+                }) {
+                    self.selection_anchor = None;
+                    self.selection = Some(sel);
+                    return true;
+                }
                 self.selection_anchor = Some((line, col));
                 self.selection = Some(((line, col), (line, col)));
                 true
@@ -799,15 +1015,31 @@ impl MarkdownView {
         }
     }
 
+    /// The rendered line under the mouse pointer, tracked from [`MouseEventKind::Moved`] events.
+    pub fn hovered_line(&self) -> Option<usize> {
+        let line = self.state.y as usize + self.hover_row? as usize;
+        (line < self.rendered.len()).then_some(line)
+    }
+
+    pub fn is_focused(&self) -> bool {
+        !self.unfocused
+    }
+
+    /// Sets focus. While unfocused the visual-mode caret is drawn with
+    /// [`ratatui_components_core::theme::ChromeTheme::cursor_unfocused`].
+    pub fn set_focused(&mut self, focused: bool) {
+        self.unfocused = !focused;
+    }
+
     pub fn clear_selection(&mut self) {
         self.selection_anchor = None;
         self.selection = None;
+        self.visual.stop();
     }
 
     pub fn selected_text(&mut self) -> Option<String> {
-        let ((l0, c0), (l1, c1)) = self.selection?;
-        let ((start_line, start_col), (end_line, end_col)) =
-            normalize_sel_inclusive((l0, c0), (l1, c1));
+        let sel = self.selection?;
+        let (start_line, end_line) = (sel.0.0.min(sel.1.0), sel.0.0.max(sel.1.0));
 
         let theme = Theme::default();
         self.ensure_layout(self.cached_width.unwrap_or(80), &theme);
@@ -820,16 +1052,7 @@ impl MarkdownView {
                 out.push('\n');
             }
 
-            let (from, to) = if start_line == end_line {
-                (start_col, end_col)
-            } else if line_idx == start_line {
-                (start_col, u32::MAX)
-            } else if line_idx == end_line {
-                (0, end_col)
-            } else {
-                (0, u32::MAX)
-            };
-
+            let (from, to) = selection::line_cols(sel, self.visual.is_block(), line_idx)?;
             if let Some((bs, be)) = render::byte_range_for_cols_in_spans(&line.spans, from, to) {
                 out.push_str(&render::slice_spans_by_bytes(&line.spans, bs, be));
             }
@@ -858,6 +1081,7 @@ impl MarkdownView {
             self.options.padding_right,
         );
         self.ensure_layout(inner.width, theme);
+        self.apply_scroll_anchor();
         let start = self.state.y as usize;
         let end = (start + content_area.height as usize).min(self.rendered.len());
         self.materialize_highlights(start, end, theme);
@@ -872,40 +1096,26 @@ impl MarkdownView {
             );
             if let Some(line) = line {
                 if self.options.enable_selection
-                    && let Some(((l0, c0), (l1, c1))) = self.selection
+                    && let Some(sel) = self.selection
+                    && let Some((from, to)) = selection::line_cols(sel, self.visual.is_block(), idx)
+                    && let Some((bs, be)) =
+                        render::byte_range_for_cols_in_spans(&line.spans, from, to)
                 {
-                    let ((start_line, start_col), (end_line, end_col)) =
-                        normalize_sel_inclusive((l0, c0), (l1, c1));
-                    if idx >= start_line && idx <= end_line {
-                        let (from, to) = if start_line == end_line {
-                            (start_col, end_col)
-                        } else if idx == start_line {
-                            (start_col, u32::MAX)
-                        } else if idx == end_line {
-                            (0, end_col)
-                        } else {
-                            (0, u32::MAX)
-                        };
-                        if let Some((bs, be)) =
-                            render::byte_range_for_cols_in_spans(&line.spans, from, to)
-                        {
-                            let spans = render::apply_modifier_to_byte_ranges(
-                                line.spans.clone(),
-                                &[(bs, be)],
-                                Modifier::REVERSED,
-                            );
-                            render::render_spans_clipped(
-                                inner.x,
-                                y,
-                                self.state.x,
-                                inner.width,
-                                buf,
-                                &spans,
-                                theme.text_primary,
-                            );
-                            continue;
-                        }
-                    }
+                    let spans = render::apply_style_to_byte_ranges(
+                        line.spans.clone(),
+                        &[(bs, be)],
+                        theme.selection_style(),
+                    );
+                    render::render_spans_clipped(
+                        inner.x,
+                        y,
+                        self.state.x,
+                        inner.width,
+                        buf,
+                        &spans,
+                        theme.text_primary,
+                    );
+                    continue;
                 }
 
                 render::render_spans_clipped(
@@ -920,14 +1130,27 @@ impl MarkdownView {
             }
         }
 
+        if let Some(line) = self.hovered_line() {
+            render::render_line_highlight(inner, buf, &self.state, line, theme.chrome.hover);
+        }
+        if let Some(caret) = self.visual.caret() {
+            render::render_caret(
+                inner,
+                buf,
+                &self.state,
+                caret,
+                theme.chrome.cursor_style(self.is_focused()),
+            );
+        }
         if let Some(sb_x) = scrollbar_x {
             render::render_scrollbar(
                 Rect::new(sb_x, area.y, 1, area.height),
                 buf,
                 &self.state,
-                theme.text_muted,
+                theme.chrome.scrollbar,
             );
         }
+        theme.color_profile.apply_to_buffer(buf, area);
     }
 
     pub fn lines_for_width(&mut self, width: u16, theme: &Theme) -> Vec<Line<'static>> {
@@ -935,7 +1158,9 @@ impl MarkdownView {
             .saturating_sub(self.options.padding_left)
             .saturating_sub(self.options.padding_right);
         self.ensure_layout(width, theme);
-        self.ensure_all_highlights();
+        if !theme.is_monochrome() {
+            self.ensure_all_highlights();
+        }
         self.materialize_highlights(0, self.rendered.len(), theme);
         self.rendered
             .iter()
@@ -957,10 +1182,30 @@ impl MarkdownView {
         )
     }
 
+    /// Records the block shown in the top line and how far into it that line starts.
+    ///
+    /// Keeps an anchor that is already pending: it was recorded against an older layout that
+    /// has not been shown yet.
+    fn capture_scroll_anchor(&mut self) {
+        if self.scroll_anchor.is_some() || self.rendered.is_empty() {
+            return;
+        }
+        let top = (self.state.y as usize).min(self.rendered.len() - 1);
+        let block = self
+            .block_starts
+            .partition_point(|&start| start <= top)
+            .saturating_sub(1);
+        let start = self.block_starts.get(block).copied().unwrap_or(0);
+        let offset =
+            ScrollAnchor::text_offset(self.rendered[start..top].iter().map(|l| l.plain.as_str()));
+        self.scroll_anchor = Some(ScrollAnchor::new(block, offset, &self.state));
+    }
+
     fn ensure_layout(&mut self, width: u16, theme: &Theme) {
         if self.cached_width == Some(width) && !self.rendered.is_empty() {
             return;
         }
+        self.capture_scroll_anchor();
         self.cached_width = Some(width);
         self.rendered = layout_blocks(
             &self.blocks,
@@ -969,6 +1214,7 @@ impl MarkdownView {
             theme,
             self.options.show_code_line_numbers,
             self.options.table_style,
+            &mut self.block_starts,
         );
         let content_h = self.rendered.len() as u32;
         let content_w = self
@@ -980,6 +1226,25 @@ impl MarkdownView {
         self.state.set_content(content_w, content_h);
     }
 
+    /// Restores the scroll position captured by [`Self::capture_scroll_anchor`].
+    ///
+    /// Called from `render_ref` once both the new viewport and the new layout are known.
+    fn apply_scroll_anchor(&mut self) {
+        let Some(anchor) = self.scroll_anchor.take() else {
+            return;
+        };
+        let row = self.block_starts.get(anchor.src).map(|&start| {
+            let end = self
+                .block_starts
+                .get(anchor.src + 1)
+                .copied()
+                .unwrap_or(self.rendered.len());
+            let rows = self.rendered[start..end].iter().map(|l| l.plain.as_str());
+            (start + ScrollAnchor::row_for_offset(rows, anchor.offset)) as u32
+        });
+        anchor.restore(&mut self.state, row);
+    }
+
     fn ensure_all_highlights(&mut self) {
         let Some(hi) = self.highlighter.clone() else {
             return;
@@ -1006,6 +1271,9 @@ impl MarkdownView {
     }
 
     fn materialize_highlights(&mut self, start: usize, end: usize, theme: &Theme) {
+        if theme.is_monochrome() {
+            return;
+        }
         let Some(hi) = self.highlighter.clone() else {
             return;
         };
@@ -1065,7 +1333,7 @@ impl MarkdownView {
                         .cloned(),
                 );
             } else {
-                spans.extend(patch_spans_style(content, theme.code_inline));
+                spans.extend(patch_spans_style(content, theme.markdown.code_block));
             }
 
             self.rendered[idx].spans = spans;
@@ -1074,17 +1342,24 @@ impl MarkdownView {
     }
 }
 
-fn normalize_sel(a: (usize, u32), b: (usize, u32)) -> ((usize, u32), (usize, u32)) {
-    if a.0 < b.0 || (a.0 == b.0 && a.1 <= b.1) {
-        (a, b)
-    } else {
-        (b, a)
+impl Component for MarkdownView {
+    type Action = SelectionAction;
+
+    fn handle_event(&mut self, area: Rect, event: InputEvent) -> SelectionAction {
+        self.handle_event_action_in_area(area, event)
     }
-}
 
-fn normalize_sel_inclusive(a: (usize, u32), b: (usize, u32)) -> ((usize, u32), (usize, u32)) {
-    let (start, end) = normalize_sel(a, b);
-    (start, (end.0, end.1.saturating_add(1)))
+    fn render(&mut self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        self.render_ref(area, buf, theme);
+    }
+
+    fn bindings(&self) -> Vec<BindingGroup> {
+        self.options.help_groups()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        MarkdownView::set_focused(self, focused);
+    }
 }
 
 fn inset_h(area: Rect, left: u16, right: u16) -> Rect {
@@ -1114,6 +1389,7 @@ struct ParseOptions<'a> {
     code_block_indent_in_blockquote: u16,
     footnote_hanging_indent: bool,
     base_url: Option<&'a str>,
+    tabs: TabPolicy,
 }
 
 fn parse_markdown_blocks(input: &str, opts: ParseOptions<'_>) -> Vec<Block> {
@@ -1171,6 +1447,7 @@ fn parse_markdown_blocks(input: &str, opts: ParseOptions<'_>) -> Vec<Block> {
         code_indent: u16,
         code_block_indent: u16,
         code_block_indent_in_blockquote: u16,
+        code_tabs: TabPolicy,
         wants_blank: bool,
         preserve_new_lines: bool,
 
@@ -1234,6 +1511,7 @@ fn parse_markdown_blocks(input: &str, opts: ParseOptions<'_>) -> Vec<Block> {
                 code_indent: opts.code_block_indent,
                 code_block_indent: opts.code_block_indent,
                 code_block_indent_in_blockquote: opts.code_block_indent_in_blockquote,
+                code_tabs: opts.tabs,
                 wants_blank: false,
                 preserve_new_lines: opts.preserve_new_lines,
 
@@ -1827,7 +2105,10 @@ fn parse_markdown_blocks(input: &str, opts: ParseOptions<'_>) -> Vec<Block> {
                         match ch {
                             '\n' => b.code_lines.push(std::mem::take(&mut b.code_current)),
                             '\r' => {}
-                            '\t' => b.code_current.push_str("    "),
+                            '\t' => {
+                                let col = UnicodeWidthStr::width(b.code_current.as_str());
+                                b.code_tabs.push_tab(&mut b.code_current, col);
+                            }
                             other => b.code_current.push(other),
                         }
                     }
@@ -2172,9 +2453,12 @@ fn layout_blocks(
     theme: &Theme,
     show_code_line_numbers: bool,
     table_style: TableStyle,
+    block_starts: &mut Vec<usize>,
 ) -> Vec<RenderedLine> {
     let mut out: Vec<RenderedLine> = Vec::new();
+    block_starts.clear();
     for b in blocks {
+        block_starts.push(out.len());
         match b {
             Block::Blank(prefix) => {
                 let spans = if prefix.is_empty() {
@@ -2195,7 +2479,7 @@ fn layout_blocks(
                     UnicodeWidthStr::width(join_segments_plain(prefix).as_str()) as u16;
                 let remaining = width.saturating_sub(prefix_cols);
                 let rule_len = remaining.clamp(1, 8) as usize;
-                spans.push(Span::styled("-".repeat(rule_len), theme.text_muted));
+                spans.push(Span::styled("-".repeat(rule_len), theme.markdown.rule));
                 let plain = join_spans_plain(&spans);
                 out.push(RenderedLine {
                     spans,
@@ -2208,7 +2492,7 @@ fn layout_blocks(
                 let indent_span = if code.indent > 0 {
                     Some(Span::styled(
                         " ".repeat(code.indent as usize),
-                        theme.code_inline,
+                        theme.markdown.code_block,
                     ))
                 } else {
                     None
@@ -2237,10 +2521,10 @@ fn layout_blocks(
                         let gutter = format!("{n:>width$} │ ", width = line_number_w);
                         spans.push(Span::styled(
                             gutter,
-                            theme.code_inline.patch(theme.text_muted),
+                            theme.markdown.code_block.patch(theme.code.line_number),
                         ));
                     }
-                    spans.push(Span::styled(line.clone(), theme.code_inline));
+                    spans.push(Span::styled(line.clone(), theme.markdown.code_block));
                     let plain = join_spans_plain(&spans);
                     out.push(RenderedLine {
                         spans,
@@ -2380,7 +2664,7 @@ fn layout_table_glow(table: &TableBlock, width: u16, theme: &Theme) -> Vec<Rende
         out.push(RenderedLine {
             spans: vec![Span::styled(
                 table_separator_line_glow(&col_w),
-                theme.text_muted,
+                theme.markdown.table_border,
             )],
             plain: table_separator_line_glow(&col_w),
             code_ref: None,
@@ -2460,7 +2744,7 @@ fn layout_table_row_glow(
         let mut spans: Vec<Span<'static>> = Vec::new();
         for ci in 0..cols {
             if ci > 0 {
-                spans.push(Span::styled("│".to_string(), theme.text_muted));
+                spans.push(Span::styled("│".to_string(), theme.markdown.table_border));
             }
 
             let mut cell_spans = cells_wrapped[ci]
@@ -2470,7 +2754,7 @@ fn layout_table_row_glow(
 
             if is_header {
                 for s in &mut cell_spans {
-                    s.style = s.style.add_modifier(Modifier::BOLD);
+                    s.style = s.style.patch(theme.markdown.table_header);
                 }
             }
 
@@ -2506,17 +2790,14 @@ fn truncate_spans_with_ellipsis(
 
     'outer: for span in spans {
         let mut buf = String::new();
-        for ch in span.content.as_ref().chars() {
-            let w = UnicodeWidthChar::width(ch).unwrap_or(0);
-            if w == 0 {
-                continue;
-            }
+        for g in grapheme::graphemes(span.content.as_ref()) {
+            let w = grapheme::width(g);
             if cols + w > max_cols {
                 truncated = true;
                 break 'outer;
             }
             cols += w;
-            buf.push(ch);
+            buf.push_str(g);
         }
         if !buf.is_empty() {
             out.push(Span::styled(buf, span.style));
@@ -2539,17 +2820,7 @@ fn truncate_spans_with_ellipsis(
     // Remove content until we have room for '…' (width=1).
     while cols >= max_cols {
         if let Some(last) = out.pop() {
-            let text = last.content.to_string();
-            let mut removed = 0usize;
-            let mut new = String::new();
-            for ch in text.chars() {
-                let w = UnicodeWidthChar::width(ch).unwrap_or(0);
-                if w == 0 {
-                    continue;
-                }
-                new.push(ch);
-                removed += w;
-            }
+            let removed = UnicodeWidthStr::width(last.content.as_ref());
             cols = cols.saturating_sub(removed);
         } else {
             break;
@@ -2562,16 +2833,13 @@ fn truncate_spans_with_ellipsis(
     let mut cur = 0usize;
     for span in out {
         let mut buf = String::new();
-        for ch in span.content.as_ref().chars() {
-            let w = UnicodeWidthChar::width(ch).unwrap_or(0);
-            if w == 0 {
-                continue;
-            }
+        for g in grapheme::graphemes(span.content.as_ref()) {
+            let w = grapheme::width(g);
             if cur + w > target {
                 break;
             }
             cur += w;
-            buf.push(ch);
+            buf.push_str(g);
         }
         if !buf.is_empty() {
             trimmed.push(Span::styled(buf, span.style));
@@ -2654,7 +2922,7 @@ fn layout_table_box(table: &TableBlock, width: u16, theme: &Theme) -> Vec<Render
     out.push(RenderedLine {
         spans: vec![Span::styled(
             table_border_line(&col_w, BorderLine::Top),
-            theme.text_muted,
+            theme.markdown.table_border,
         )],
         plain: table_border_line(&col_w, BorderLine::Top),
         code_ref: None,
@@ -2666,7 +2934,7 @@ fn layout_table_box(table: &TableBlock, width: u16, theme: &Theme) -> Vec<Render
         out.push(RenderedLine {
             spans: vec![Span::styled(
                 table_border_line(&col_w, BorderLine::HeaderSep),
-                theme.text_muted,
+                theme.markdown.table_border,
             )],
             plain: table_border_line(&col_w, BorderLine::HeaderSep),
             code_ref: None,
@@ -2678,7 +2946,7 @@ fn layout_table_box(table: &TableBlock, width: u16, theme: &Theme) -> Vec<Render
     out.push(RenderedLine {
         spans: vec![Span::styled(
             table_border_line(&col_w, BorderLine::Bottom),
-            theme.text_muted,
+            theme.markdown.table_border,
         )],
         plain: table_border_line(&col_w, BorderLine::Bottom),
         code_ref: None,
@@ -2756,7 +3024,7 @@ fn layout_table_row(
     let mut out: Vec<RenderedLine> = Vec::new();
     for li in 0..row_h {
         let mut spans: Vec<Span<'static>> = Vec::new();
-        spans.push(Span::styled("│".to_string(), theme.text_muted));
+        spans.push(Span::styled("│".to_string(), theme.markdown.table_border));
         for ci in 0..cols {
             spans.push(Span::styled(" ".to_string(), theme.text_primary));
             let mut cell_spans = cells_wrapped[ci]
@@ -2765,14 +3033,14 @@ fn layout_table_row(
                 .unwrap_or_else(|| vec![Span::raw("")]);
             if is_header {
                 for s in &mut cell_spans {
-                    s.style = s.style.add_modifier(Modifier::BOLD);
+                    s.style = s.style.patch(theme.markdown.table_header);
                 }
             }
             let align = aligns.get(ci).copied().unwrap_or(Alignment::Left);
             let cell_spans = pad_spans(cell_spans, col_w[ci], align, theme.text_primary);
             spans.extend(cell_spans);
             spans.push(Span::styled(" ".to_string(), theme.text_primary));
-            spans.push(Span::styled("│".to_string(), theme.text_muted));
+            spans.push(Span::styled("│".to_string(), theme.markdown.table_border));
         }
         let plain = join_spans_plain(&spans);
         out.push(RenderedLine {
@@ -2942,17 +3210,14 @@ fn last_url_breakpoint_before(s: &str, max_cols: usize) -> Option<usize> {
     }
     let mut cols = 0usize;
     let mut best: Option<usize> = None;
-    for (byte_idx, ch) in s.char_indices() {
-        let w = UnicodeWidthChar::width(ch).unwrap_or(0);
-        if w == 0 {
-            continue;
-        }
+    for (byte_idx, g) in grapheme::grapheme_indices(s) {
+        let w = grapheme::width(g);
         if cols + w > max_cols {
             break;
         }
         cols += w;
-        if is_url_break_char(ch) {
-            best = Some(byte_idx + ch.len_utf8());
+        if g.chars().next().is_some_and(is_url_break_char) {
+            best = Some(byte_idx + g.len());
         }
     }
     best
@@ -2999,16 +3264,13 @@ fn split_to_width(seg: &Segment, max_cols: usize) -> (Segment, Segment) {
     }
     let mut cols = 0usize;
     let mut idx = 0usize;
-    for (byte_idx, ch) in seg.text.char_indices() {
-        let w = UnicodeWidthChar::width(ch).unwrap_or(0);
-        if w == 0 {
-            continue;
-        }
+    for (byte_idx, g) in grapheme::grapheme_indices(&seg.text) {
+        let w = grapheme::width(g);
         if cols + w > max_cols {
             break;
         }
         cols += w;
-        idx = byte_idx + ch.len_utf8();
+        idx = byte_idx + g.len();
     }
     let (a, b) = seg.text.split_at(idx);
     let mut left = seg.clone();
@@ -3035,29 +3297,26 @@ fn style_for_segment(theme: &Theme, seg: &Segment) -> Style {
     } else {
         match seg.style {
             ProseStyle::Normal => theme.text_primary,
-            ProseStyle::Heading(1) => theme
-                .text_primary
-                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
-            ProseStyle::Heading(_) => theme.text_primary.add_modifier(Modifier::BOLD),
-            ProseStyle::BlockQuote => theme.text_muted,
-            ProseStyle::List => theme.text_muted,
+            ProseStyle::Heading(level) => theme.markdown.heading(level),
+            ProseStyle::BlockQuote => theme.markdown.blockquote,
+            ProseStyle::List => theme.markdown.list_marker,
         }
     };
 
     if seg.inline_code {
-        style = theme.code_inline;
+        style = theme.code.inline;
     }
     if seg.link {
-        style = theme.accent.add_modifier(Modifier::UNDERLINED);
+        style = theme.markdown.link;
     }
     if seg.flags.emphasis {
-        style = style.add_modifier(Modifier::ITALIC);
+        style = style.patch(theme.markdown.emphasis);
     }
     if seg.flags.strong {
-        style = style.add_modifier(Modifier::BOLD);
+        style = style.patch(theme.markdown.strong);
     }
     if seg.flags.strike {
-        style = style.add_modifier(Modifier::CROSSED_OUT);
+        style = style.patch(theme.markdown.strikethrough);
     }
 
     style
@@ -3114,6 +3373,7 @@ mod tests {
                 code_block_indent_in_blockquote: 2,
                 footnote_hanging_indent: true,
                 base_url: None,
+                tabs: TabPolicy::default(),
             },
         );
         let code = blocks
@@ -3127,6 +3387,27 @@ mod tests {
         assert_eq!(code.lines.as_slice(), &["fn main() {}".to_string()]);
     }
 
+    #[test]
+    fn code_block_tabs_use_the_tab_policy() {
+        let options = document::MarkdownRenderOptions {
+            tabs: TabPolicy::new(8),
+            ..Default::default()
+        };
+        let doc = document::MarkdownDocument::parse("```make\nall:\tbuild\n```\n", &options);
+        let text = doc
+            .render(40, &Theme::default(), &options, None)
+            .into_text();
+        let plain: Vec<String> = text
+            .lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(
+            plain.iter().any(|l| l.contains("all:    build")),
+            "{plain:?}"
+        );
+    }
+
     #[test]
     fn wraps_prose_but_not_code() {
         let md = "hello world\n\n```txt\nabcdef\n```\n";
@@ -3178,6 +3459,7 @@ mod tests {
                 code_block_indent_in_blockquote: 2,
                 footnote_hanging_indent: true,
                 base_url: None,
+                tabs: TabPolicy::default(),
             },
         );
         assert!(blocks.iter().any(|b| matches!(b, Block::Table(_))));
@@ -3607,4 +3889,89 @@ mod tests {
 
         assert_eq!(highlighter.calls.load(Ordering::SeqCst), 0);
     }
+
+    #[test]
+    fn resize_keeps_bottom_anchored_view_at_bottom() {
+        let md = (0..40)
+            .map(|i| format!("paragraph {i} with some words to wrap"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let theme = Theme::default();
+        let mut view = MarkdownView::new();
+        view.set_markdown(&md);
+
+        let wide = Rect::new(0, 0, 60, 10);
+        let mut buf = Buffer::empty(wide);
+        view.render_ref(wide, &mut buf, &theme);
+        view.state.to_bottom();
+
+        let _ = view.handle_event_action(InputEvent::Resize {
+            width: 20,
+            height: 10,
+        });
+        let narrow = Rect::new(0, 0, 20, 10);
+        let mut buf = Buffer::empty(narrow);
+        view.render_ref(narrow, &mut buf, &theme);
+        assert_eq!(
+            view.state.y + view.state.viewport_h as u32,
+            view.state.content_h
+        );
+    }
+
+    fn top_line(view: &MarkdownView) -> &str {
+        view.rendered[view.state.y as usize].plain.trim_end()
+    }
+
+    #[test]
+    fn resize_keeps_the_top_line_anchored_to_its_text() {
+        let md = (0..40)
+            .map(|i| format!("paragraph {i} has several words that wrap at narrow widths"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let theme = Theme::default();
+        let mut view = MarkdownView::new();
+        view.set_markdown(&md);
+
+        let wide = Rect::new(0, 0, 80, 10);
+        view.render_ref(wide, &mut Buffer::empty(wide), &theme);
+        view.state.y = 30;
+        assert_eq!(
+            top_line(&view),
+            "paragraph 15 has several words that wrap at narrow widths"
+        );
+
+        let narrow = Rect::new(0, 0, 20, 10);
+        let _ = view.handle_event_action(InputEvent::Resize {
+            width: 20,
+            height: 10,
+        });
+        view.render_ref(narrow, &mut Buffer::empty(narrow), &theme);
+        assert_eq!(top_line(&view), "paragraph 15 has");
+
+        // Scrolled into the middle of a wrapped paragraph, the same words stay on top.
+        view.scroll_y_by(1);
+        assert_eq!(top_line(&view), "several words that");
+        let narrower = Rect::new(0, 0, 14, 10);
+        view.render_ref(narrower, &mut Buffer::empty(narrower), &theme);
+        assert_eq!(top_line(&view), "has several");
+    }
+
+    #[test]
+    fn set_markdown_keeps_the_top_block_in_place() {
+        let md = (0..40)
+            .map(|i| format!("line {i}"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let theme = Theme::default();
+        let mut view = MarkdownView::new();
+        view.set_markdown(&md);
+        let area = Rect::new(0, 0, 40, 10);
+        view.render_ref(area, &mut Buffer::empty(area), &theme);
+        view.state.y = 20;
+        assert_eq!(top_line(&view), "line 10");
+
+        view.set_markdown(&format!("{md}\n\nline 40"));
+        view.render_ref(area, &mut Buffer::empty(area), &theme);
+        assert_eq!(top_line(&view), "line 10");
+    }
 }
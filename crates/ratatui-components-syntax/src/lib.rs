@@ -7,6 +7,13 @@
 //!
 //! The facade crate `ratatui-components` re-exports this crate behind feature flags, so most apps
 //! can just enable `ratatui-components/syntect` or `ratatui-components/treesitter`.
+//!
+//! Highlighters emit the theme's full colors. Color downsampling happens once, when a view
+//! renders: every view adapts its buffer to `Theme::color_profile`
+//! (see `ratatui_components_core::color::ColorProfile`). Text rendered outside the views (e.g.
+//! with `code_render::render_code_lines`) can be adapted with `ColorProfile::adapt_text`. The
+//! `termprofile` feature of the syntect backend is only needed when its output is used without
+//! either.
 #[cfg(feature = "syntect")]
 pub mod syntect;
 
@@ -16,6 +16,7 @@ use ratatui::style::Stylize;
 use ratatui::widgets::Block;
 use ratatui::widgets::Borders;
 use ratatui_components::diff::DiffView;
+use ratatui_components::focus::FocusManager;
 use ratatui_components::input::InputEvent;
 use ratatui_components::input::KeyCode as K;
 use ratatui_components::input::KeyEvent as KE;
@@ -71,7 +72,7 @@ fn main() -> io::Result<()> {
     transcript.set_highlighter(Some(highlighter.clone()));
     transcript.push_markdown(
         Role::System,
-        "# MVP demo\n\nLeft: transcript (Markdown + ANSI)\nRight: diff preview\nBottom: composer\n\nPress `Tab` to switch focus, `q` to quit.",
+        "# MVP demo\n\nLeft: transcript (Markdown + ANSI)\nRight: diff preview\nBottom: composer\n\nPress `Tab`/`Shift+Tab` to switch focus, `q` to quit.",
     );
     transcript.push_ansi(
         Role::Tool,
@@ -88,7 +89,9 @@ fn main() -> io::Result<()> {
     diff.set_language_override(Some("rs"));
 
     let mut input = TextArea::new();
-    let mut focus = Focus::Input;
+    let mut focus = FocusManager::new(vec![Focus::Transcript, Focus::Diff, Focus::Input]);
+    focus.focus(&Focus::Input);
+    sync_focus(&focus, &mut transcript, &mut diff, &mut input);
     let mut copied: Option<String> = None;
 
     let res = run(
@@ -118,11 +121,12 @@ fn run<B: ratatui::backend::Backend<Error = io::Error>>(
     transcript: &mut TranscriptView,
     diff: &mut DiffView,
     input: &mut TextArea,
-    focus: &mut Focus,
+    focus: &mut FocusManager<Focus>,
     copied: &mut Option<String>,
 ) -> io::Result<()> {
     let mut layout = LayoutState::default();
     loop {
+        let focused = focus.focused().copied().unwrap_or(Focus::Input);
         terminal.draw(|f| {
             let cursor = ui(
                 f,
@@ -130,7 +134,7 @@ fn run<B: ratatui::backend::Backend<Error = io::Error>>(
                 transcript,
                 diff,
                 input,
-                *focus,
+                focused,
                 copied,
                 &mut layout,
             );
@@ -153,16 +157,14 @@ fn run<B: ratatui::backend::Backend<Error = io::Error>>(
                     return Ok(());
                 }
 
-                if matches!(key.code, KeyCode::Tab) {
-                    *focus = match focus {
-                        Focus::Transcript => Focus::Diff,
-                        Focus::Diff => Focus::Input,
-                        Focus::Input => Focus::Transcript,
-                    };
+                if let Some(ev) = to_input_event(key)
+                    && focus.handle_event(&ev)
+                {
+                    sync_focus(focus, transcript, diff, input);
                     continue;
                 }
 
-                match *focus {
+                match focused {
                     Focus::Input => {
                         if let Some(ev) = to_input_event(key) {
                             match input.input(ev) {
@@ -214,6 +216,18 @@ fn run<B: ratatui::backend::Backend<Error = io::Error>>(
     }
 }
 
+/// Tells each pane whether it has focus, so carets and cursors switch to their unfocused styles.
+fn sync_focus(
+    focus: &FocusManager<Focus>,
+    transcript: &mut TranscriptView,
+    diff: &mut DiffView,
+    input: &mut TextArea,
+) {
+    transcript.set_focused(focus.is_focused(&Focus::Transcript));
+    diff.set_focused(focus.is_focused(&Focus::Diff));
+    input.set_focused(focus.is_focused(&Focus::Input));
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 struct LayoutState {
     transcript: ratatui::layout::Rect,
@@ -251,8 +265,14 @@ fn ui(
         _ => "Diff".cyan(),
     };
 
-    let left_block = Block::default().title(left_title).borders(Borders::ALL);
-    let right_block = Block::default().title(right_title).borders(Borders::ALL);
+    let left_block = Block::default()
+        .title(left_title)
+        .borders(Borders::ALL)
+        .border_style(theme.chrome.border_style(focus == Focus::Transcript));
+    let right_block = Block::default()
+        .title(right_title)
+        .borders(Borders::ALL)
+        .border_style(theme.chrome.border_style(focus == Focus::Diff));
 
     let left_inner = left_block.inner(left);
     let right_inner = right_block.inner(right);
@@ -272,7 +292,10 @@ fn ui(
         Focus::Input => input_title.cyan().bold(),
         _ => input_title.cyan(),
     };
-    let input_block = Block::default().title(input_title).borders(Borders::ALL);
+    let input_block = Block::default()
+        .title(input_title)
+        .borders(Borders::ALL)
+        .border_style(theme.chrome.border_style(focus == Focus::Input));
     let input_inner = input_block.inner(input_area);
     f.render_widget(input_block, input_area);
 
@@ -295,6 +318,7 @@ fn to_input_event(key: crossterm::event::KeyEvent) -> Option<InputEvent> {
         KeyCode::Backspace => K::Backspace,
         KeyCode::Delete => K::Delete,
         KeyCode::Tab => K::Tab,
+        KeyCode::BackTab => K::BackTab,
         KeyCode::Esc => K::Esc,
         KeyCode::Left => K::Left,
         KeyCode::Right => K::Right,
@@ -315,6 +339,7 @@ fn to_input_event(key: crossterm::event::KeyEvent) -> Option<InputEvent> {
                 .modifiers
                 .contains(crossterm::event::KeyModifiers::CONTROL),
             alt: key.modifiers.contains(crossterm::event::KeyModifiers::ALT),
+            ..Default::default()
         }),
     ))
 }
@@ -334,7 +359,9 @@ fn to_mouse_event(m: crossterm::event::MouseEvent) -> Option<MouseEvent> {
         MouseEventKind::Up(b) => ratatui_components::input::MouseEventKind::Up(to_mouse_button(b)?),
         MouseEventKind::ScrollUp => ratatui_components::input::MouseEventKind::ScrollUp,
         MouseEventKind::ScrollDown => ratatui_components::input::MouseEventKind::ScrollDown,
-        _ => return None,
+        MouseEventKind::ScrollLeft => ratatui_components::input::MouseEventKind::ScrollLeft,
+        MouseEventKind::ScrollRight => ratatui_components::input::MouseEventKind::ScrollRight,
+        MouseEventKind::Moved => ratatui_components::input::MouseEventKind::Moved,
     };
 
     Some(MouseEvent {
@@ -347,6 +374,7 @@ fn to_mouse_event(m: crossterm::event::MouseEvent) -> Option<MouseEvent> {
                 .modifiers
                 .contains(crossterm::event::KeyModifiers::CONTROL),
             alt: m.modifiers.contains(crossterm::event::KeyModifiers::ALT),
+            ..Default::default()
         },
     })
 }
@@ -397,6 +397,7 @@ fn to_key_event(key: &crossterm::event::KeyEvent) -> Option<ratatui_components::
                 .modifiers
                 .contains(crossterm::event::KeyModifiers::CONTROL),
             alt: key.modifiers.contains(crossterm::event::KeyModifiers::ALT),
+            ..Default::default()
         }),
     )
 }
@@ -431,7 +432,9 @@ fn to_mouse_event(m: crossterm::event::MouseEvent) -> Option<MouseEvent> {
         MouseEventKind::Up(b) => ratatui_components::input::MouseEventKind::Up(to_mouse_button(b)?),
         MouseEventKind::ScrollUp => ratatui_components::input::MouseEventKind::ScrollUp,
         MouseEventKind::ScrollDown => ratatui_components::input::MouseEventKind::ScrollDown,
-        _ => return None,
+        MouseEventKind::ScrollLeft => ratatui_components::input::MouseEventKind::ScrollLeft,
+        MouseEventKind::ScrollRight => ratatui_components::input::MouseEventKind::ScrollRight,
+        MouseEventKind::Moved => ratatui_components::input::MouseEventKind::Moved,
     };
 
     Some(MouseEvent {
@@ -444,6 +447,7 @@ fn to_mouse_event(m: crossterm::event::MouseEvent) -> Option<MouseEvent> {
                 .modifiers
                 .contains(crossterm::event::KeyModifiers::CONTROL),
             alt: m.modifiers.contains(crossterm::event::KeyModifiers::ALT),
+            ..Default::default()
         },
     })
 }
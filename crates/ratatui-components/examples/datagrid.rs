@@ -160,6 +160,7 @@ fn to_key_event(key: crossterm::event::KeyEvent) -> Option<ratatui_components::i
         shift: key.modifiers.contains(KeyModifiers::SHIFT),
         ctrl: key.modifiers.contains(KeyModifiers::CONTROL),
         alt: key.modifiers.contains(KeyModifiers::ALT),
+        ..Default::default()
     };
 
     let code = match key.code {
@@ -180,5 +181,5 @@ fn to_key_event(key: crossterm::event::KeyEvent) -> Option<ratatui_components::i
         _ => return None,
     };
 
-    Some(E { code, modifiers })
+    Some(E::new(code).with_modifiers(modifiers))
 }
@@ -348,6 +348,7 @@ fn to_key_event(key: crossterm::event::KeyEvent) -> Option<ratatui_components::i
         shift: key.modifiers.contains(KeyModifiers::SHIFT),
         ctrl: key.modifiers.contains(KeyModifiers::CONTROL),
         alt: key.modifiers.contains(KeyModifiers::ALT),
+        ..Default::default()
     };
 
     let code = match key.code {
@@ -368,7 +369,7 @@ fn to_key_event(key: crossterm::event::KeyEvent) -> Option<ratatui_components::i
         _ => return None,
     };
 
-    Some(E { code, modifiers })
+    Some(E::new(code).with_modifiers(modifiers))
 }
 
 fn point_in(r: Rect, x: u16, y: u16) -> bool {
@@ -386,7 +387,9 @@ fn to_mouse_event(m: crossterm::event::MouseEvent) -> Option<MouseEvent> {
         MouseEventKind::Up(b) => ratatui_components::input::MouseEventKind::Up(to_mouse_button(b)?),
         MouseEventKind::ScrollUp => ratatui_components::input::MouseEventKind::ScrollUp,
         MouseEventKind::ScrollDown => ratatui_components::input::MouseEventKind::ScrollDown,
-        _ => return None,
+        MouseEventKind::ScrollLeft => ratatui_components::input::MouseEventKind::ScrollLeft,
+        MouseEventKind::ScrollRight => ratatui_components::input::MouseEventKind::ScrollRight,
+        MouseEventKind::Moved => ratatui_components::input::MouseEventKind::Moved,
     };
 
     Some(MouseEvent {
@@ -399,6 +402,7 @@ fn to_mouse_event(m: crossterm::event::MouseEvent) -> Option<MouseEvent> {
                 .modifiers
                 .contains(crossterm::event::KeyModifiers::CONTROL),
             alt: m.modifiers.contains(crossterm::event::KeyModifiers::ALT),
+            ..Default::default()
         },
     })
 }
@@ -542,8 +546,7 @@ fn build_keymap() -> Vec<BindingAction> {
                 "bottom",
                 vec![E::new(K::Char('G')).with_modifiers(M {
                     shift: true,
-                    ctrl: false,
-                    alt: false,
+                    ..M::none()
                 })],
             ),
             action: Action::Bottom,
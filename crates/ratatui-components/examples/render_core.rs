@@ -151,8 +151,8 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> io::Result<()>
                     Some("diff"),
                     hi_ref,
                     CodeRenderStyles {
-                        base: theme.code_inline,
-                        gutter: theme.text_muted,
+                        base: theme.code.inline,
+                        gutter: theme.code.line_number,
                     },
                     CodeRenderOptions {
                         show_line_numbers: true,
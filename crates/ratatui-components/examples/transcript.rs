@@ -190,6 +190,7 @@ fn to_input_event(key: crossterm::event::KeyEvent) -> Option<InputEvent> {
             .modifiers
             .contains(crossterm::event::KeyModifiers::CONTROL),
         alt: key.modifiers.contains(crossterm::event::KeyModifiers::ALT),
+        ..Default::default()
     };
 
     let code = match key.code {
@@ -210,7 +211,7 @@ fn to_input_event(key: crossterm::event::KeyEvent) -> Option<InputEvent> {
         _ => return None,
     };
 
-    Some(InputEvent::Key(KE { code, modifiers }))
+    Some(InputEvent::Key(KE::new(code).with_modifiers(modifiers)))
 }
 
 fn next_chunk_boundary(s: &str, start: usize, max_chars: usize) -> usize {
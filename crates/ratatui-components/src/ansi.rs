@@ -1,37 +1,64 @@
 use ansi_to_tui::IntoText;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Modifier;
 use ratatui::text::Line;
 use ratatui::text::Text;
 use unicode_width::UnicodeWidthStr;
 
+use crate::component::Component;
+use crate::help;
+use crate::help::BindingGroup;
+use crate::input::ClickTracker;
 use crate::input::InputEvent;
+use crate::input::KeyEvent;
 use crate::input::MouseButton;
 use crate::input::MouseEvent;
 use crate::input::MouseEventKind;
+use crate::keymap::ChordMatch;
+use crate::keymap::ChordState;
+use crate::keymap::Keymap;
 use crate::render;
+use crate::render::MarkerKind;
+use crate::render::ScrollbarLayout;
+use crate::render::ScrollbarMarker;
+use crate::scroll::ScrollAction;
 use crate::scroll::ScrollBindings;
+use crate::selection;
 use crate::selection::SelectionAction;
 use crate::selection::SelectionBindings;
+use crate::selection::VisualSelection;
+use crate::selection::VisualStep;
+use crate::tabs::TabPolicy;
 use crate::theme::Theme;
+use crate::viewport::ScrollAnimator;
 use crate::viewport::ViewportState;
-
-fn expand_tabs(s: &str) -> std::borrow::Cow<'_, str> {
-    if s.contains('\t') {
-        std::borrow::Cow::Owned(s.replace('\t', "    "))
-    } else {
-        std::borrow::Cow::Borrowed(s)
-    }
-}
+use std::time::Instant;
 
 /// Converts an ANSI-colored string into a [`Text`].
 ///
-/// Tabs are expanded as 4 spaces before parsing to keep hit-testing and selection consistent.
+/// Tabs are expanded with the default [`TabPolicy`] to keep hit-testing and selection consistent;
+/// see [`ansi_text_with_tabs`].
+///
+/// Colors are kept as parsed (possibly truecolor); downsample them for the terminal with
+/// [`ColorProfile::adapt_text`](ratatui_components_core::color::ColorProfile::adapt_text).
 pub fn ansi_text(input: &str) -> Text<'static> {
-    let input = expand_tabs(input);
+    ansi_text_with_tabs(input, TabPolicy::default())
+}
+
+/// Like [`ansi_text`], expanding tabs with `tabs`.
+///
+/// Tabs are expanded after the escape sequences are parsed, so tab stops count visible columns.
+/// Tabs become spaces; [`TabPolicy::glyph`] is only drawn by [`AnsiTextView`].
+pub fn ansi_text_with_tabs(input: &str, tabs: TabPolicy) -> Text<'static> {
+    let mut text = parse_ansi(input);
+    for line in &mut text.lines {
+        tabs.expand_line(line);
+    }
+    text
+}
+
+fn parse_ansi(input: &str) -> Text<'static> {
     input
-        .as_ref()
         .into_text()
         .unwrap_or_else(|_| Text::from(input.to_string()))
 }
@@ -40,23 +67,39 @@ pub fn ansi_text(input: &str) -> Text<'static> {
 #[derive(Clone, Debug)]
 pub struct AnsiTextViewOptions {
     pub show_scrollbar: bool,
+    /// Draws a horizontal scrollbar on the bottom row.
+    pub show_scrollbar_x: bool,
     pub scroll: ScrollBindings,
     pub enable_selection: bool,
     pub selection: SelectionBindings,
+    /// How tabs are expanded; applies to input set after the change.
+    pub tabs: TabPolicy,
 }
 
 impl Default for AnsiTextViewOptions {
     fn default() -> Self {
         Self {
             show_scrollbar: true,
+            show_scrollbar_x: false,
             scroll: ScrollBindings::default(),
             enable_selection: true,
             selection: SelectionBindings::default(),
+            tabs: TabPolicy::default(),
         }
     }
 }
 
-/// A scrollable ANSI text viewer with optional mouse selection + copy-on-request.
+impl AnsiTextViewOptions {
+    /// Describes the configured bindings for help screens (see [`help::HelpPanel`]).
+    pub fn help_groups(&self) -> Vec<BindingGroup> {
+        help::view_help_groups(
+            &self.scroll,
+            self.enable_selection.then_some(&self.selection),
+        )
+    }
+}
+
+/// A scrollable ANSI text viewer with optional mouse or keyboard selection + copy-on-request.
 ///
 /// Selection is performed in terminal cell units (line, column). When the copy binding is pressed,
 /// the view returns a [`SelectionAction::CopyRequested`] containing the extracted plain text.
@@ -65,11 +108,23 @@ impl Default for AnsiTextViewOptions {
 #[derive(Clone, Default)]
 pub struct AnsiTextView {
     lines: Vec<Line<'static>>,
+    /// Tab start columns per line, for drawing the tab glyph; empty when the input has no tabs.
+    tab_cols: Vec<Vec<u32>>,
     max_content_width: u16,
     pub state: ViewportState,
+    scroll_chord: ChordState,
+    /// Built from `options.scroll` on first use and dropped when the options change.
+    scroll_keymap: Option<Keymap<ScrollAction>>,
+    scroll_animator: ScrollAnimator,
+    clicks: ClickTracker,
     options: AnsiTextViewOptions,
     selection_anchor: Option<(usize, u32)>,
     selection: Option<((usize, u32), (usize, u32))>,
+    hover_row: Option<u16>,
+    scrollbar_markers: Vec<ScrollbarMarker>,
+    visual: VisualSelection,
+    /// Inverted so the derived `Default` starts focused.
+    unfocused: bool,
 }
 
 impl AnsiTextView {
@@ -86,7 +141,19 @@ impl AnsiTextView {
 
     /// Sets the ANSI input and updates internal content metrics.
     pub fn set_ansi(&mut self, input: &str) {
-        let text = ansi_text(input);
+        let tabs = self.options.tabs;
+        let mut text = parse_ansi(input);
+        self.tab_cols.clear();
+        if input.contains('\t') {
+            self.tab_cols = text
+                .lines
+                .iter()
+                .map(|l| tabs.tab_cols(l.spans.iter().map(|s| s.content.as_ref())))
+                .collect();
+            for line in &mut text.lines {
+                tabs.expand_line(line);
+            }
+        }
         self.lines = text.lines;
         self.max_content_width = self.lines.iter().map(line_width).max().unwrap_or(0);
         self.state
@@ -102,35 +169,138 @@ impl AnsiTextView {
     pub fn handle_event_action(&mut self, event: InputEvent) -> SelectionAction {
         match event {
             InputEvent::Paste(_) => SelectionAction::None,
+            InputEvent::Resize { .. } | InputEvent::FocusGained => SelectionAction::None,
+            InputEvent::FocusLost => {
+                // Drop half-typed chords and any in-progress drag; the release may never arrive.
+                self.scroll_chord.clear();
+                self.selection_anchor = None;
+                SelectionAction::Redraw
+            }
+            InputEvent::Tick(now) => {
+                let mut redraw =
+                    self.options
+                        .scroll
+                        .tick(&mut self.scroll_animator, &mut self.state, now);
+                if let ChordMatch::Matched(action) = self
+                    .scroll_keymap
+                    .get_or_insert_with(|| self.options.scroll.keymap())
+                    .expire(&mut self.scroll_chord, now)
+                {
+                    self.apply_scroll_action(action);
+                    redraw = true;
+                }
+                if redraw {
+                    SelectionAction::Redraw
+                } else {
+                    SelectionAction::None
+                }
+            }
             InputEvent::Mouse(m) => match m.kind {
                 MouseEventKind::ScrollUp => {
-                    self.state.scroll_y_by(-3);
+                    self.options.scroll.wheel(
+                        &mut self.scroll_animator,
+                        &mut self.state,
+                        -3,
+                        Instant::now(),
+                    );
                     SelectionAction::Redraw
                 }
                 MouseEventKind::ScrollDown => {
-                    self.state.scroll_y_by(3);
+                    self.options.scroll.wheel(
+                        &mut self.scroll_animator,
+                        &mut self.state,
+                        3,
+                        Instant::now(),
+                    );
                     SelectionAction::Redraw
                 }
-                _ => SelectionAction::None,
-            },
-            InputEvent::Key(key) => {
-                if self.options.enable_selection && self.options.selection.is_clear(&key) {
-                    self.clear_selection();
-                    return SelectionAction::Redraw;
+                MouseEventKind::ScrollLeft => {
+                    self.state.scroll_x_by(-self.options.scroll.horiz_step);
+                    SelectionAction::Redraw
                 }
-                if self.options.enable_selection && self.options.selection.is_copy(&key) {
-                    return self
-                        .selected_text()
-                        .map(SelectionAction::CopyRequested)
-                        .unwrap_or(SelectionAction::None);
+                MouseEventKind::ScrollRight => {
+                    self.state.scroll_x_by(self.options.scroll.horiz_step);
+                    SelectionAction::Redraw
                 }
+                _ => SelectionAction::None,
+            },
+            InputEvent::Key(key) => self.handle_key_event_at(key, Instant::now()),
+        }
+    }
 
-                let Some(action) = self.options.scroll.action_for(&key) else {
-                    return SelectionAction::None;
-                };
-                self.options.scroll.apply(&mut self.state, action);
-                SelectionAction::Redraw
+    /// Handles a key received at `now`.
+    ///
+    /// The timestamp drives [`ScrollBindings::chord_timeout`]; a prefix left pending is
+    /// resolved by a later [`InputEvent::Tick`].
+    pub fn handle_key_event_at(&mut self, key: KeyEvent, now: Instant) -> SelectionAction {
+        let action = self.handle_key(key, now);
+        // A bound chord prefix fired because the key could not continue it; handle the key too.
+        match self.scroll_chord.take_replay() {
+            Some(key) => match self.handle_key_event_at(key, now) {
+                SelectionAction::None => action,
+                replayed => replayed,
+            },
+            None => action,
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, now: Instant) -> SelectionAction {
+        if self.options.enable_selection && self.options.selection.is_clear(&key) {
+            self.clear_selection();
+            return SelectionAction::Redraw;
+        }
+        if self.options.enable_selection && self.options.selection.is_copy(&key) {
+            let text = self.selected_text();
+            if self.visual.is_active() {
+                self.clear_selection();
             }
+            return text
+                .map(SelectionAction::CopyRequested)
+                .unwrap_or(SelectionAction::None);
+        }
+        if self.options.enable_selection {
+            let step = self.visual.step(
+                &key,
+                &self.options.selection,
+                self.scroll_keymap
+                    .get_or_insert_with(|| self.options.scroll.keymap()),
+                &mut self.scroll_chord,
+                Some(now),
+            );
+            if let Some(action) = self.apply_visual_step(step) {
+                return action;
+            }
+        }
+
+        let action = match self
+            .scroll_keymap
+            .get_or_insert_with(|| self.options.scroll.keymap())
+            .feed(&mut self.scroll_chord, &key, Some(now))
+        {
+            ChordMatch::Matched(action) => action,
+            ChordMatch::Pending => return SelectionAction::Redraw,
+            ChordMatch::NoMatch => return SelectionAction::None,
+        };
+        self.options.scroll.apply(&mut self.state, action);
+        SelectionAction::Redraw
+    }
+
+    fn apply_visual_step(&mut self, step: VisualStep) -> Option<SelectionAction> {
+        let (lines, state) = (&self.lines, &mut self.state);
+        let action = self.visual.apply(step, state, lines.len(), |l| {
+            render::slice_spans_by_bytes(&lines[l].spans, 0, usize::MAX)
+        })?;
+        self.selection_anchor = None;
+        self.selection = self.visual.selection();
+        Some(action)
+    }
+
+    /// Applies a scroll action resolved outside a key press, moving the visual caret if active.
+    fn apply_scroll_action(&mut self, action: ScrollAction) {
+        if self.visual.is_active() {
+            self.apply_visual_step(VisualStep::Move(action.into()));
+        } else {
+            self.options.scroll.apply(&mut self.state, action);
         }
     }
 
@@ -150,7 +320,10 @@ impl AnsiTextView {
     ) -> SelectionAction {
         match event {
             InputEvent::Paste(_) => SelectionAction::None,
-            InputEvent::Key(_) => self.handle_event_action(event),
+            InputEvent::Resize { .. } | InputEvent::FocusGained => SelectionAction::None,
+            InputEvent::Key(_) | InputEvent::Tick(_) | InputEvent::FocusLost => {
+                self.handle_event_action(event)
+            }
             InputEvent::Mouse(m) => {
                 if self.handle_mouse_event(area, m) {
                     SelectionAction::Redraw
@@ -163,17 +336,44 @@ impl AnsiTextView {
 
     /// Handles mouse events for scrolling and drag-selection.
     pub fn handle_mouse_event(&mut self, area: Rect, event: MouseEvent) -> bool {
+        self.handle_mouse_event_at(area, event, Instant::now())
+    }
+
+    /// Like [`Self::handle_mouse_event`], but with the time the event was received.
+    ///
+    /// The timestamp drives double-click (select word) and triple-click (select line) detection.
+    pub fn handle_mouse_event_at(&mut self, area: Rect, event: MouseEvent, now: Instant) -> bool {
         if area.width == 0 || area.height == 0 {
             return false;
         }
 
+        let content_area = self.scrollbar_layout(area).content;
+
         match event.kind {
-            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+            MouseEventKind::ScrollUp => {
+                self.options
+                    .scroll
+                    .wheel(&mut self.scroll_animator, &mut self.state, -3, now);
+                return true;
+            }
+            MouseEventKind::ScrollDown => {
+                self.options
+                    .scroll
+                    .wheel(&mut self.scroll_animator, &mut self.state, 3, now);
+                return true;
+            }
+            MouseEventKind::ScrollLeft | MouseEventKind::ScrollRight => {
                 return !matches!(
                     self.handle_event_action(InputEvent::Mouse(event)),
                     SelectionAction::None
                 );
             }
+            MouseEventKind::Moved => {
+                let row = event.position_in(content_area).map(|(_, row)| row);
+                let changed = row != self.hover_row;
+                self.hover_row = row;
+                return changed;
+            }
             _ => {}
         }
 
@@ -184,12 +384,6 @@ impl AnsiTextView {
             return false;
         }
 
-        let content_area = if self.options.show_scrollbar && area.width >= 2 {
-            Rect::new(area.x, area.y, area.width - 1, area.height)
-        } else {
-            area
-        };
-
         let content_start_x = content_area.x;
         let content_end_x = content_area
             .x
@@ -243,8 +437,21 @@ impl AnsiTextView {
             .min(self.lines.len().saturating_sub(1) as u32) as usize;
         let col = self.state.x.saturating_add(rel_x);
 
+        let click = self.clicks.on_mouse(&event, now);
         match event.kind {
             MouseEventKind::Down(MouseButton::Left) => {
+                self.visual.stop();
+                let count = click.map_or(1, |c| c.count);
+                if let Some(sel) = selection::click_selection(count, line, col, || {
+                    self.lines
+                        .get(line)
+                        .map(|l| render::slice_spans_by_bytes(&l.spans, 0, usize::MAX))
+                        .unwrap_or_default()
+                }) {
+                    self.selection_anchor = None;
+                    self.selection = Some(sel);
+                    return true;
+                }
                 self.selection_anchor = Some((line, col));
                 self.selection = Some(((line, col), (line, col)));
                 true
@@ -268,15 +475,31 @@ impl AnsiTextView {
         }
     }
 
+    /// The line under the mouse pointer, tracked from [`MouseEventKind::Moved`] events.
+    pub fn hovered_line(&self) -> Option<usize> {
+        let line = self.state.y as usize + self.hover_row? as usize;
+        (line < self.lines.len()).then_some(line)
+    }
+
+    pub fn is_focused(&self) -> bool {
+        !self.unfocused
+    }
+
+    /// Sets focus. While unfocused the visual-mode caret is drawn with
+    /// [`crate::theme::ChromeTheme::cursor_unfocused`].
+    pub fn set_focused(&mut self, focused: bool) {
+        self.unfocused = !focused;
+    }
+
     pub fn clear_selection(&mut self) {
         self.selection_anchor = None;
         self.selection = None;
+        self.visual.stop();
     }
 
     pub fn selected_text(&self) -> Option<String> {
-        let ((l0, c0), (l1, c1)) = self.selection?;
-        let ((start_line, start_col), (end_line, end_col)) =
-            normalize_sel_inclusive((l0, c0), (l1, c1));
+        let sel = self.selection?;
+        let (start_line, end_line) = (sel.0.0.min(sel.1.0), sel.0.0.max(sel.1.0));
 
         let mut out = String::new();
         for line_idx in start_line..=end_line {
@@ -284,15 +507,7 @@ impl AnsiTextView {
             if line_idx > start_line {
                 out.push('\n');
             }
-            let (from, to) = if start_line == end_line {
-                (start_col, end_col)
-            } else if line_idx == start_line {
-                (start_col, u32::MAX)
-            } else if line_idx == end_line {
-                (0, end_col)
-            } else {
-                (0, u32::MAX)
-            };
+            let (from, to) = selection::line_cols(sel, self.visual.is_block(), line_idx)?;
 
             if let Some((bs, be)) = render::byte_range_for_cols_in_spans(&line.spans, from, to) {
                 out.push_str(&render::slice_spans_by_bytes(&line.spans, bs, be));
@@ -310,11 +525,7 @@ impl AnsiTextView {
     }
 
     pub fn set_viewport(&mut self, area: Rect) {
-        let content_area = if self.options.show_scrollbar && area.width >= 2 {
-            Rect::new(area.x, area.y, area.width - 1, area.height)
-        } else {
-            area
-        };
+        let content_area = self.scrollbar_layout(area).content;
         self.state
             .set_viewport(content_area.width, content_area.height);
     }
@@ -326,14 +537,8 @@ impl AnsiTextView {
 
         self.set_viewport(area);
 
-        let (content_area, scrollbar_x) = if self.options.show_scrollbar && area.width >= 2 {
-            (
-                Rect::new(area.x, area.y, area.width - 1, area.height),
-                Some(area.x + area.width - 1),
-            )
-        } else {
-            (area, None)
-        };
+        let layout = self.scrollbar_layout(area);
+        let content_area = layout.content;
 
         for row in 0..content_area.height {
             let y = content_area.y + row;
@@ -343,63 +548,107 @@ impl AnsiTextView {
                 theme.text_primary,
             );
             if let Some(line) = self.lines.get(idx) {
-                if self.options.enable_selection
-                    && let Some(((l0, c0), (l1, c1))) = self.selection
+                let selected = if self.options.enable_selection
+                    && let Some(sel) = self.selection
+                    && let Some((from, to)) = selection::line_cols(sel, self.visual.is_block(), idx)
+                    && let Some((bs, be)) =
+                        render::byte_range_for_cols_in_spans(&line.spans, from, to)
                 {
-                    let ((start_line, start_col), (end_line, end_col)) =
-                        normalize_sel_inclusive((l0, c0), (l1, c1));
-                    if idx >= start_line && idx <= end_line {
-                        let (from, to) = if start_line == end_line {
-                            (start_col, end_col)
-                        } else if idx == start_line {
-                            (start_col, u32::MAX)
-                        } else if idx == end_line {
-                            (0, end_col)
-                        } else {
-                            (0, u32::MAX)
-                        };
-                        if let Some((bs, be)) =
-                            render::byte_range_for_cols_in_spans(&line.spans, from, to)
-                        {
-                            let spans = render::apply_modifier_to_byte_ranges(
-                                line.spans.clone(),
-                                &[(bs, be)],
-                                Modifier::REVERSED,
-                            );
-                            render::render_spans_clipped(
-                                content_area.x,
-                                y,
-                                self.state.x,
-                                content_area.width,
-                                buf,
-                                &spans,
-                                theme.text_primary,
-                            );
-                            continue;
-                        }
-                    }
-                }
-
+                    Some(render::apply_style_to_byte_ranges(
+                        line.spans.clone(),
+                        &[(bs, be)],
+                        theme.selection_style(),
+                    ))
+                } else {
+                    None
+                };
                 render::render_spans_clipped(
                     content_area.x,
                     y,
                     self.state.x,
                     content_area.width,
                     buf,
-                    &line.spans,
+                    selected.as_deref().unwrap_or(&line.spans),
                     theme.text_primary,
                 );
+                if let Some(cols) = self.tab_cols.get(idx) {
+                    render::render_tab_glyphs(
+                        content_area.x,
+                        y,
+                        self.state.x,
+                        content_area.width,
+                        buf,
+                        cols,
+                        self.options.tabs,
+                    );
+                }
             }
         }
 
-        if let Some(sb_x) = scrollbar_x {
-            render::render_scrollbar(
-                Rect::new(sb_x, area.y, 1, area.height),
+        if let Some(line) = self.hovered_line() {
+            render::render_line_highlight(content_area, buf, &self.state, line, theme.chrome.hover);
+        }
+        if let Some(caret) = self.visual.caret() {
+            render::render_caret(
+                content_area,
                 buf,
                 &self.state,
-                theme.text_muted,
+                caret,
+                theme.chrome.cursor_style(self.is_focused()),
             );
         }
+        if let Some(track) = layout.vertical {
+            render::render_scrollbar(track, buf, &self.state, theme.chrome.scrollbar);
+            let mut markers = self.scrollbar_markers.clone();
+            if self.options.enable_selection
+                && let Some(((l0, c0), (l1, c1))) = self.selection
+            {
+                let ((start, _), (end, _)) = normalize_sel_inclusive((l0, c0), (l1, c1));
+                markers.push(ScrollbarMarker::range(
+                    start as u32..end as u32 + 1,
+                    MarkerKind::Selection,
+                ));
+            }
+            render::render_scrollbar_markers(track, buf, &self.state, &markers, &theme.chrome);
+        }
+        if let Some(track) = layout.horizontal {
+            render::render_scrollbar_x(track, buf, &self.state, theme.chrome.scrollbar);
+        }
+        theme.color_profile.apply_to_buffer(buf, area);
+    }
+
+    /// Sets the markers drawn on the vertical scrollbar (search hits, diagnostics, ...), in
+    /// lines. The selection is marked automatically.
+    pub fn set_scrollbar_markers(&mut self, markers: Vec<ScrollbarMarker>) {
+        self.scrollbar_markers = markers;
+    }
+
+    fn scrollbar_layout(&self, area: Rect) -> ScrollbarLayout {
+        ScrollbarLayout::new(
+            area,
+            self.options.show_scrollbar,
+            self.options.show_scrollbar_x,
+        )
+    }
+}
+
+impl Component for AnsiTextView {
+    type Action = SelectionAction;
+
+    fn handle_event(&mut self, area: Rect, event: InputEvent) -> SelectionAction {
+        self.handle_event_action_in_area(area, event)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        self.render_ref(area, buf, theme);
+    }
+
+    fn bindings(&self) -> Vec<BindingGroup> {
+        self.options.help_groups()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        AnsiTextView::set_focused(self, focused);
     }
 }
 
@@ -438,4 +687,57 @@ mod tests {
         let mut buf = Buffer::empty(Rect::new(0, 0, 10, 2));
         v.render_ref(Rect::new(0, 0, 10, 2), &mut buf, &theme);
     }
+
+    #[test]
+    fn tabs_align_to_visible_columns() {
+        let text = ansi_text("\u{1b}[31mab\u{1b}[0m\tc");
+        let plain: String = text.lines[0]
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(plain, "ab  c");
+
+        let tabs = TabPolicy::new(8).with_glyph(Some('→'));
+        let text = ansi_text_with_tabs("\tc", tabs);
+        assert_eq!(text.lines[0].spans[0].content, "        c");
+
+        let mut v = AnsiTextView::with_options(AnsiTextViewOptions {
+            show_scrollbar: false,
+            tabs,
+            ..Default::default()
+        });
+        v.set_ansi("\u{1b}[31mab\u{1b}[0m\tc");
+        let area = Rect::new(0, 0, 10, 1);
+        let mut buf = Buffer::empty(area);
+        v.render_ref(area, &mut buf, &Theme::default());
+        assert_eq!(buf[(2, 0)].symbol(), "→");
+        assert_eq!(buf[(8, 0)].symbol(), "c");
+        v.selection = Some(((0, 0), (0, 8)));
+        assert_eq!(v.selected_text().as_deref(), Some("ab      c"));
+    }
+
+    #[test]
+    fn focus_lost_drops_pending_chord_and_drag() {
+        let mut v = AnsiTextView::new();
+        v.set_ansi(&"x\n".repeat(50));
+        v.set_viewport(Rect::new(0, 0, 20, 5));
+        v.state.y = 10;
+        let now = Instant::now();
+        let g = crate::keymap::key_char('g');
+        assert_eq!(
+            v.handle_key_event_at(g.clone(), now),
+            SelectionAction::Redraw
+        );
+        v.selection_anchor = Some((0, 0));
+
+        assert_eq!(
+            v.handle_event_action(InputEvent::FocusLost),
+            SelectionAction::Redraw
+        );
+        assert_eq!(v.selection_anchor, None);
+        // The next `g` starts a new chord instead of completing `g g`.
+        assert_eq!(v.handle_key_event_at(g, now), SelectionAction::Redraw);
+        assert_eq!(v.state.y, 10);
+    }
 }
@@ -4,7 +4,6 @@ use parser::DiffLineKind;
 use parser::ParsedDiff;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::text::Line;
 use ratatui::text::Span;
@@ -16,16 +15,33 @@ use std::collections::hash_map::DefaultHasher;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::sync::Arc;
+use std::time::Instant;
 
+use crate::component::Component;
+use crate::help;
+use crate::help::BindingGroup;
+use crate::input::ClickTracker;
 use crate::input::MouseButton;
 use crate::input::MouseEvent;
 use crate::input::MouseEventKind;
+use crate::keymap::ChordMatch;
+use crate::keymap::ChordState;
+use crate::keymap::Keymap;
 use crate::render;
+use crate::render::MarkerKind;
+use crate::render::ScrollbarLayout;
+use crate::render::ScrollbarMarker;
+use crate::scroll::ScrollAction;
 use crate::scroll::ScrollBindings;
+use crate::selection;
 use crate::selection::SelectionAction;
 use crate::selection::SelectionBindings;
+use crate::selection::VisualSelection;
+use crate::selection::VisualStep;
+use crate::tabs::TabPolicy;
 use crate::text::CodeHighlighter;
 use crate::theme::Theme;
+use crate::viewport::ScrollAnimator;
 use crate::viewport::ViewportState;
 
 /// Options for [`DiffView`].
@@ -35,15 +51,20 @@ use crate::viewport::ViewportState;
 /// - optional line numbers
 /// - optional syntax highlighting for code-like lines
 /// - mouse drag selection + copy-on-request
+/// - keyboard visual-mode selection (see [`VisualSelection`])
 #[derive(Clone, Debug)]
 pub struct DiffViewOptions {
     pub show_line_numbers: bool,
     pub show_scrollbar: bool,
+    /// Draws a horizontal scrollbar on the bottom row.
+    pub show_scrollbar_x: bool,
     pub highlight_hunks: bool,
     pub highlight_inline_changes: bool,
     pub scroll: ScrollBindings,
     pub enable_selection: bool,
     pub selection: SelectionBindings,
+    /// How tabs are expanded; applies to diffs set after the change.
+    pub tabs: TabPolicy,
 }
 
 impl Default for DiffViewOptions {
@@ -51,15 +72,27 @@ impl Default for DiffViewOptions {
         Self {
             show_line_numbers: true,
             show_scrollbar: true,
+            show_scrollbar_x: false,
             highlight_hunks: true,
             highlight_inline_changes: true,
             scroll: ScrollBindings::default(),
             enable_selection: true,
             selection: SelectionBindings::default(),
+            tabs: TabPolicy::default(),
         }
     }
 }
 
+impl DiffViewOptions {
+    /// Describes the configured bindings for help screens (see [`help::HelpPanel`]).
+    pub fn help_groups(&self) -> Vec<BindingGroup> {
+        help::view_help_groups(
+            &self.scroll,
+            self.enable_selection.then_some(&self.selection),
+        )
+    }
+}
+
 /// A scrollable unified-diff viewer with optional syntax highlighting and selection + copy.
 ///
 /// - The diff input is parsed via [`set_diff`](Self::set_diff).
@@ -69,6 +102,11 @@ impl Default for DiffViewOptions {
 pub struct DiffView {
     parsed: ParsedDiff,
     pub state: ViewportState,
+    scroll_chord: ChordState,
+    /// Built from `options.scroll` on first use and dropped when the options change.
+    scroll_keymap: Option<Keymap<ScrollAction>>,
+    scroll_animator: ScrollAnimator,
+    clicks: ClickTracker,
     options: DiffViewOptions,
     highlighter: Option<Arc<dyn CodeHighlighter + Send + Sync>>,
     language_override: Option<String>,
@@ -77,6 +115,12 @@ pub struct DiffView {
     highlight_scratch: String,
     selection_anchor: Option<(usize, u32)>,
     selection: Option<((usize, u32), (usize, u32))>,
+    hover_row: Option<u16>,
+    hunk_markers: Vec<ScrollbarMarker>,
+    scrollbar_markers: Vec<ScrollbarMarker>,
+    visual: VisualSelection,
+    /// Inverted so the derived `Default` starts focused.
+    unfocused: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -92,6 +136,10 @@ impl Clone for DiffView {
         Self {
             parsed: self.parsed.clone(),
             state: self.state,
+            scroll_chord: ChordState::default(),
+            scroll_keymap: self.scroll_keymap.clone(),
+            scroll_animator: ScrollAnimator::default(),
+            clicks: ClickTracker::default(),
             options: self.options.clone(),
             highlighter: self.highlighter.clone(),
             language_override: self.language_override.clone(),
@@ -100,6 +148,11 @@ impl Clone for DiffView {
             highlight_scratch: String::new(),
             selection_anchor: self.selection_anchor,
             selection: self.selection,
+            hover_row: self.hover_row,
+            hunk_markers: self.hunk_markers.clone(),
+            scrollbar_markers: self.scrollbar_markers.clone(),
+            visual: self.visual,
+            unfocused: self.unfocused,
         }
     }
 }
@@ -130,12 +183,20 @@ impl DiffView {
 
     /// Parses a unified diff string and updates internal layout metrics.
     pub fn set_diff(&mut self, diff: &str) {
-        self.parsed = parser::parse_unified_diff(diff);
+        self.parsed = parser::parse_unified_diff(diff, self.options.tabs);
         self.inline_ranges = if self.options.highlight_inline_changes {
             compute_inline_ranges(&self.parsed)
         } else {
             HashMap::new()
         };
+        self.hunk_markers = self
+            .parsed
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.kind == DiffLineKind::HunkHeader)
+            .map(|(idx, _)| ScrollbarMarker::new(idx as u32, MarkerKind::Hunk))
+            .collect();
         self.invalidate_highlighting();
         self.state.set_content(
             self.parsed.max_content_width as u32,
@@ -155,11 +216,7 @@ impl DiffView {
         };
         let _ = (old_w, new_w);
 
-        let content_area = if self.options.show_scrollbar && area.width >= 2 {
-            Rect::new(area.x, area.y, area.width - 1, area.height)
-        } else {
-            area
-        };
+        let content_area = self.scrollbar_layout(area).content;
 
         let viewport_w = content_area.width.saturating_sub(gutter_w);
         self.state.set_viewport(viewport_w, content_area.height);
@@ -184,25 +241,116 @@ impl DiffView {
     pub fn handle_event_action(&mut self, event: crate::input::InputEvent) -> SelectionAction {
         match event {
             crate::input::InputEvent::Paste(_) => SelectionAction::None,
-            crate::input::InputEvent::Mouse(_) => SelectionAction::None,
-            crate::input::InputEvent::Key(key) => {
-                if self.options.enable_selection && self.options.selection.is_clear(&key) {
-                    self.clear_selection();
-                    return SelectionAction::Redraw;
+            crate::input::InputEvent::Resize { .. } | crate::input::InputEvent::FocusGained => {
+                SelectionAction::None
+            }
+            crate::input::InputEvent::FocusLost => {
+                // Drop half-typed chords and any in-progress drag; the release may never arrive.
+                self.scroll_chord.clear();
+                self.selection_anchor = None;
+                SelectionAction::Redraw
+            }
+            crate::input::InputEvent::Tick(now) => {
+                let mut redraw =
+                    self.options
+                        .scroll
+                        .tick(&mut self.scroll_animator, &mut self.state, now);
+                if let ChordMatch::Matched(action) = self
+                    .scroll_keymap
+                    .get_or_insert_with(|| self.options.scroll.keymap())
+                    .expire(&mut self.scroll_chord, now)
+                {
+                    self.apply_scroll_action(action);
+                    redraw = true;
                 }
-                if self.options.enable_selection && self.options.selection.is_copy(&key) {
-                    return self
-                        .selected_text()
-                        .map(SelectionAction::CopyRequested)
-                        .unwrap_or(SelectionAction::None);
+                if redraw {
+                    SelectionAction::Redraw
+                } else {
+                    SelectionAction::None
                 }
+            }
+            crate::input::InputEvent::Mouse(_) => SelectionAction::None,
+            crate::input::InputEvent::Key(key) => self.handle_key_event_at(key, Instant::now()),
+        }
+    }
 
-                let Some(action) = self.options.scroll.action_for(&key) else {
-                    return SelectionAction::None;
-                };
-                self.options.scroll.apply(&mut self.state, action);
-                SelectionAction::Redraw
+    /// Handles a key received at `now`.
+    ///
+    /// The timestamp drives [`ScrollBindings::chord_timeout`]; a prefix left pending is
+    /// resolved by a later [`InputEvent::Tick`](crate::input::InputEvent::Tick).
+    pub fn handle_key_event_at(
+        &mut self,
+        key: crate::input::KeyEvent,
+        now: Instant,
+    ) -> SelectionAction {
+        let action = self.handle_key(key, now);
+        // A bound chord prefix fired because the key could not continue it; handle the key too.
+        match self.scroll_chord.take_replay() {
+            Some(key) => match self.handle_key_event_at(key, now) {
+                SelectionAction::None => action,
+                replayed => replayed,
+            },
+            None => action,
+        }
+    }
+
+    fn handle_key(&mut self, key: crate::input::KeyEvent, now: Instant) -> SelectionAction {
+        if self.options.enable_selection && self.options.selection.is_clear(&key) {
+            self.clear_selection();
+            return SelectionAction::Redraw;
+        }
+        if self.options.enable_selection && self.options.selection.is_copy(&key) {
+            let text = self.selected_text();
+            if self.visual.is_active() {
+                self.clear_selection();
             }
+            return text
+                .map(SelectionAction::CopyRequested)
+                .unwrap_or(SelectionAction::None);
+        }
+        if self.options.enable_selection {
+            let step = self.visual.step(
+                &key,
+                &self.options.selection,
+                self.scroll_keymap
+                    .get_or_insert_with(|| self.options.scroll.keymap()),
+                &mut self.scroll_chord,
+                Some(now),
+            );
+            if let Some(action) = self.apply_visual_step(step) {
+                return action;
+            }
+        }
+
+        let action = match self
+            .scroll_keymap
+            .get_or_insert_with(|| self.options.scroll.keymap())
+            .feed(&mut self.scroll_chord, &key, Some(now))
+        {
+            ChordMatch::Matched(action) => action,
+            ChordMatch::Pending => return SelectionAction::Redraw,
+            ChordMatch::NoMatch => return SelectionAction::None,
+        };
+        self.options.scroll.apply(&mut self.state, action);
+        SelectionAction::Redraw
+    }
+
+    fn apply_visual_step(&mut self, step: VisualStep) -> Option<SelectionAction> {
+        let (lines, state) = (&self.parsed.lines, &mut self.state);
+        let action = self
+            .visual
+            .apply(step, state, lines.len(), |l| lines[l].content.clone())?;
+        self.selection_anchor = None;
+        self.selection = self.visual.selection();
+        Some(action)
+    }
+
+    /// Applies a scroll action resolved outside a key press, moving the visual caret if active.
+    fn apply_scroll_action(&mut self, action: ScrollAction) {
+        if self.visual.is_active() {
+            self.apply_visual_step(VisualStep::Move(action.into()));
+        } else {
+            self.options.scroll.apply(&mut self.state, action);
         }
     }
 
@@ -222,7 +370,12 @@ impl DiffView {
     ) -> SelectionAction {
         match event {
             crate::input::InputEvent::Paste(_) => SelectionAction::None,
-            crate::input::InputEvent::Key(_) => self.handle_event_action(event),
+            crate::input::InputEvent::Resize { .. } | crate::input::InputEvent::FocusGained => {
+                SelectionAction::None
+            }
+            crate::input::InputEvent::Key(_)
+            | crate::input::InputEvent::Tick(_)
+            | crate::input::InputEvent::FocusLost => self.handle_event_action(event),
             crate::input::InputEvent::Mouse(m) => {
                 if self.handle_mouse_event(area, m) {
                     SelectionAction::Redraw
@@ -235,6 +388,13 @@ impl DiffView {
 
     /// Handles mouse events for scrolling and drag-selection.
     pub fn handle_mouse_event(&mut self, area: Rect, event: MouseEvent) -> bool {
+        self.handle_mouse_event_at(area, event, Instant::now())
+    }
+
+    /// Like [`Self::handle_mouse_event`], but with the time the event was received.
+    ///
+    /// The timestamp drives double-click (select word) and triple-click (select line) detection.
+    pub fn handle_mouse_event_at(&mut self, area: Rect, event: MouseEvent, now: Instant) -> bool {
         if area.width == 0 || area.height == 0 {
             return false;
         }
@@ -243,15 +403,35 @@ impl DiffView {
             return false;
         }
 
+        let content_area = self.scrollbar_layout(area).content;
+
         match event.kind {
             MouseEventKind::ScrollUp => {
-                self.state.scroll_y_by(-3);
+                self.options
+                    .scroll
+                    .wheel(&mut self.scroll_animator, &mut self.state, -3, now);
                 return true;
             }
             MouseEventKind::ScrollDown => {
-                self.state.scroll_y_by(3);
+                self.options
+                    .scroll
+                    .wheel(&mut self.scroll_animator, &mut self.state, 3, now);
+                return true;
+            }
+            MouseEventKind::ScrollLeft => {
+                self.state.scroll_x_by(-self.options.scroll.horiz_step);
                 return true;
             }
+            MouseEventKind::ScrollRight => {
+                self.state.scroll_x_by(self.options.scroll.horiz_step);
+                return true;
+            }
+            MouseEventKind::Moved => {
+                let row = event.position_in(content_area).map(|(_, row)| row);
+                let changed = row != self.hover_row;
+                self.hover_row = row;
+                return changed;
+            }
             _ => {}
         }
 
@@ -259,12 +439,6 @@ impl DiffView {
             return false;
         }
 
-        let content_area = if self.options.show_scrollbar && area.width >= 2 {
-            Rect::new(area.x, area.y, area.width - 1, area.height)
-        } else {
-            area
-        };
-
         let gutter_w = if self.options.show_line_numbers {
             let old_w = digits(self.parsed.max_old_lineno).max(1);
             let new_w = digits(self.parsed.max_new_lineno).max(1);
@@ -327,8 +501,22 @@ impl DiffView {
             .min(self.parsed.lines.len().saturating_sub(1) as u32) as usize;
         let col = self.state.x.saturating_add(rel_x);
 
+        let click = self.clicks.on_mouse(&event, now);
         match event.kind {
             MouseEventKind::Down(MouseButton::Left) => {
+                self.visual.stop();
+                let count = click.map_or(1, |c| c.count);
+                if let Some(sel) = selection::click_selection(count, line, col, || {
+                    self.parsed
+                        .lines
+                        .get(line)
+                        .map(|l| l.content.clone())
+                        .unwrap_or_default()
+                }) {
+                    self.selection_anchor = None;
+                    self.selection = Some(sel);
+                    return true;
+                }
                 self.selection_anchor = Some((line, col));
                 self.selection = Some(((line, col), (line, col)));
                 true
@@ -355,15 +543,31 @@ impl DiffView {
         }
     }
 
+    /// The diff line under the mouse pointer, tracked from [`MouseEventKind::Moved`] events.
+    pub fn hovered_line(&self) -> Option<usize> {
+        let line = self.state.y as usize + self.hover_row? as usize;
+        (line < self.parsed.lines.len()).then_some(line)
+    }
+
+    pub fn is_focused(&self) -> bool {
+        !self.unfocused
+    }
+
+    /// Sets focus. While unfocused the visual-mode caret is drawn with
+    /// [`crate::theme::ChromeTheme::cursor_unfocused`].
+    pub fn set_focused(&mut self, focused: bool) {
+        self.unfocused = !focused;
+    }
+
     pub fn clear_selection(&mut self) {
         self.selection_anchor = None;
         self.selection = None;
+        self.visual.stop();
     }
 
     pub fn selected_text(&self) -> Option<String> {
-        let ((l0, c0), (l1, c1)) = self.selection?;
-        let ((start_line, start_col), (end_line, end_col)) =
-            normalize_sel_inclusive((l0, c0), (l1, c1));
+        let sel = self.selection?;
+        let (start_line, end_line) = (sel.0.0.min(sel.1.0), sel.0.0.max(sel.1.0));
 
         let mut out = String::new();
         for line_idx in start_line..=end_line {
@@ -371,15 +575,7 @@ impl DiffView {
             if line_idx > start_line {
                 out.push('\n');
             }
-            let (from, to) = if start_line == end_line {
-                (start_col, end_col)
-            } else if line_idx == start_line {
-                (start_col, u32::MAX)
-            } else if line_idx == end_line {
-                (0, end_col)
-            } else {
-                (0, u32::MAX)
-            };
+            let (from, to) = selection::line_cols(sel, self.visual.is_block(), line_idx)?;
             if let Some((bs, be)) = render::byte_range_for_cols(&line.content, from, to) {
                 out.push_str(&line.content[bs..be]);
             }
@@ -394,16 +590,17 @@ impl DiffView {
 
         self.set_viewport(area);
 
-        let code_bg = self.highlighter.as_ref().and_then(|h| h.background_color());
+        // Monochrome output drops highlighting entirely; the gutter markers carry add/del.
+        let highlight =
+            self.options.highlight_hunks && self.highlighter.is_some() && !theme.is_monochrome();
+        let code_bg = self
+            .highlighter
+            .as_ref()
+            .filter(|_| !theme.is_monochrome())
+            .and_then(|h| h.background_color());
 
-        let (content_area, scrollbar_x) = if self.options.show_scrollbar && area.width >= 2 {
-            (
-                Rect::new(area.x, area.y, area.width - 1, area.height),
-                Some(area.x + area.width - 1),
-            )
-        } else {
-            (area, None)
-        };
+        let layout = self.scrollbar_layout(area);
+        let content_area = layout.content;
 
         let (old_w, new_w, gutter_w) = if self.options.show_line_numbers {
             let old_w = digits(self.parsed.max_old_lineno).max(1);
@@ -419,11 +616,7 @@ impl DiffView {
         let start = self.state.y as usize;
         let end = (start + content_area.height as usize).min(self.parsed.lines.len());
 
-        let highlighted = if self.options.highlight_hunks
-            && self.highlighter.is_some()
-            && content_w > 0
-            && start < end
-        {
+        let highlighted = if highlight && content_w > 0 && start < end {
             Some(self.highlight_visible_cached(start, end))
         } else {
             None
@@ -434,9 +627,9 @@ impl DiffView {
             let idx = (self.state.y as usize).saturating_add(row as usize);
             let Some(line) = self.parsed.lines.get(idx) else {
                 let style = if let Some(bg) = code_bg {
-                    theme.text_primary.bg(bg)
+                    theme.diff.context.bg(bg)
                 } else {
-                    theme.text_primary
+                    theme.diff.context
                 };
                 buf.set_style(Rect::new(content_area.x, y, content_area.width, 1), style);
                 continue;
@@ -502,37 +695,20 @@ impl DiffView {
                     if self.options.highlight_inline_changes
                         && let Some(ranges) = self.inline_ranges.get(&idx)
                     {
-                        spans = render::apply_modifier_to_byte_ranges(
-                            spans,
-                            ranges,
-                            Modifier::REVERSED,
-                        );
+                        spans =
+                            render::apply_style_to_byte_ranges(spans, ranges, theme.diff.intraline);
                     }
                     if self.options.enable_selection
-                        && let Some(((l0, c0), (l1, c1))) = self.selection
+                        && let Some(sel) = self.selection
+                        && let Some((from, to)) =
+                            selection::line_cols(sel, self.visual.is_block(), idx)
+                        && let Some((bs, be)) = render::byte_range_for_cols(&line.content, from, to)
                     {
-                        let ((start_line, start_col), (end_line, end_col)) =
-                            normalize_sel_inclusive((l0, c0), (l1, c1));
-                        if idx >= start_line && idx <= end_line {
-                            let (from, to) = if start_line == end_line {
-                                (start_col, end_col)
-                            } else if idx == start_line {
-                                (start_col, u32::MAX)
-                            } else if idx == end_line {
-                                (0, end_col)
-                            } else {
-                                (0, u32::MAX)
-                            };
-                            if let Some((bs, be)) =
-                                render::byte_range_for_cols(&line.content, from, to)
-                            {
-                                spans = render::apply_modifier_to_byte_ranges(
-                                    spans,
-                                    &[(bs, be)],
-                                    Modifier::REVERSED,
-                                );
-                            }
-                        }
+                        spans = render::apply_style_to_byte_ranges(
+                            spans,
+                            &[(bs, be)],
+                            theme.selection_style(),
+                        );
                     }
                     render::render_spans_clipped(
                         content_area.x + gutter_w,
@@ -556,16 +732,72 @@ impl DiffView {
                     );
                 }
             }
+            render::render_tab_glyphs(
+                content_area.x + gutter_w,
+                y,
+                self.state.x,
+                content_w,
+                buf,
+                &line.tab_cols,
+                self.options.tabs,
+            );
         }
 
-        if let Some(sb_x) = scrollbar_x {
-            render::render_scrollbar(
-                Rect::new(sb_x, area.y, 1, area.height),
+        let text_area = Rect {
+            x: content_area.x + gutter_w.min(content_area.width),
+            width: content_w,
+            ..content_area
+        };
+        if let Some(line) = self.hovered_line() {
+            render::render_line_highlight(text_area, buf, &self.state, line, theme.chrome.hover);
+        }
+        if let Some(caret) = self.visual.caret() {
+            render::render_caret(
+                text_area,
                 buf,
                 &self.state,
-                theme.text_muted,
+                caret,
+                theme.chrome.cursor_style(self.is_focused()),
             );
         }
+        if let Some(track) = layout.vertical {
+            render::render_scrollbar(track, buf, &self.state, theme.chrome.scrollbar);
+            let mut markers = self.hunk_markers.clone();
+            markers.extend(self.scrollbar_markers.iter().cloned());
+            if self.options.enable_selection
+                && let Some(((l0, c0), (l1, c1))) = self.selection
+            {
+                let ((start, _), (end, _)) = normalize_sel_inclusive((l0, c0), (l1, c1));
+                markers.push(ScrollbarMarker::range(
+                    start as u32..end as u32 + 1,
+                    MarkerKind::Selection,
+                ));
+            }
+            render::render_scrollbar_markers(track, buf, &self.state, &markers, &theme.chrome);
+        }
+        if let Some(track) = layout.horizontal {
+            let track = Rect {
+                x: track.x + gutter_w.min(track.width),
+                width: track.width.saturating_sub(gutter_w),
+                ..track
+            };
+            render::render_scrollbar_x(track, buf, &self.state, theme.chrome.scrollbar);
+        }
+        theme.color_profile.apply_to_buffer(buf, area);
+    }
+
+    /// Sets extra markers for the vertical scrollbar (search hits, diagnostics, ...), in diff
+    /// lines. Hunk headers and the selection are marked automatically.
+    pub fn set_scrollbar_markers(&mut self, markers: Vec<ScrollbarMarker>) {
+        self.scrollbar_markers = markers;
+    }
+
+    fn scrollbar_layout(&self, area: Rect) -> ScrollbarLayout {
+        ScrollbarLayout::new(
+            area,
+            self.options.show_scrollbar,
+            self.options.show_scrollbar_x,
+        )
     }
 
     pub fn as_text(&self, theme: &Theme) -> Text<'static> {
@@ -579,11 +811,13 @@ impl DiffView {
 
     pub fn lines_for_transcript(&mut self, theme: &Theme) -> Vec<Line<'static>> {
         let mut out: Vec<Line<'static>> = Vec::with_capacity(self.parsed.lines.len());
-        let highlighted = if self.options.highlight_hunks && self.highlighter.is_some() {
-            Some(self.highlight_visible_cached(0, self.parsed.lines.len()))
-        } else {
-            None
-        };
+        let highlighted =
+            if self.options.highlight_hunks && self.highlighter.is_some() && !theme.is_monochrome()
+            {
+                Some(self.highlight_visible_cached(0, self.parsed.lines.len()))
+            } else {
+                None
+            };
 
         for (idx, l) in self.parsed.lines.iter().enumerate() {
             let line_style = style_for_kind(theme, l.kind);
@@ -606,7 +840,7 @@ impl DiffView {
                         && let Some(ranges) = self.inline_ranges.get(&idx)
                     {
                         rest =
-                            render::apply_modifier_to_byte_ranges(rest, ranges, Modifier::REVERSED);
+                            render::apply_style_to_byte_ranges(rest, ranges, theme.diff.intraline);
                     }
                     spans.extend(rest);
                     Line::from(spans)
@@ -731,6 +965,26 @@ impl DiffView {
     }
 }
 
+impl Component for DiffView {
+    type Action = SelectionAction;
+
+    fn handle_event(&mut self, area: Rect, event: crate::input::InputEvent) -> SelectionAction {
+        self.handle_event_action_in_area(area, event)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        self.render_ref(area, buf, theme);
+    }
+
+    fn bindings(&self) -> Vec<BindingGroup> {
+        self.options.help_groups()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        DiffView::set_focused(self, focused);
+    }
+}
+
 fn highlight_inputs_hash(
     parsed: &ParsedDiff,
     language_override: Option<&str>,
@@ -797,19 +1051,20 @@ fn marker_for_kind(kind: DiffLineKind) -> char {
 
 fn style_for_kind(theme: &Theme, kind: DiffLineKind) -> Style {
     match kind {
-        DiffLineKind::Add => theme.diff_add,
-        DiffLineKind::Del => theme.diff_del,
-        DiffLineKind::HunkHeader => theme.accent.add_modifier(Modifier::BOLD),
-        DiffLineKind::FileHeader => theme.text_muted,
-        DiffLineKind::Context | DiffLineKind::Meta => theme.text_primary,
+        DiffLineKind::Add => theme.diff.add,
+        DiffLineKind::Del => theme.diff.del,
+        DiffLineKind::HunkHeader => theme.diff.hunk_header,
+        DiffLineKind::FileHeader => theme.diff.file_header,
+        DiffLineKind::Context => theme.diff.context,
+        DiffLineKind::Meta => theme.diff.meta,
     }
 }
 
 fn gutter_style_for_kind(theme: &Theme, kind: DiffLineKind) -> Style {
     match kind {
-        DiffLineKind::Add => theme.diff_add,
-        DiffLineKind::Del => theme.diff_del,
-        _ => theme.text_muted,
+        DiffLineKind::Add => theme.diff.add,
+        DiffLineKind::Del => theme.diff.del,
+        _ => theme.diff.gutter,
     }
 }
 
@@ -929,6 +1184,7 @@ mod tests {
     use ratatui::buffer::Buffer;
     use ratatui::layout::Rect;
     use ratatui::style::Color;
+    use ratatui::style::Modifier;
     use std::sync::atomic::AtomicUsize;
     use std::sync::atomic::Ordering;
 
@@ -978,6 +1234,26 @@ diff --git a/a.txt b/a.txt
         view.render_ref(Rect::new(0, 0, 2, 2), &mut buf, &theme);
     }
 
+    #[test]
+    fn scrollbar_marks_hunk_headers() {
+        let mut diff = String::from("--- a/f\n+++ b/f\n@@ -1,20 +1,20 @@\n");
+        for i in 0..20 {
+            diff.push_str(&format!(" line {i}\n"));
+        }
+        diff.push_str("@@ -40,2 +40,2 @@\n-old\n+new\n");
+        let mut view = DiffView::new();
+        view.set_diff(&diff);
+        let theme = Theme::default();
+        let area = Rect::new(0, 0, 30, 5);
+        let mut buf = Buffer::empty(area);
+        view.render_ref(area, &mut buf, &theme);
+
+        let track: Vec<&str> = (0..5).map(|y| buf[(29, y)].symbol()).collect();
+        let hunk = MarkerKind::Hunk.glyph();
+        assert_eq!(track.iter().filter(|s| **s == hunk).count(), 2, "{track:?}");
+        assert_eq!(track[4], hunk);
+    }
+
     #[test]
     fn renders_with_highlighter() {
         let diff = "\
@@ -1040,6 +1316,53 @@ diff --git a/a.txt b/a.txt
         );
     }
 
+    #[test]
+    fn monochrome_strips_highlighting_and_keeps_markers() {
+        struct ItalicHighlighter;
+
+        impl CodeHighlighter for ItalicHighlighter {
+            fn highlight_lines(
+                &self,
+                _language: Option<&str>,
+                lines: &[&str],
+            ) -> Vec<Vec<Span<'static>>> {
+                let style = Style::default()
+                    .fg(Color::Rgb(1, 2, 3))
+                    .add_modifier(Modifier::ITALIC);
+                lines
+                    .iter()
+                    .map(|l| vec![Span::styled((*l).to_string(), style)])
+                    .collect()
+            }
+        }
+
+        let diff = "\
+@@ -1,1 +1,1 @@
+-a
++b
+";
+        let mut view = DiffView::with_options(DiffViewOptions {
+            show_line_numbers: false,
+            show_scrollbar: false,
+            ..Default::default()
+        });
+        view.set_highlighter(Some(Arc::new(ItalicHighlighter)));
+        view.set_diff(diff);
+        view.selection = Some(((2, 0), (2, 0)));
+
+        let theme = Theme::monochrome();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 3));
+        view.render_ref(buf.area, &mut buf, &theme);
+
+        assert_eq!(buf[(0, 1)].symbol(), "-");
+        assert_eq!(buf[(0, 2)].symbol(), "+");
+        for cell in buf.content() {
+            assert_eq!((cell.fg, cell.bg), (Color::Reset, Color::Reset));
+            assert!(!cell.modifier.contains(Modifier::ITALIC));
+        }
+        assert!(buf[(2, 2)].modifier.contains(Modifier::REVERSED));
+    }
+
     #[test]
     fn caches_visible_highlighting_across_renders() {
         #[derive(Default)]
@@ -1163,4 +1486,70 @@ diff --git a/a.txt b/a.txt
 
         assert_eq!(view.selected_text().as_deref(), Some("b"));
     }
+
+    #[test]
+    fn tab_glyph_is_drawn_but_not_copied_or_highlighted() {
+        #[derive(Default)]
+        struct RecordingHighlighter {
+            seen: std::sync::Mutex<Vec<String>>,
+        }
+
+        impl CodeHighlighter for RecordingHighlighter {
+            fn highlight_lines(
+                &self,
+                _language: Option<&str>,
+                lines: &[&str],
+            ) -> Vec<Vec<Span<'static>>> {
+                let mut seen = self.seen.lock().unwrap();
+                seen.extend(lines.iter().map(|l| (*l).to_string()));
+                lines
+                    .iter()
+                    .map(|l| vec![Span::raw((*l).to_string())])
+                    .collect()
+            }
+        }
+
+        let highlighter = Arc::new(RecordingHighlighter::default());
+        let mut view = DiffView::with_options(DiffViewOptions {
+            show_line_numbers: false,
+            show_scrollbar: false,
+            tabs: TabPolicy::default().with_glyph(Some(crate::tabs::TAB_GLYPH)),
+            ..Default::default()
+        });
+        view.set_highlighter(Some(highlighter.clone()));
+        view.set_diff("@@ -1 +1 @@\n+a\tb\n");
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 2));
+        view.render_ref(buf.area, &mut buf, &Theme::default());
+        assert_eq!(buf[(3, 1)].symbol(), "→");
+        assert_eq!(buf[(6, 1)].symbol(), "b");
+        assert_eq!(*highlighter.seen.lock().unwrap(), vec!["a   b".to_string()]);
+
+        view.selection = Some(((1, 0), (1, 4)));
+        assert_eq!(view.selected_text().as_deref(), Some("a   b"));
+    }
+
+    #[test]
+    fn focus_lost_drops_pending_chord_and_drag() {
+        let mut v = DiffView::new();
+        v.set_diff(&format!("@@ -0,0 +1,50 @@\n{}", "+x\n".repeat(50)));
+        v.set_viewport(Rect::new(0, 0, 20, 5));
+        v.state.y = 10;
+        let now = Instant::now();
+        let g = crate::keymap::key_char('g');
+        assert_eq!(
+            v.handle_key_event_at(g.clone(), now),
+            SelectionAction::Redraw
+        );
+        v.selection_anchor = Some((0, 0));
+
+        assert_eq!(
+            v.handle_event_action(crate::input::InputEvent::FocusLost),
+            SelectionAction::Redraw
+        );
+        assert_eq!(v.selection_anchor, None);
+        // The next `g` starts a new chord instead of completing `g g`.
+        assert_eq!(v.handle_key_event_at(g, now), SelectionAction::Redraw);
+        assert_eq!(v.state.y, 10);
+    }
 }
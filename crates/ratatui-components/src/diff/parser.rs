@@ -1,3 +1,4 @@
+use crate::tabs::TabPolicy;
 use unicode_width::UnicodeWidthStr;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -15,6 +16,8 @@ pub struct DiffLine {
     pub kind: DiffLineKind,
     pub raw: String,
     pub content: String,
+    /// Display columns in `content` where tabs started, for drawing [`TabPolicy::glyph`].
+    pub tab_cols: Vec<u32>,
     pub old_lineno: Option<u32>,
     pub new_lineno: Option<u32>,
     pub language_hint: Option<String>,
@@ -28,7 +31,9 @@ pub struct ParsedDiff {
     pub max_content_width: u16,
 }
 
-pub fn parse_unified_diff(input: &str) -> ParsedDiff {
+/// Parses `input`, expanding tabs with `tabs`. Tab stops in code lines count from the start of
+/// the code, after the `+`/`-`/` ` marker.
+pub fn parse_unified_diff(input: &str, tabs: TabPolicy) -> ParsedDiff {
     let mut out = ParsedDiff::default();
 
     let mut in_hunk = false;
@@ -37,14 +42,8 @@ pub fn parse_unified_diff(input: &str) -> ParsedDiff {
     let mut language_hint: Option<String> = None;
 
     for raw in input.lines() {
-        let raw = if raw.contains('\t') {
-            raw.replace('\t', "    ")
-        } else {
-            raw.to_string()
-        };
-
         let (kind, old_no, new_no, advance_old, advance_new) =
-            if let Some((o, n)) = parse_hunk_header(&raw) {
+            if let Some((o, n)) = parse_hunk_header(raw) {
                 in_hunk = true;
                 old_lineno = o;
                 new_lineno = n;
@@ -58,13 +57,13 @@ pub fn parse_unified_diff(input: &str) -> ParsedDiff {
                 || raw.starts_with("rename to ")
             {
                 in_hunk = false;
-                if let Some(ext) = parse_diff_git_extension(&raw) {
+                if let Some(ext) = parse_diff_git_extension(raw) {
                     language_hint = Some(ext);
                 }
                 (DiffLineKind::FileHeader, None, None, false, false)
             } else if raw.starts_with("--- ") || raw.starts_with("+++ ") {
                 in_hunk = false;
-                if let Some(ext) = parse_file_header_extension(&raw) {
+                if let Some(ext) = parse_file_header_extension(raw) {
                     language_hint = Some(ext);
                 }
                 (DiffLineKind::FileHeader, None, None, false, false)
@@ -86,7 +85,9 @@ pub fn parse_unified_diff(input: &str) -> ParsedDiff {
                 (DiffLineKind::Meta, None, None, false, false)
             };
 
-        let content = displayed_content(kind, &raw);
+        let tab_cols = tabs.tab_cols([displayed_content(kind, raw)]);
+        let content = tabs.expand(displayed_content(kind, raw)).into_owned();
+        let raw = tabs.expand(raw).into_owned();
 
         if let Some(v) = old_no {
             out.max_old_lineno = out.max_old_lineno.max(v);
@@ -102,6 +103,7 @@ pub fn parse_unified_diff(input: &str) -> ParsedDiff {
             kind,
             raw,
             content,
+            tab_cols,
             old_lineno: old_no,
             new_lineno: new_no,
             language_hint: language_hint.clone(),
@@ -118,12 +120,12 @@ pub fn parse_unified_diff(input: &str) -> ParsedDiff {
     out
 }
 
-fn displayed_content(kind: DiffLineKind, raw: &str) -> String {
+fn displayed_content(kind: DiffLineKind, raw: &str) -> &str {
     match kind {
         DiffLineKind::Add | DiffLineKind::Del | DiffLineKind::Context => {
-            raw.strip_prefix(['+', '-', ' ']).unwrap_or(raw).to_string()
+            raw.strip_prefix(['+', '-', ' ']).unwrap_or(raw)
         }
-        _ => raw.to_string(),
+        _ => raw,
     }
 }
 
@@ -186,7 +188,7 @@ diff --git a/a.txt b/a.txt
 -old
 +new
 ";
-        let parsed = parse_unified_diff(diff);
+        let parsed = parse_unified_diff(diff, TabPolicy::default());
         assert!(
             parsed
                 .lines
@@ -222,6 +224,15 @@ diff --git a/a.txt b/a.txt
         assert_eq!(add.content, "new");
     }
 
+    #[test]
+    fn tab_stops_count_from_the_code_column() {
+        let diff = "@@ -1 +1 @@\n+a\tb\n";
+        let parsed = parse_unified_diff(diff, TabPolicy::default());
+        assert_eq!(parsed.lines[1].content, "a   b");
+        assert_eq!(parsed.lines[1].raw, "+a  b");
+        assert_eq!(parsed.lines[1].tab_cols, vec![1]);
+    }
+
     #[test]
     fn parses_hunk_header_start_positions() {
         assert_eq!(parse_hunk_header("@@ -1 +2 @@"), Some((1, 2)));
@@ -238,7 +249,7 @@ diff --git a/main.rs b/main.rs
 @@ -1 +1 @@
  a
 ";
-        let parsed = parse_unified_diff(diff);
+        let parsed = parse_unified_diff(diff, TabPolicy::default());
         let ctx = parsed
             .lines
             .iter()
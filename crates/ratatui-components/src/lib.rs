@@ -37,6 +37,8 @@ pub use ansi::AnsiTextView;
 pub use ansi::AnsiTextViewOptions;
 #[cfg(feature = "ansi")]
 pub use ansi::ansi_text;
+#[cfg(feature = "ansi")]
+pub use ansi::ansi_text_with_tabs;
 
 #[cfg(feature = "diff")]
 pub use diff::DiffView;
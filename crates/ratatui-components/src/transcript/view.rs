@@ -1,31 +1,50 @@
+use crate::component::Component;
+use crate::component::ComponentAction;
+use crate::help;
+use crate::help::BindingGroup;
+use crate::input::ClickTracker;
 use crate::input::InputEvent;
 use crate::input::KeyCode;
+use crate::input::KeyEvent;
 use crate::input::MouseButton;
 use crate::input::MouseEvent;
 use crate::input::MouseEventKind;
+use crate::keymap;
+use crate::keymap::Binding;
+use crate::keymap::ChordMatch;
+use crate::keymap::ChordState;
+use crate::keymap::Keymap;
 use crate::render;
 use crate::scroll::ScrollAction;
 use crate::scroll::ScrollBindings;
+use crate::selection;
 use crate::selection::SelectionAction;
 use crate::selection::SelectionBindings;
+use crate::selection::VisualSelection;
+use crate::selection::VisualStep;
+use crate::tabs::TabPolicy;
 use crate::text::CodeHighlighter;
 use crate::theme::Theme;
+use crate::viewport::ScrollAnchor;
+use crate::viewport::ScrollAnimator;
 use crate::viewport::ViewportState;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::text::Line;
 use ratatui::text::Span;
 
-use crate::ansi::ansi_text;
+use crate::ansi::ansi_text_with_tabs;
 use crate::diff::DiffView;
+use crate::diff::DiffViewOptions;
 #[cfg(feature = "mdstream")]
 use crate::markdown::streaming::MarkdownStreamView;
 use crate::markdown::view::MarkdownView;
+use crate::markdown::view::MarkdownViewOptions;
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::sync::Arc;
+use std::time::Instant;
 use unicode_width::UnicodeWidthStr;
 
 /// A logical role for transcript entries.
@@ -81,6 +100,9 @@ pub struct TranscriptViewOptions {
     pub scroll: ScrollBindings,
     pub enable_selection: bool,
     pub selection: SelectionBindings,
+    /// How tabs are expanded to spaces in every entry kind (the glyph is not drawn); applies to
+    /// entries laid out after the change.
+    pub tabs: TabPolicy,
 }
 
 impl Default for TranscriptViewOptions {
@@ -95,10 +117,35 @@ impl Default for TranscriptViewOptions {
             scroll: ScrollBindings::default(),
             enable_selection: true,
             selection: SelectionBindings::default(),
+            tabs: TabPolicy::default(),
         }
     }
 }
 
+impl TranscriptViewOptions {
+    /// Describes the configured bindings for help screens (see [`help::HelpPanel`]).
+    ///
+    /// Includes the built-in `f` (toggle follow-tail) binding.
+    pub fn help_groups(&self) -> Vec<BindingGroup> {
+        let mut groups = help::view_help_groups(
+            &self.scroll,
+            self.enable_selection.then_some(&self.selection),
+        );
+        groups.insert(
+            0,
+            BindingGroup::new(
+                "Transcript",
+                vec![Binding::new(
+                    "f",
+                    "toggle follow tail",
+                    vec![keymap::key_char('f')],
+                )],
+            ),
+        );
+        groups
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 struct EntryMetrics {
     height: u16,
@@ -196,21 +243,40 @@ pub struct TranscriptView {
     layout_dirty_from: Option<usize>,
     follow_tail_pinned: bool,
     force_to_bottom: bool,
+    /// Top row as `(entry, visible characters into it)`, restored after the next re-layout.
+    scroll_anchor: Option<ScrollAnchor>,
     cache: LinesLru,
     pub state: ViewportState,
+    scroll_chord: ChordState,
+    /// Built from `options.scroll` on first use and dropped when the options change.
+    scroll_keymap: Option<Keymap<ScrollAction>>,
+    scroll_animator: ScrollAnimator,
+    clicks: ClickTracker,
     options: TranscriptViewOptions,
     highlighter: Option<Arc<dyn CodeHighlighter + Send + Sync>>,
     selection_anchor: Option<(u32, u32)>,
     selection: Option<((u32, u32), (u32, u32))>,
+    hover_row: Option<u16>,
+    visual: VisualSelection,
+    /// Inverted so the derived `Default` starts focused.
+    unfocused: bool,
     #[cfg(feature = "mdstream")]
     streaming_markdown: Option<(usize, MarkdownStreamView)>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TranscriptAction {
     None,
     Redraw,
     FollowTailToggled(bool),
+    /// The copy binding was pressed with a selection (see [`SelectionAction::CopyRequested`]).
+    CopyRequested(String),
+}
+
+impl ComponentAction for TranscriptAction {
+    fn is_consumed(&self) -> bool {
+        !matches!(self, Self::None)
+    }
 }
 
 impl TranscriptView {
@@ -261,12 +327,33 @@ impl TranscriptView {
         self.invalidate_layout();
     }
 
+    /// Handles an event.
+    ///
+    /// On [`InputEvent::Resize`], a transcript that follows the tail is kept pinned to the
+    /// bottom after the re-layout; otherwise the text in the top row stays on top.
     pub fn handle_event(&mut self, event: InputEvent) -> TranscriptAction {
         match event {
             InputEvent::Paste(_) => TranscriptAction::None,
             InputEvent::Mouse(_) => TranscriptAction::None,
+            InputEvent::Resize { .. } => {
+                self.on_resize();
+                TranscriptAction::Redraw
+            }
+            InputEvent::FocusGained => TranscriptAction::None,
+            InputEvent::FocusLost => {
+                self.on_focus_lost();
+                TranscriptAction::Redraw
+            }
+            InputEvent::Tick(now) => {
+                if self.tick(now) {
+                    TranscriptAction::Redraw
+                } else {
+                    TranscriptAction::None
+                }
+            }
             InputEvent::Key(key) => {
                 if matches!(key.code, KeyCode::Char('f'))
+                    && !key.is_release()
                     && !key.modifiers.ctrl
                     && !key.modifiers.alt
                 {
@@ -275,26 +362,22 @@ impl TranscriptView {
                     return TranscriptAction::FollowTailToggled(enabled);
                 }
 
-                if let Some(action) = self.options.scroll.action_for(&key) {
-                    match action {
-                        ScrollAction::Up => self.scroll_y_by(-self.options.scroll.line_step),
-                        ScrollAction::Down => self.scroll_y_by(self.options.scroll.line_step),
-                        ScrollAction::Left => self.scroll_x_by(-self.options.scroll.horiz_step),
-                        ScrollAction::Right => self.scroll_x_by(self.options.scroll.horiz_step),
-                        ScrollAction::PageUp => {
-                            self.scroll_y_by(-(self.state.viewport_h.saturating_sub(1) as i32))
-                        }
-                        ScrollAction::PageDown => {
-                            self.scroll_y_by(self.state.viewport_h.saturating_sub(1) as i32)
-                        }
-                        ScrollAction::Top => {
-                            self.state.to_top();
-                            self.follow_tail_pinned = false;
-                        }
-                        ScrollAction::Bottom => {
-                            self.state.to_bottom();
-                            self.follow_tail_pinned = true;
-                        }
+                let action = match self
+                    .scroll_keymap
+                    .get_or_insert_with(|| self.options.scroll.keymap())
+                    .feed(&mut self.scroll_chord, &key, Some(Instant::now()))
+                {
+                    ChordMatch::Matched(action) => Some(action),
+                    ChordMatch::Pending => return TranscriptAction::Redraw,
+                    ChordMatch::NoMatch => None,
+                };
+                if let Some(action) = action {
+                    self.apply_scroll_action(action);
+                    if let Some(key) = self.scroll_chord.take_replay() {
+                        return match self.handle_event(InputEvent::Key(key)) {
+                            TranscriptAction::None => TranscriptAction::Redraw,
+                            replayed => replayed,
+                        };
                     }
                     return TranscriptAction::Redraw;
                 }
@@ -307,54 +390,90 @@ impl TranscriptView {
         match event {
             InputEvent::Paste(_) => SelectionAction::None,
             InputEvent::Mouse(_) => SelectionAction::None,
-            InputEvent::Key(key) => {
-                if self.options.enable_selection && self.options.selection.is_clear(&key) {
-                    self.clear_selection();
-                    return SelectionAction::Redraw;
-                }
-                if self.options.enable_selection && self.options.selection.is_copy(&key) {
-                    return self
-                        .selected_text()
-                        .map(SelectionAction::CopyRequested)
-                        .unwrap_or(SelectionAction::None);
-                }
-
-                if matches!(key.code, KeyCode::Char('f'))
-                    && !key.modifiers.ctrl
-                    && !key.modifiers.alt
-                {
-                    self.set_follow_tail(!self.options.follow_tail);
-                    return SelectionAction::Redraw;
+            InputEvent::Resize { .. } => {
+                self.on_resize();
+                SelectionAction::Redraw
+            }
+            InputEvent::FocusGained => SelectionAction::None,
+            InputEvent::FocusLost => {
+                self.on_focus_lost();
+                SelectionAction::Redraw
+            }
+            InputEvent::Tick(now) => {
+                if self.tick(now) {
+                    SelectionAction::Redraw
+                } else {
+                    SelectionAction::None
                 }
+            }
+            InputEvent::Key(key) => self.handle_key_event_at(key, Instant::now()),
+        }
+    }
 
-                let Some(action) = self.options.scroll.action_for(&key) else {
-                    return SelectionAction::None;
-                };
-
-                match action {
-                    ScrollAction::Up => self.scroll_y_by(-self.options.scroll.line_step),
-                    ScrollAction::Down => self.scroll_y_by(self.options.scroll.line_step),
-                    ScrollAction::Left => self.scroll_x_by(-self.options.scroll.horiz_step),
-                    ScrollAction::Right => self.scroll_x_by(self.options.scroll.horiz_step),
-                    ScrollAction::PageUp => {
-                        self.scroll_y_by(-(self.state.viewport_h.saturating_sub(1) as i32))
-                    }
-                    ScrollAction::PageDown => {
-                        self.scroll_y_by(self.state.viewport_h.saturating_sub(1) as i32)
-                    }
-                    ScrollAction::Top => {
-                        self.state.to_top();
-                        self.follow_tail_pinned = false;
-                    }
-                    ScrollAction::Bottom => {
-                        self.state.to_bottom();
-                        self.follow_tail_pinned = true;
-                    }
-                }
+    /// Handles a key received at `now`.
+    ///
+    /// The timestamp drives [`ScrollBindings::chord_timeout`]; a prefix left pending is
+    /// resolved by a later [`InputEvent::Tick`].
+    pub fn handle_key_event_at(&mut self, key: KeyEvent, now: Instant) -> SelectionAction {
+        let action = self.handle_key(key, now);
+        // A bound chord prefix fired because the key could not continue it; handle the key too.
+        match self.scroll_chord.take_replay() {
+            Some(key) => match self.handle_key_event_at(key, now) {
+                SelectionAction::None => action,
+                replayed => replayed,
+            },
+            None => action,
+        }
+    }
 
-                SelectionAction::Redraw
+    fn handle_key(&mut self, key: KeyEvent, now: Instant) -> SelectionAction {
+        if self.options.enable_selection && self.options.selection.is_clear(&key) {
+            self.clear_selection();
+            return SelectionAction::Redraw;
+        }
+        if self.options.enable_selection && self.options.selection.is_copy(&key) {
+            let text = self.selected_text();
+            if self.visual.is_active() {
+                self.clear_selection();
             }
+            return text
+                .map(SelectionAction::CopyRequested)
+                .unwrap_or(SelectionAction::None);
+        }
+        if self.options.enable_selection {
+            let step = self.visual.step(
+                &key,
+                &self.options.selection,
+                self.scroll_keymap
+                    .get_or_insert_with(|| self.options.scroll.keymap()),
+                &mut self.scroll_chord,
+                Some(now),
+            );
+            if let Some(action) = self.apply_visual_step(step) {
+                return action;
+            }
+        }
+
+        if matches!(key.code, KeyCode::Char('f'))
+            && !key.is_release()
+            && !key.modifiers.ctrl
+            && !key.modifiers.alt
+        {
+            self.set_follow_tail(!self.options.follow_tail);
+            return SelectionAction::Redraw;
         }
+
+        let action = match self
+            .scroll_keymap
+            .get_or_insert_with(|| self.options.scroll.keymap())
+            .feed(&mut self.scroll_chord, &key, Some(now))
+        {
+            ChordMatch::Matched(action) => action,
+            ChordMatch::Pending => return SelectionAction::Redraw,
+            ChordMatch::NoMatch => return SelectionAction::None,
+        };
+        self.apply_scroll_action(action);
+        SelectionAction::Redraw
     }
 
     pub fn handle_event_action_in_area(
@@ -364,7 +483,6 @@ impl TranscriptView {
     ) -> SelectionAction {
         match event {
             InputEvent::Paste(_) => SelectionAction::None,
-            InputEvent::Key(_) => self.handle_event_action(event),
             InputEvent::Mouse(m) => {
                 if self.handle_mouse_event(area, m) {
                     SelectionAction::Redraw
@@ -372,9 +490,105 @@ impl TranscriptView {
                     SelectionAction::None
                 }
             }
+            InputEvent::Key(_)
+            | InputEvent::Resize { .. }
+            | InputEvent::FocusGained
+            | InputEvent::FocusLost
+            | InputEvent::Tick(_) => self.handle_event_action(event),
         }
     }
 
+    fn on_resize(&mut self) {
+        if self.options.follow_tail && self.follow_tail_pinned {
+            self.force_to_bottom = true;
+        } else {
+            self.capture_scroll_anchor();
+        }
+    }
+
+    /// Advances smooth scrolling and resolves a chord prefix whose timeout has elapsed.
+    fn tick(&mut self, now: Instant) -> bool {
+        let mut redraw = self
+            .options
+            .scroll
+            .tick(&mut self.scroll_animator, &mut self.state, now);
+        if let ChordMatch::Matched(action) = self
+            .scroll_keymap
+            .get_or_insert_with(|| self.options.scroll.keymap())
+            .expire(&mut self.scroll_chord, now)
+        {
+            if self.visual.is_active() {
+                self.apply_visual_step(VisualStep::Move(action.into()));
+            } else {
+                self.apply_scroll_action(action);
+            }
+            redraw = true;
+        }
+        redraw
+    }
+
+    fn apply_scroll_action(&mut self, action: ScrollAction) {
+        match action {
+            ScrollAction::Up => self.scroll_y_by(-self.options.scroll.line_step),
+            ScrollAction::Down => self.scroll_y_by(self.options.scroll.line_step),
+            ScrollAction::Left => self.scroll_x_by(-self.options.scroll.horiz_step),
+            ScrollAction::Right => self.scroll_x_by(self.options.scroll.horiz_step),
+            ScrollAction::PageUp => {
+                self.scroll_y_by(-(self.state.viewport_h.saturating_sub(1) as i32))
+            }
+            ScrollAction::PageDown => {
+                self.scroll_y_by(self.state.viewport_h.saturating_sub(1) as i32)
+            }
+            ScrollAction::Top => {
+                self.state.to_top();
+                self.follow_tail_pinned = false;
+            }
+            ScrollAction::Bottom => {
+                self.state.to_bottom();
+                self.follow_tail_pinned = true;
+            }
+        }
+    }
+
+    /// Wheel scrolling through [`ScrollBindings::wheel`]; follow-tail pins on the target.
+    fn wheel(&mut self, lines: i32, now: Instant) {
+        self.options
+            .scroll
+            .wheel(&mut self.scroll_animator, &mut self.state, lines, now);
+        if lines < 0 {
+            self.follow_tail_pinned = false;
+        } else if self.scroll_animator.target(&self.state) >= self.max_y() {
+            self.follow_tail_pinned = true;
+        }
+    }
+
+    /// Applies a visual-mode key; the viewport follows the caret and follow-tail pins like
+    /// [`Self::scroll_y_by`].
+    fn apply_visual_step(&mut self, step: VisualStep) -> Option<SelectionAction> {
+        let (mut visual, mut state) = (self.visual, self.state);
+        let lines = self.total_lines() as usize;
+        let action = visual.apply(step, &mut state, lines, |l| self.line_text(l as u32))?;
+        let scrolled_up = state.y < self.state.y;
+        (self.visual, self.state) = (visual, state);
+        if scrolled_up {
+            self.follow_tail_pinned = false;
+        } else if self.is_at_bottom() {
+            self.follow_tail_pinned = true;
+        }
+        self.selection_anchor = None;
+        self.selection = self
+            .visual
+            .selection()
+            .map(|((l0, c0), (l1, c1))| ((l0 as u32, c0), (l1 as u32, c1)));
+        Some(action)
+    }
+
+    fn on_focus_lost(&mut self) {
+        // Drop half-typed chords and any in-progress drag; the release may never arrive.
+        self.scroll_chord.clear();
+        self.selection_anchor = None;
+    }
+
     pub fn push_markdown(&mut self, role: Role, markdown: &str) {
         self.push_entry(TranscriptEntry {
             role,
@@ -452,6 +666,7 @@ impl TranscriptView {
 
     pub fn clear(&mut self) {
         self.entries.clear();
+        self.scroll_anchor = None;
         self.invalidate_layout();
         self.state.to_top();
         self.state.clamp();
@@ -527,7 +742,7 @@ impl TranscriptView {
                         prefix_w,
                         buf,
                         &prefix,
-                        theme.text_muted,
+                        theme.transcript.separator,
                     );
                 }
                 Located::Entry { idx, line } => {
@@ -540,7 +755,7 @@ impl TranscriptView {
                         prefix_w,
                         buf,
                         &prefix,
-                        theme.text_muted,
+                        theme.transcript.separator,
                     );
 
                     if visible_content_w == 0 {
@@ -553,35 +768,18 @@ impl TranscriptView {
                     };
                     let owned_spans: Vec<Span<'static>>;
                     let spans: &[Span<'static>] = if self.options.enable_selection
-                        && let Some(((l0, c0), (l1, c1))) = self.selection
+                        && let Some(sel) = self.selection
+                        && let Some((from, to)) =
+                            selection::line_cols(sel, self.visual.is_block(), global)
+                        && let Some((bs, be)) =
+                            render::byte_range_for_cols_in_spans(&content_line.spans, from, to)
                     {
-                        let ((start_line, start_col), (end_line, end_col)) =
-                            normalize_sel_inclusive((l0, c0), (l1, c1));
-                        if global >= start_line && global <= end_line {
-                            let (from, to) = if start_line == end_line {
-                                (start_col, end_col)
-                            } else if global == start_line {
-                                (start_col, u32::MAX)
-                            } else if global == end_line {
-                                (0, end_col)
-                            } else {
-                                (0, u32::MAX)
-                            };
-                            if let Some((bs, be)) =
-                                render::byte_range_for_cols_in_spans(&content_line.spans, from, to)
-                            {
-                                owned_spans = render::apply_modifier_to_byte_ranges(
-                                    content_line.spans.clone(),
-                                    &[(bs, be)],
-                                    Modifier::REVERSED,
-                                );
-                                &owned_spans
-                            } else {
-                                &content_line.spans
-                            }
-                        } else {
-                            &content_line.spans
-                        }
+                        owned_spans = render::apply_style_to_byte_ranges(
+                            content_line.spans.clone(),
+                            &[(bs, be)],
+                            theme.selection_style(),
+                        );
+                        &owned_spans
                     } else {
                         &content_line.spans
                     };
@@ -598,17 +796,42 @@ impl TranscriptView {
             }
         }
 
+        let text_area = Rect {
+            x: content_area.x + prefix_w,
+            width: visible_content_w,
+            ..content_area
+        };
+        if let Some(line) = self.hovered_line() {
+            render::render_line_highlight(
+                text_area,
+                buf,
+                &self.state,
+                line as usize,
+                theme.chrome.hover,
+            );
+        }
+        if let Some(caret) = self.visual.caret() {
+            render::render_caret(
+                text_area,
+                buf,
+                &self.state,
+                caret,
+                theme.chrome.cursor_style(self.is_focused()),
+            );
+        }
         if let Some(sb_x) = scrollbar_x {
             render::render_scrollbar(
                 Rect::new(sb_x, area.y, 1, area.height),
                 buf,
                 &self.state,
-                theme.text_muted,
+                theme.chrome.scrollbar,
             );
         }
+        theme.color_profile.apply_to_buffer(buf, area);
     }
 
     fn invalidate_layout(&mut self) {
+        self.capture_scroll_anchor();
         self.cached_width = None;
         self.layout_dirty = true;
         self.layout_dirty_from = None;
@@ -629,6 +852,10 @@ impl TranscriptView {
     fn ensure_layout(&mut self, width: u16, theme: &Theme) {
         let new_gutter = self.compute_gutter_width();
         let gutter_changed = new_gutter != self.gutter_width;
+        if gutter_changed || self.cached_width.is_some_and(|w| w != width) {
+            // Record the anchor against the old layout before the gutter or width changes.
+            self.capture_scroll_anchor();
+        }
         self.gutter_width = new_gutter;
 
         let width_changed = self.cached_width != Some(width);
@@ -648,6 +875,7 @@ impl TranscriptView {
         self.cache.set_capacity(self.options.cache_entries);
 
         if !self.layout_dirty {
+            self.apply_scroll_anchor(width, theme);
             return;
         }
 
@@ -670,27 +898,12 @@ impl TranscriptView {
 
             let mut max_content_width: u16 = 0;
             for entry in &self.entries {
-                let m = compute_entry_metrics(entry, content_w);
+                let m = compute_entry_metrics(entry, content_w, self.options.tabs);
                 max_content_width = max_content_width.max(m.max_width);
                 self.metrics.push(m);
             }
             self.rebuild_offsets();
-
-            if let Some(max_total) = self.options.max_total_lines {
-                let mut removed_any = false;
-                while self.total_lines() > max_total && !self.entries.is_empty() {
-                    self.entries.remove(0);
-                    if !self.metrics.is_empty() {
-                        self.metrics.remove(0);
-                    }
-                    self.cache.clear();
-                    self.rebuild_offsets();
-                    removed_any = true;
-                }
-                if removed_any {
-                    self.clear_selection();
-                }
-            }
+            self.trim_to_max_total_lines();
 
             self.state
                 .set_content(max_content_width as u32, self.total_lines());
@@ -703,27 +916,11 @@ impl TranscriptView {
             }
             for idx in start_idx..self.entries.len() {
                 let entry = &self.entries[idx];
-                self.metrics[idx] = compute_entry_metrics(entry, content_w);
+                self.metrics[idx] = compute_entry_metrics(entry, content_w, self.options.tabs);
             }
             self.rebuild_offsets();
             let max_content_width = self.metrics.iter().map(|m| m.max_width).max().unwrap_or(0);
-
-            if let Some(max_total) = self.options.max_total_lines {
-                let mut removed_any = false;
-                while self.total_lines() > max_total && !self.entries.is_empty() {
-                    self.entries.remove(0);
-                    if !self.metrics.is_empty() {
-                        self.metrics.remove(0);
-                    }
-                    self.cache.clear();
-                    self.layout_dirty_from = None;
-                    self.rebuild_offsets();
-                    removed_any = true;
-                }
-                if removed_any {
-                    self.clear_selection();
-                }
-            }
+            self.trim_to_max_total_lines();
 
             self.state
                 .set_content(max_content_width as u32, self.total_lines());
@@ -732,11 +929,83 @@ impl TranscriptView {
         if self.force_to_bottom {
             self.state.to_bottom();
             self.force_to_bottom = false;
+            self.scroll_anchor = None;
         }
+        self.apply_scroll_anchor(width, theme);
         if self.is_at_bottom() {
             self.follow_tail_pinned = true;
         }
-        let _ = theme;
+    }
+
+    /// Drops the oldest entries until the transcript fits `max_total_lines`, moving the scroll
+    /// position up with them so the visible rows stay in place.
+    fn trim_to_max_total_lines(&mut self) {
+        let Some(max_total) = self.options.max_total_lines else {
+            return;
+        };
+        let mut removed_any = false;
+        while self.total_lines() > max_total && !self.entries.is_empty() {
+            let removed_rows = self.offsets.get(1).copied().unwrap_or(0);
+            self.entries.remove(0);
+            if !self.metrics.is_empty() {
+                self.metrics.remove(0);
+            }
+            self.cache.clear();
+            self.rebuild_offsets();
+            self.state.y = self.state.y.saturating_sub(removed_rows);
+            if let Some(anchor) = &mut self.scroll_anchor {
+                if anchor.src == 0 {
+                    anchor.offset = 0;
+                }
+                anchor.src = anchor.src.saturating_sub(1);
+            }
+            removed_any = true;
+        }
+        if removed_any {
+            self.clear_selection();
+        }
+    }
+
+    /// Records the entry shown in the top row and how far into it that row starts, using the
+    /// current layout. Keeps an anchor that is already pending.
+    fn capture_scroll_anchor(&mut self) {
+        if self.scroll_anchor.is_some()
+            || self.entries.is_empty()
+            || self.offsets.len() != self.entries.len() + 1
+        {
+            return;
+        }
+        let Some(width) = self.cached_width else {
+            return;
+        };
+        let anchor = match self.locate(self.state.y) {
+            Located::Entry { idx, line } => {
+                let lines = self.entry_lines(idx, width, &Theme::default());
+                let rows = lines.iter().take(line as usize).map(line_plain);
+                ScrollAnchor::new(idx, ScrollAnchor::text_offset(rows), &self.state)
+            }
+            // The spacer below an entry: anchor to the start of the next one.
+            Located::Spacer => {
+                ScrollAnchor::new(upper_bound(&self.offsets, self.state.y), 0, &self.state)
+            }
+        };
+        self.scroll_anchor = Some(anchor);
+    }
+
+    /// Restores the scroll position captured by [`Self::capture_scroll_anchor`] once the layout
+    /// for `width` is known.
+    fn apply_scroll_anchor(&mut self, width: u16, theme: &Theme) {
+        let Some(anchor) = self.scroll_anchor.take() else {
+            return;
+        };
+        let row = if anchor.src < self.entries.len() {
+            let lines = self.entry_lines(anchor.src, width, theme);
+            let line = ScrollAnchor::row_for_offset(lines.iter().map(line_plain), anchor.offset);
+            Some(self.offsets[anchor.src] + line as u32)
+        } else {
+            None
+        };
+        anchor.restore(&mut self.state, row);
     }
 
     fn compute_gutter_width(&self) -> u16 {
@@ -791,6 +1060,17 @@ impl TranscriptView {
         }
     }
 
+    fn line_text(&mut self, global: u32) -> String {
+        let (Some(width), Located::Entry { idx, line }) = (self.cached_width, self.locate(global))
+        else {
+            return String::new();
+        };
+        self.entry_lines(idx, width, &Theme::default())
+            .get(line as usize)
+            .map(|l| render::slice_spans_by_bytes(&l.spans, 0, usize::MAX))
+            .unwrap_or_default()
+    }
+
     fn entry_lines(&mut self, idx: usize, width: u16, theme: &Theme) -> Vec<Line<'static>> {
         if let Some(lines) = self.cache.get(idx) {
             return lines.clone();
@@ -808,12 +1088,25 @@ impl TranscriptView {
             return vec![Line::from("")];
         };
 
-        let lines = render_entry_lines(entry, content_w, theme, self.highlighter.clone());
+        let lines = render_entry_lines(
+            entry,
+            content_w,
+            theme,
+            self.highlighter.clone(),
+            self.options.tabs,
+        );
         self.cache.insert(idx, lines.clone());
         lines
     }
 
     pub fn handle_mouse_event(&mut self, area: Rect, event: MouseEvent) -> bool {
+        self.handle_mouse_event_at(area, event, Instant::now())
+    }
+
+    /// Like [`Self::handle_mouse_event`], but with the time the event was received.
+    ///
+    /// The timestamp drives double-click (select word) and triple-click (select line) detection.
+    pub fn handle_mouse_event_at(&mut self, area: Rect, event: MouseEvent, now: Instant) -> bool {
         if area.width == 0 || area.height == 0 {
             return false;
         }
@@ -840,7 +1133,7 @@ impl TranscriptView {
                 {
                     return false;
                 }
-                self.scroll_y_by(-3);
+                self.wheel(-3, now);
                 return true;
             }
             MouseEventKind::ScrollDown => {
@@ -851,9 +1144,29 @@ impl TranscriptView {
                 {
                     return false;
                 }
-                self.scroll_y_by(3);
+                self.wheel(3, now);
                 return true;
             }
+            MouseEventKind::ScrollLeft => {
+                if event.position_in(content_area).is_none() {
+                    return false;
+                }
+                self.scroll_x_by(-self.options.scroll.horiz_step);
+                return true;
+            }
+            MouseEventKind::ScrollRight => {
+                if event.position_in(content_area).is_none() {
+                    return false;
+                }
+                self.scroll_x_by(self.options.scroll.horiz_step);
+                return true;
+            }
+            MouseEventKind::Moved => {
+                let row = event.position_in(content_area).map(|(_, row)| row);
+                let changed = row != self.hover_row;
+                self.hover_row = row;
+                return changed;
+            }
             _ => {}
         }
 
@@ -920,8 +1233,18 @@ impl TranscriptView {
             .min(self.total_lines().saturating_sub(1));
         let col = self.state.x.saturating_add(rel_x);
 
+        let click = self.clicks.on_mouse(&event, now);
         match event.kind {
             MouseEventKind::Down(MouseButton::Left) => {
+                self.visual.stop();
+                let count = click.map_or(1, |c| c.count);
+                if let Some(sel) = selection::click_selection(count, global_line, col, || {
+                    self.line_text(global_line)
+                }) {
+                    self.selection_anchor = None;
+                    self.selection = Some(sel);
+                    return true;
+                }
                 self.selection_anchor = Some((global_line, col));
                 self.selection = Some(((global_line, col), (global_line, col)));
                 true
@@ -945,15 +1268,41 @@ impl TranscriptView {
         }
     }
 
+    /// The rendered line under the mouse pointer, tracked from [`MouseEventKind::Moved`] events.
+    pub fn hovered_line(&self) -> Option<u32> {
+        let global = self.state.y.saturating_add(self.hover_row? as u32);
+        (global < self.total_lines()).then_some(global)
+    }
+
+    /// The entry under the mouse pointer, tracked from [`MouseEventKind::Moved`] events.
+    ///
+    /// Returns `None` when the pointer is outside the view or over the spacer between entries.
+    pub fn hovered_entry(&self) -> Option<usize> {
+        match self.locate(self.hovered_line()?) {
+            Located::Entry { idx, .. } => Some(idx),
+            Located::Spacer => None,
+        }
+    }
+
+    pub fn is_focused(&self) -> bool {
+        !self.unfocused
+    }
+
+    /// Sets focus. While unfocused the visual-mode caret is drawn with
+    /// [`crate::theme::ChromeTheme::cursor_unfocused`].
+    pub fn set_focused(&mut self, focused: bool) {
+        self.unfocused = !focused;
+    }
+
     pub fn clear_selection(&mut self) {
         self.selection_anchor = None;
         self.selection = None;
+        self.visual.stop();
     }
 
     pub fn selected_text(&mut self) -> Option<String> {
-        let ((l0, c0), (l1, c1)) = self.selection?;
-        let ((start_line, start_col), (end_line, end_col)) =
-            normalize_sel_inclusive((l0, c0), (l1, c1));
+        let sel = self.selection?;
+        let (start_line, end_line) = (sel.0.0.min(sel.1.0), sel.0.0.max(sel.1.0));
         let width = self.cached_width?;
 
         let mut out = String::new();
@@ -965,15 +1314,7 @@ impl TranscriptView {
                 out.push('\n');
             }
 
-            let (from, to) = if start_line == end_line {
-                (start_col, end_col)
-            } else if global == start_line {
-                (start_col, u32::MAX)
-            } else if global == end_line {
-                (0, end_col)
-            } else {
-                (0, u32::MAX)
-            };
+            let (from, to) = selection::line_cols(sel, self.visual.is_block(), global)?;
 
             match self.locate(global) {
                 Located::Spacer => {}
@@ -1038,6 +1379,10 @@ impl TranscriptView {
             }
             _ => {
                 let mut v = MarkdownStreamView::default();
+                v.set_render_options(MarkdownViewOptions {
+                    tabs: self.options.tabs,
+                    ..Default::default()
+                });
                 v.set_highlighter(self.highlighter.clone());
                 v.set_pending_code_fence_max_lines(
                     self.options.streaming_markdown_pending_code_fence_max_lines,
@@ -1080,6 +1425,36 @@ impl TranscriptView {
     }
 }
 
+impl Component for TranscriptView {
+    type Action = TranscriptAction;
+
+    /// Like [`TranscriptView::handle_event_action_in_area`], but reports follow-tail toggles as
+    /// [`TranscriptAction::FollowTailToggled`].
+    fn handle_event(&mut self, area: Rect, event: InputEvent) -> TranscriptAction {
+        let follow_tail = self.options.follow_tail;
+        match self.handle_event_action_in_area(area, event) {
+            SelectionAction::None => TranscriptAction::None,
+            SelectionAction::CopyRequested(text) => TranscriptAction::CopyRequested(text),
+            SelectionAction::Redraw if self.options.follow_tail != follow_tail => {
+                TranscriptAction::FollowTailToggled(self.options.follow_tail)
+            }
+            SelectionAction::Redraw => TranscriptAction::Redraw,
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        self.render_ref(area, buf, theme);
+    }
+
+    fn bindings(&self) -> Vec<BindingGroup> {
+        self.options.help_groups()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        TranscriptView::set_focused(self, focused);
+    }
+}
+
 fn prefix_width(gutter_width: u16) -> u16 {
     // "<LABEL padded> │ " = gutter_width + 3
     gutter_width.saturating_add(3)
@@ -1087,7 +1462,7 @@ fn prefix_width(gutter_width: u16) -> u16 {
 
 fn make_prefix(role: Role, gutter_width: u16, first: bool, theme: &Theme) -> Vec<Span<'static>> {
     let role_style = role_style(theme, role);
-    let sep_style = theme.text_muted;
+    let sep_style = theme.transcript.separator;
     if first {
         vec![
             Span::styled(
@@ -1105,7 +1480,7 @@ fn make_prefix(role: Role, gutter_width: u16, first: bool, theme: &Theme) -> Vec
 }
 
 fn blank_prefix(gutter_width: u16, theme: &Theme) -> Vec<Span<'static>> {
-    let sep_style = theme.text_muted;
+    let sep_style = theme.transcript.separator;
     vec![
         Span::styled(" ".repeat(gutter_width as usize), sep_style),
         Span::styled(" │ ".to_string(), sep_style),
@@ -1114,10 +1489,10 @@ fn blank_prefix(gutter_width: u16, theme: &Theme) -> Vec<Span<'static>> {
 
 fn role_style(theme: &Theme, role: Role) -> Style {
     match role {
-        Role::User => theme.accent.add_modifier(Modifier::BOLD),
-        Role::Assistant => theme.text_primary.add_modifier(Modifier::BOLD),
-        Role::Tool => theme.text_muted.add_modifier(Modifier::BOLD),
-        Role::System => theme.text_muted,
+        Role::User => theme.transcript.user,
+        Role::Assistant => theme.transcript.assistant,
+        Role::Tool => theme.transcript.tool,
+        Role::System => theme.transcript.system,
     }
 }
 
@@ -1149,8 +1524,12 @@ impl TextWrap {
     }
 }
 
-fn compute_entry_metrics(entry: &TranscriptEntry, content_width: u16) -> EntryMetrics {
-    let lines = render_entry_lines(entry, content_width, &Theme::default(), None);
+fn compute_entry_metrics(
+    entry: &TranscriptEntry,
+    content_width: u16,
+    tabs: TabPolicy,
+) -> EntryMetrics {
+    let lines = render_entry_lines(entry, content_width, &Theme::default(), None, tabs);
     let height = lines.len().min(u16::MAX as usize) as u16;
     let max_width = lines
         .iter()
@@ -1165,23 +1544,34 @@ fn render_entry_lines(
     content_width: u16,
     theme: &Theme,
     highlighter: Option<Arc<dyn CodeHighlighter + Send + Sync>>,
+    tabs: TabPolicy,
 ) -> Vec<Line<'static>> {
     match &entry.content {
         EntryContent::Markdown(md) => {
-            let mut view = MarkdownView::new();
+            let mut view = MarkdownView::with_options(MarkdownViewOptions {
+                tabs,
+                ..Default::default()
+            });
             view.set_markdown(md);
             view.set_highlighter(highlighter);
             view.lines_for_width(content_width, theme)
         }
         EntryContent::Diff(diff) => {
-            let mut view = DiffView::new();
+            let mut view = DiffView::with_options(DiffViewOptions {
+                tabs,
+                ..Default::default()
+            });
             view.set_diff(diff);
             view.set_highlighter(highlighter);
             view.lines_for_transcript(theme)
         }
-        EntryContent::Ansi(s) => ansi_text(s).lines,
+        EntryContent::Ansi(s) => ansi_text_with_tabs(s, tabs).lines,
         EntryContent::Plain(s) => {
-            let text = s.replace('\t', "    ");
+            let text = s
+                .split('\n')
+                .map(|l| tabs.expand(l))
+                .collect::<Vec<_>>()
+                .join("\n");
             TextWrap::wrap_plain(&text, content_width)
         }
     }
@@ -1201,6 +1591,10 @@ enum Located {
     Spacer,
 }
 
+fn line_plain(line: &Line<'_>) -> String {
+    line.spans.iter().map(|s| s.content.as_ref()).collect()
+}
+
 fn upper_bound(sorted: &[u32], value: u32) -> usize {
     let mut lo = 0usize;
     let mut hi = sorted.len();
@@ -1215,19 +1609,6 @@ fn upper_bound(sorted: &[u32], value: u32) -> usize {
     lo
 }
 
-fn normalize_sel(a: (u32, u32), b: (u32, u32)) -> ((u32, u32), (u32, u32)) {
-    if a.0 < b.0 || (a.0 == b.0 && a.1 <= b.1) {
-        (a, b)
-    } else {
-        (b, a)
-    }
-}
-
-fn normalize_sel_inclusive(a: (u32, u32), b: (u32, u32)) -> ((u32, u32), (u32, u32)) {
-    let (start, end) = normalize_sel(a, b);
-    (start, (end.0, end.1.saturating_add(1)))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1310,6 +1691,14 @@ mod tests {
         let act = tv.handle_event(InputEvent::Key(KeyEvent::new(KeyCode::Char('f'))));
         assert_eq!(act, TranscriptAction::FollowTailToggled(true));
         assert!(tv.follow_tail_enabled());
+
+        // The `Component` impl reports the toggle too.
+        let area = Rect::new(0, 0, 20, 4);
+        let f = InputEvent::Key(KeyEvent::new(KeyCode::Char('f')));
+        let act = Component::handle_event(&mut tv, area, f);
+        assert_eq!(act, TranscriptAction::FollowTailToggled(false));
+        let act = Component::handle_event(&mut tv, area, InputEvent::Paste(String::new()));
+        assert_eq!(act, TranscriptAction::None);
     }
 
     #[test]
@@ -1323,11 +1712,139 @@ mod tests {
         tv.state.set_viewport(20, 5);
         tv.state.to_top();
         let ev = KeyEvent::new(KeyCode::Char('d')).with_modifiers(KeyModifiers {
-            shift: false,
             ctrl: true,
-            alt: false,
+            ..KeyModifiers::none()
         });
         let _ = tv.handle_event(InputEvent::Key(ev));
         assert!(tv.state.y > 0);
     }
+
+    #[test]
+    fn resize_keeps_followed_tail_at_bottom() {
+        let mut tv = TranscriptView::new();
+        for i in 0..50 {
+            tv.push_plain(
+                Role::Assistant,
+                &format!("entry {i} with a few words to wrap"),
+            );
+        }
+        let theme = Theme::default();
+        let wide = Rect::new(0, 0, 60, 8);
+        let mut buf = Buffer::empty(wide);
+        tv.render_ref(wide, &mut buf, &theme);
+        assert!(tv.is_at_bottom());
+
+        let _ = tv.handle_event(InputEvent::Resize {
+            width: 16,
+            height: 8,
+        });
+        let narrow = Rect::new(0, 0, 16, 8);
+        let mut buf = Buffer::empty(narrow);
+        tv.render_ref(narrow, &mut buf, &theme);
+        assert!(tv.is_at_bottom());
+    }
+
+    #[test]
+    fn resize_keeps_the_top_entry_in_place() {
+        let mut tv = TranscriptView::with_options(TranscriptViewOptions {
+            follow_tail: false,
+            ..Default::default()
+        });
+        for i in 0..50 {
+            tv.push_plain(
+                Role::Assistant,
+                &format!("entry {i} with a few words to wrap"),
+            );
+        }
+        let theme = Theme::default();
+        let wide = Rect::new(0, 0, 60, 8);
+        tv.render_ref(wide, &mut Buffer::empty(wide), &theme);
+        tv.state.y = 20;
+        assert!(tv.line_text(tv.state.y).starts_with("entry 10 "));
+
+        let _ = tv.handle_event(InputEvent::Resize {
+            width: 24,
+            height: 8,
+        });
+        let narrow = Rect::new(0, 0, 24, 8);
+        tv.render_ref(narrow, &mut Buffer::empty(narrow), &theme);
+        assert!(tv.line_text(tv.state.y).starts_with("entry 10"));
+        assert!(tv.state.y > 20);
+    }
+
+    #[test]
+    fn visual_line_mode_moves_the_view_and_yanks() {
+        let mut tv = TranscriptView::new();
+        for i in 0..10 {
+            tv.push_plain(Role::Assistant, &format!("entry {i}"));
+        }
+        let theme = Theme::default();
+        let area = Rect::new(0, 0, 40, 4);
+        tv.render_ref(area, &mut Buffer::empty(area), &theme);
+        let top = tv.state.y;
+        let expected = format!("{}\n{}", tv.line_text(top - 1), tv.line_text(top));
+
+        let key = |c| InputEvent::Key(crate::keymap::key_char(c));
+        assert_eq!(tv.handle_event_action(key('V')), SelectionAction::Redraw);
+        assert_eq!(tv.handle_event_action(key('k')), SelectionAction::Redraw);
+        assert_eq!(tv.state.y, top - 1);
+        assert!(!tv.follow_tail_pinned);
+        assert_eq!(
+            tv.handle_event_action(key('y')),
+            SelectionAction::CopyRequested(expected)
+        );
+        assert_eq!(tv.selection, None);
+    }
+
+    #[test]
+    fn trimming_old_entries_keeps_the_view_in_place() {
+        let mut tv = TranscriptView::with_options(TranscriptViewOptions {
+            follow_tail: false,
+            max_total_lines: Some(20),
+            ..Default::default()
+        });
+        for i in 0..10 {
+            tv.push_plain(Role::Assistant, &format!("entry {i}"));
+        }
+        let theme = Theme::default();
+        let area = Rect::new(0, 0, 40, 4);
+        tv.render_ref(area, &mut Buffer::empty(area), &theme);
+        tv.state.y = 10;
+        assert_eq!(tv.line_text(tv.state.y), "entry 5");
+
+        tv.push_plain(Role::Assistant, "entry 10");
+        tv.push_plain(Role::Assistant, "entry 11");
+        tv.render_ref(area, &mut Buffer::empty(area), &theme);
+        assert_eq!(tv.entries.len(), 10);
+        assert_eq!(tv.line_text(tv.state.y), "entry 5");
+    }
+
+    #[test]
+    fn double_click_selects_word_in_entry() {
+        let mut tv = TranscriptView::with_options(TranscriptViewOptions {
+            show_scrollbar: false,
+            follow_tail: false,
+            ..Default::default()
+        });
+        tv.push_plain(Role::User, "hello world");
+        let area = Rect::new(0, 0, 40, 4);
+        let mut buf = Buffer::empty(area);
+        tv.render_ref(area, &mut buf, &Theme::default());
+
+        let x = prefix_width(tv.gutter_width) + 8;
+        let t0 = Instant::now();
+        for n in 0..2 {
+            tv.handle_mouse_event_at(
+                area,
+                MouseEvent {
+                    x,
+                    y: 0,
+                    kind: MouseEventKind::Down(MouseButton::Left),
+                    modifiers: KeyModifiers::none(),
+                },
+                t0 + std::time::Duration::from_millis(50 * n),
+            );
+        }
+        assert_eq!(tv.selected_text().as_deref(), Some("world"));
+    }
 }